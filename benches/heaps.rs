@@ -0,0 +1,54 @@
+//! Heap Benchmark
+//! 
+//! Times the hand-written heaps from `queues` on identical inputs:
+//! - push every key then pop them all
+//! - meld one singleton heap per key into a single heap
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use dsa_practice::queues::binomial_heap::BinomialHeap;
+use dsa_practice::queues::dary_heap::DaryHeap;
+use dsa_practice::queues::heaps::{keys, meld_singletons, push_then_pop};
+use dsa_practice::queues::pairing_heap::PairingHeap;
+
+const SIZES: [usize; 2] = [1_000, 50_000];
+
+fn bench_push_then_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("heap push then pop");
+    for n in SIZES {
+        let input = keys(n, 0x5eed);
+        for d in [2, 4, 8] {
+            group.bench_with_input(BenchmarkId::new(format!("{}-ary", d), n), &input, |b, input| {
+                b.iter(|| push_then_pop(DaryHeap::new(d), input))
+            });
+        }
+        group.bench_with_input(BenchmarkId::new("pairing", n), &input, |b, input| {
+            b.iter(|| push_then_pop(PairingHeap::new(), input))
+        });
+        group.bench_with_input(BenchmarkId::new("binomial", n), &input, |b, input| {
+            b.iter(|| push_then_pop(BinomialHeap::new(), input))
+        });
+    }
+    group.finish();
+}
+
+fn bench_meld(c: &mut Criterion) {
+    let mut group = c.benchmark_group("heap meld singletons");
+    for n in SIZES {
+        let input = keys(n, 0x5eed);
+        for d in [2, 4, 8] {
+            group.bench_with_input(BenchmarkId::new(format!("{}-ary", d), n), &input, |b, input| {
+                b.iter(|| meld_singletons(|| DaryHeap::new(d), input))
+            });
+        }
+        group.bench_with_input(BenchmarkId::new("pairing", n), &input, |b, input| {
+            b.iter(|| meld_singletons(PairingHeap::new, input))
+        });
+        group.bench_with_input(BenchmarkId::new("binomial", n), &input, |b, input| {
+            b.iter(|| meld_singletons(BinomialHeap::new, input))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_push_then_pop, bench_meld);
+criterion_main!(benches);
@@ -0,0 +1,95 @@
+//! Ordered Map Benchmark
+//! 
+//! Times every `OrderedMap` in `trees` against `std::collections::BTreeMap`
+//! on identical pseudo-random keys:
+//! - insert into an empty map
+//! - look up keys, about half of them present
+//! - remove keys from a full map
+//! 
+//! A second group inserts keys in ascending order, the worst case for an
+//! unbalanced BST. A third runs the random phases on `BTree` at orders 3, 6,
+//! 16 and 64, and a fourth compares bulk loading sorted keys.
+
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkGroup, BenchmarkId, Criterion};
+use dsa_practice::trees::avl::AvlTree;
+use dsa_practice::trees::bst::Bst;
+use dsa_practice::trees::btree::BTree;
+use dsa_practice::trees::ordered_map::{get_keys, insert_keys, keys, remove_keys, OrderedMap};
+use dsa_practice::trees::red_black::LlrbTree;
+use dsa_practice::trees::treap::Treap;
+use std::collections::BTreeMap;
+
+const SIZES: [usize; 2] = [1_000, 20_000];
+
+/// Benchmark one map type's insert, get and remove phases on `n` keys.
+fn bench_map<M: OrderedMap<u64, u64>>(group: &mut BenchmarkGroup<'_, WallTime>, name: &str, n: usize, make: impl Fn() -> M) {
+    let input = keys(n, 0x5eed);
+    let probes = keys(n, 0xfeed);
+    let full = || {
+        let mut map = make();
+        insert_keys(&mut map, &input);
+        map
+    };
+    group.bench_function(BenchmarkId::new(format!("{} insert", name), n), |b| b.iter(full));
+    let map = full();
+    group.bench_function(BenchmarkId::new(format!("{} get", name), n), |b| b.iter(|| get_keys(&map, &probes)));
+    group.bench_function(BenchmarkId::new(format!("{} remove", name), n), |b| {
+        b.iter_batched(full, |mut map| remove_keys(&mut map, &probes), BatchSize::LargeInput)
+    });
+}
+
+fn bench_ordered_maps(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ordered maps");
+    for n in SIZES {
+        bench_map(&mut group, "std BTreeMap", n, BTreeMap::new);
+        bench_map(&mut group, "unbalanced BST", n, Bst::new);
+        bench_map(&mut group, "AVL tree", n, AvlTree::new);
+        bench_map(&mut group, "left-leaning red-black tree", n, LlrbTree::new);
+        bench_map(&mut group, "treap", n, Treap::new);
+    }
+    group.finish();
+}
+
+fn bench_sorted_inserts(c: &mut Criterion) {
+    fn insert<M: OrderedMap<u64, u64>>(mut map: M, input: &[u64]) -> M {
+        insert_keys(&mut map, input);
+        map
+    }
+    let mut group = c.benchmark_group("sorted inserts");
+    for n in [1_000u64, 5_000] {
+        let input: Vec<u64> = (0..n).collect();
+        group.bench_function(BenchmarkId::new("std BTreeMap", n), |b| b.iter(|| insert(BTreeMap::new(), &input)));
+        group.bench_function(BenchmarkId::new("unbalanced BST", n), |b| b.iter(|| insert(Bst::new(), &input)));
+        group.bench_function(BenchmarkId::new("AVL tree", n), |b| b.iter(|| insert(AvlTree::new(), &input)));
+        group.bench_function(BenchmarkId::new("left-leaning red-black tree", n), |b| b.iter(|| insert(LlrbTree::new(), &input)));
+        group.bench_function(BenchmarkId::new("treap", n), |b| b.iter(|| insert(Treap::new(), &input)));
+    }
+    group.finish();
+}
+
+fn bench_btree_orders(c: &mut Criterion) {
+    let mut group = c.benchmark_group("btree orders");
+    for n in SIZES {
+        bench_map(&mut group, "std BTreeMap", n, BTreeMap::new);
+        bench_map(&mut group, "BTree<3>", n, BTree::<u64, u64, 3>::new);
+        bench_map(&mut group, "BTree<6>", n, BTree::<u64, u64, 6>::new);
+        bench_map(&mut group, "BTree<16>", n, BTree::<u64, u64, 16>::new);
+        bench_map(&mut group, "BTree<64>", n, BTree::<u64, u64, 64>::new);
+    }
+    group.finish();
+}
+
+fn bench_bulk_load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("btree bulk load");
+    for n in [1_000u64, 100_000] {
+        let sorted = || (0..n).map(|key| (key, key));
+        group.bench_function(BenchmarkId::new("std BTreeMap", n), |b| b.iter(|| sorted().collect::<BTreeMap<_, _>>()));
+        group.bench_function(BenchmarkId::new("BTree<16>", n), |b| b.iter(|| BTree::<u64, u64, 16>::from_sorted(sorted())));
+        group.bench_function(BenchmarkId::new("BTree<64>", n), |b| b.iter(|| BTree::<u64, u64, 64>::from_sorted(sorted())));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ordered_maps, bench_sorted_inserts, bench_btree_orders, bench_bulk_load);
+criterion_main!(benches);
@@ -7,8 +7,10 @@
 //! - Container With Most Water
 //! - Trapping Rain Water
 //! - Sliding Window problems
+//! - Count of Inversions and Count of Smaller Numbers After Self (Fenwick tree)
 
 use crate::{measure_time, print_array, assert_result};
+use crate::trees::fenwick::FenwickTree;
 
 /// Problem: Two Sum
 /// Given an array of integers nums and an integer target, 
@@ -157,6 +159,43 @@ pub fn max_sliding_window(nums: &[i32], k: usize) -> Vec<i32> {
     result
 }
 
+/// Replace each value by its rank among the distinct values, so the ranks can index a Fenwick tree.
+fn compress_ranks(nums: &[i32]) -> Vec<usize> {
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    nums.iter().map(|num| sorted.binary_search(num).unwrap()).collect()
+}
+
+/// Problem: Count of Inversions
+/// Count the pairs `i < j` with `nums[i] > nums[j]`, in O(n log n): scanning
+/// left to right, a Fenwick tree over value ranks counts the earlier values
+/// that are greater than the current one.
+pub fn count_inversions(nums: &[i32]) -> u64 {
+    let ranks = compress_ranks(nums);
+    let mut seen = FenwickTree::new(ranks.len());
+    let mut inversions = 0;
+    for (i, &rank) in ranks.iter().enumerate() {
+        inversions += i as u64 - seen.prefix_sum(rank + 1);
+        seen.add(rank, 1);
+    }
+    inversions
+}
+
+/// Problem: Count of Smaller Numbers After Self
+/// For each element, count the strictly smaller elements to its right,
+/// scanning right to left with a Fenwick tree over value ranks.
+pub fn count_smaller(nums: &[i32]) -> Vec<usize> {
+    let ranks = compress_ranks(nums);
+    let mut seen = FenwickTree::new(ranks.len());
+    let mut counts = vec![0; ranks.len()];
+    for (i, &rank) in ranks.iter().enumerate().rev() {
+        counts[i] = seen.prefix_sum(rank);
+        seen.add(rank, 1);
+    }
+    counts
+}
+
 /// Run all array problem examples
 pub fn run_examples() {
     println!("Running Array Problem Examples...");
@@ -198,6 +237,18 @@ pub fn run_examples() {
     let result = measure_time("Max Sliding Window", || max_sliding_window(&nums, k));
     println!("Max Sliding Window: {:?}, k={} -> {:?}", nums, k, result);
     assert_result(result, vec![3, 3, 5, 5, 6, 7], "Max Sliding Window");
+    
+    // Count of Inversions
+    let nums = vec![8, 4, 2, 1];
+    let result = measure_time("Count Inversions", || count_inversions(&nums));
+    println!("Count Inversions: {:?} -> {}", nums, result);
+    assert_result(result, 6, "Count Inversions");
+    
+    // Count of Smaller Numbers After Self
+    let nums = vec![5, 2, 6, 1];
+    let result = measure_time("Count Smaller After Self", || count_smaller(&nums));
+    println!("Count Smaller After Self: {:?} -> {:?}", nums, result);
+    assert_result(result, vec![2, 1, 1, 0], "Count Smaller After Self");
 }
 
 #[cfg(test)]
@@ -243,4 +294,26 @@ mod tests {
         let nums = vec![1, 3, -1, -3, 5, 3, 6, 7];
         assert_eq!(max_sliding_window(&nums, 3), vec![3, 3, 5, 5, 6, 7]);
     }
+    
+    #[test]
+    fn test_count_inversions() {
+        assert_eq!(count_inversions(&[8, 4, 2, 1]), 6);
+        assert_eq!(count_inversions(&[3, 1, 2]), 2);
+        assert_eq!(count_inversions(&[1, 2, 3]), 0);
+        // Equal values are not inversions
+        assert_eq!(count_inversions(&[2, 2, 1, 1]), 4);
+        assert_eq!(count_inversions(&[]), 0);
+        
+        let nums: Vec<i32> = (0..200).map(|i| (i * 37 % 101) - 50).collect();
+        let brute = (0..nums.len()).flat_map(|i| (i + 1..nums.len()).map(move |j| (i, j))).filter(|&(i, j)| nums[i] > nums[j]).count();
+        assert_eq!(count_inversions(&nums), brute as u64);
+    }
+    
+    #[test]
+    fn test_count_smaller() {
+        assert_eq!(count_smaller(&[5, 2, 6, 1]), vec![2, 1, 1, 0]);
+        assert_eq!(count_smaller(&[-1, -1]), vec![0, 0]);
+        assert_eq!(count_smaller(&[i32::MAX, i32::MIN, 0]), vec![2, 0, 0]);
+        assert_eq!(count_smaller(&[]), Vec::<usize>::new());
+    }
 } 
\ No newline at end of file
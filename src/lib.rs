@@ -0,0 +1,54 @@
+//! DSA Practice - A comprehensive Rust project for practicing Data Structures and Algorithms
+//! 
+//! This project is organized by topics:
+//! - Arrays: Basic array operations, two pointers, sliding window
+//! - Linked Lists: Single, double, circular linked lists
+//! - Stacks: Stack operations, monotonic stack problems
+//! - Queues: Queue operations, priority queues
+//! - Trees: Binary trees, BST, AVL, B-trees
+//! - Graphs: DFS, BFS, shortest path algorithms
+//! - Sorting: Various sorting algorithms
+//! - Searching: Binary search, linear search
+//! - Dynamic Programming: Memoization, tabulation
+//! - Strings: String manipulation, pattern matching
+//! - Math: Mathematical algorithms and number theory
+
+pub mod arrays;
+pub mod linked_lists;
+pub mod stacks;
+pub mod queues;
+pub mod trees;
+pub mod graphs;
+pub mod sorting;
+pub mod searching;
+pub mod dynamic_programming;
+pub mod strings;
+pub mod math;
+pub mod notes;
+
+/// Utility function to measure execution time of algorithms
+pub fn measure_time<F, T>(name: &str, f: F) -> T 
+where 
+    F: FnOnce() -> T 
+{
+    use std::time::Instant;
+    let start = Instant::now();
+    let result = f();
+    let duration = start.elapsed();
+    println!("⏱️  {} took: {:?}", name, duration);
+    result
+}
+
+/// Utility function to print arrays in a nice format
+pub fn print_array<T: std::fmt::Display>(arr: &[T], name: &str) {
+    println!("{}: [{:?}]", name, arr.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
+}
+
+/// Utility function to compare results
+pub fn assert_result<T: PartialEq + std::fmt::Debug>(actual: T, expected: T, test_name: &str) {
+    if actual == expected {
+        println!("✅ {}: PASSED", test_name);
+    } else {
+        println!("❌ {}: FAILED - Expected {:?}, got {:?}", test_name, expected, actual);
+    }
+}
@@ -124,26 +124,26 @@ pub fn merge_two_lists(
 
 /// Problem: Remove Nth Node From End of List
 /// Remove the nth node from the end of the list and return its head.
+/// The list comes back unchanged when `n` is not in `1..=len`.
 pub fn remove_nth_from_end(head: Option<Box<ListNode>>, n: i32) -> Option<Box<ListNode>> {
+    let len = list_to_vec(&head).len();
+    let n = match usize::try_from(n) {
+        Ok(n) if (1..=len).contains(&n) => n,
+        _ => return head,
+    };
+    
     let mut dummy = Box::new(ListNode::new(0));
     dummy.next = head;
     
-    let mut first = &mut dummy;
-    let mut second = &mut dummy;
-    
-    // Move first pointer n+1 steps ahead
-    for _ in 0..=n {
-        first = first.next.as_mut().unwrap();
-    }
-    
-    // Move both pointers until first reaches end
-    while first.next.is_some() {
-        first = first.next.as_mut().unwrap();
-        second = second.next.as_mut().unwrap();
+    // Stop on the node just before the one to remove
+    let mut current = &mut dummy;
+    for _ in 0..len - n {
+        current = current.next.as_mut().unwrap();
     }
     
     // Remove the nth node
-    second.next = second.next.as_mut().unwrap().next.take();
+    let removed = current.next.take().unwrap();
+    current.next = removed.next;
     
     dummy.next
 }
@@ -171,8 +171,8 @@ pub fn add_two_numbers(
         current.next = Some(Box::new(ListNode::new(sum % 10)));
         current = current.next.as_mut().unwrap();
         
-        p1 = p1.as_ref().and_then(|node| &node.next);
-        p2 = p2.as_ref().and_then(|node| &node.next);
+        p1 = match p1 { Some(node) => &node.next, None => &None };
+        p2 = match p2 { Some(node) => &node.next, None => &None };
     }
     
     dummy.next
@@ -310,6 +310,17 @@ mod tests {
         let list = create_list(&[1, 2, 3, 4, 5]);
         let result = remove_nth_from_end(list, 2);
         assert_eq!(list_to_vec(&result), vec![1, 2, 3, 5]);
+        assert_eq!(list_to_vec(&remove_nth_from_end(create_list(&[1, 2]), 2)), vec![2]);
+        assert_eq!(list_to_vec(&remove_nth_from_end(create_list(&[1]), 1)), Vec::<i32>::new());
+    }
+    
+    #[test]
+    fn test_remove_nth_from_end_out_of_range() {
+        for n in [0, 4, -1] {
+            let result = remove_nth_from_end(create_list(&[1, 2, 3]), n);
+            assert_eq!(list_to_vec(&result), vec![1, 2, 3]);
+        }
+        assert_eq!(remove_nth_from_end(None, 1), None);
     }
     
     #[test]
@@ -108,6 +108,12 @@ impl LearningProgress {
     }
 }
 
+impl Default for LearningProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Personal Solution Template
 pub struct PersonalSolution {
     pub problem_name: String,
@@ -224,6 +230,12 @@ impl StudyPlan {
     }
 }
 
+impl Default for StudyPlan {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Problem Solving Strategies
 pub struct ProblemSolvingStrategies {
     strategies: HashMap<String, Vec<String>>,
@@ -292,6 +304,12 @@ impl ProblemSolvingStrategies {
     }
 }
 
+impl Default for ProblemSolvingStrategies {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Initialize default learning progress
 pub fn initialize_default_progress() -> LearningProgress {
     let mut progress = LearningProgress::new();
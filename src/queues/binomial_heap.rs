@@ -0,0 +1,170 @@
+//! Binomial Heap
+//! 
+//! A forest with at most one binomial tree of each order; a tree of order `k`
+//! holds `2^k` elements and its root's children have orders `0..k`. The forest
+//! mirrors the binary representation of `len`, so melding is binary addition
+//! with carries.
+//! - O(log n) push, pop, peek and meld
+
+use super::heaps::Heap;
+
+struct Node<T> {
+    value: T,
+    /// `children[i]` is a binomial tree of order `i`.
+    children: Vec<Box<Node<T>>>,
+}
+
+pub struct BinomialHeap<T> {
+    /// `trees[k]` is the tree of order `k`, if the forest has one.
+    trees: Vec<Option<Box<Node<T>>>>,
+    len: usize,
+}
+
+/// Combine two trees of the same order into one of the next order.
+fn link<T: Ord>(mut a: Box<Node<T>>, mut b: Box<Node<T>>) -> Box<Node<T>> {
+    if a.value <= b.value {
+        a.children.push(b);
+        a
+    } else {
+        b.children.push(a);
+        b
+    }
+}
+
+/// Add `other` into `trees` order by order, carrying linked trees upward.
+fn merge_forests<T: Ord>(trees: &mut Vec<Option<Box<Node<T>>>>, other: Vec<Option<Box<Node<T>>>>) {
+    let other_orders = other.len();
+    let mut other = other.into_iter();
+    let mut carry: Option<Box<Node<T>>> = None;
+    let mut order = 0;
+    
+    // Past the end of `other`, stop as soon as there is nothing left to carry
+    while order < other_orders || carry.is_some() {
+        if order == trees.len() {
+            trees.push(None);
+        }
+        let incoming = match (other.next().flatten(), carry.take()) {
+            (Some(x), Some(y)) => {
+                // Two incoming trees: link them and carry, leaving this order as it was
+                carry = Some(link(x, y));
+                None
+            }
+            (x, y) => x.or(y),
+        };
+        if let Some(tree) = incoming {
+            match trees[order].take() {
+                Some(existing) => carry = Some(link(existing, tree)),
+                None => trees[order] = Some(tree),
+            }
+        }
+        order += 1;
+    }
+    while matches!(trees.last(), Some(None)) {
+        trees.pop();
+    }
+}
+
+impl<T: Ord> BinomialHeap<T> {
+    pub fn new() -> Self {
+        BinomialHeap {
+            trees: Vec::new(),
+            len: 0,
+        }
+    }
+    
+    /// Orders of the trees currently in the forest, smallest first.
+    pub fn tree_orders(&self) -> Vec<usize> {
+        self.trees
+            .iter()
+            .enumerate()
+            .filter_map(|(order, tree)| tree.as_ref().map(|_| order))
+            .collect()
+    }
+    
+    fn min_order(&self) -> Option<usize> {
+        self.trees
+            .iter()
+            .enumerate()
+            .filter_map(|(order, tree)| tree.as_ref().map(|node| (order, &node.value)))
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map(|(order, _)| order)
+    }
+}
+
+impl<T: Ord> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Heap<T> for BinomialHeap<T> {
+    fn push(&mut self, value: T) {
+        let single = vec![Some(Box::new(Node {
+            value,
+            children: Vec::new(),
+        }))];
+        merge_forests(&mut self.trees, single);
+        self.len += 1;
+    }
+    
+    fn pop(&mut self) -> Option<T> {
+        let order = self.min_order()?;
+        let root = self.trees[order].take().expect("min_order points at a tree");
+        let Node { value, children } = *root;
+        let orphans = children.into_iter().map(Some).collect();
+        merge_forests(&mut self.trees, orphans);
+        self.len -= 1;
+        Some(value)
+    }
+    
+    fn peek(&self) -> Option<&T> {
+        self.min_order()
+            .and_then(|order| self.trees[order].as_ref())
+            .map(|node| &node.value)
+    }
+    
+    fn len(&self) -> usize {
+        self.len
+    }
+    
+    fn meld(&mut self, mut other: Self) {
+        merge_forests(&mut self.trees, std::mem::take(&mut other.trees));
+        self.len += other.len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_forest_matches_binary_length() {
+        let mut heap = BinomialHeap::new();
+        for x in 0..13 {
+            heap.push(x);
+        }
+        // 13 = 0b1101
+        assert_eq!(heap.tree_orders(), vec![0, 2, 3]);
+        heap.pop();
+        // 12 = 0b1100
+        assert_eq!(heap.tree_orders(), vec![2, 3]);
+    }
+    
+    #[test]
+    fn test_binomial_order_and_meld() {
+        let mut a = BinomialHeap::new();
+        let mut b = BinomialHeap::new();
+        for x in [7, 3, 9] {
+            a.push(x);
+        }
+        for x in [4, 1, 8, 2] {
+            b.push(x);
+        }
+        a.meld(b);
+        assert_eq!(a.len(), 7);
+        assert_eq!(a.tree_orders(), vec![0, 1, 2]);
+        assert_eq!(a.peek(), Some(&1));
+        let popped: Vec<i32> = std::iter::from_fn(|| a.pop()).collect();
+        assert_eq!(popped, vec![1, 2, 3, 4, 7, 8, 9]);
+    }
+}
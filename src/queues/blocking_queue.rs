@@ -0,0 +1,376 @@
+//! Bounded Blocking Queue
+//! 
+//! A fixed-capacity FIFO queue shared between threads, built from a `Mutex`
+//! around a `VecDeque` and two `Condvar`s (one for "not full", one for "not
+//! empty"). Pushes wait for space and pops wait for items, with blocking,
+//! non-blocking and timeout flavours.
+//! - `close` wakes every waiter: later pushes fail, pops drain what is left
+//! - Share it between threads with `Arc<BoundedBlockingQueue<T>>`
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Why a push did not happen. The rejected value is handed back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushError<T> {
+    /// The queue was full (`try_push`).
+    Full(T),
+    /// The queue stayed full until the deadline (`push_timeout`).
+    Timeout(T),
+    /// The queue has been closed.
+    Closed(T),
+}
+
+impl<T> PushError<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            PushError::Full(value) | PushError::Timeout(value) | PushError::Closed(value) => value,
+        }
+    }
+}
+
+impl<T> fmt::Display for PushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushError::Full(_) => write!(f, "queue is full"),
+            PushError::Timeout(_) => write!(f, "timed out waiting for space"),
+            PushError::Closed(_) => write!(f, "queue is closed"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for PushError<T> {}
+
+/// Why a pop returned no value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopError {
+    /// The queue was empty (`try_pop`).
+    Empty,
+    /// The queue stayed empty until the deadline (`pop_timeout`).
+    Timeout,
+    /// The queue is closed and fully drained.
+    Closed,
+}
+
+impl fmt::Display for PopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PopError::Empty => write!(f, "queue is empty"),
+            PopError::Timeout => write!(f, "timed out waiting for an item"),
+            PopError::Closed => write!(f, "queue is closed and empty"),
+        }
+    }
+}
+
+impl std::error::Error for PopError {}
+
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+}
+
+/// Problem: Design Bounded Blocking Queue
+/// A thread-safe FIFO queue holding at most `capacity` items.
+pub struct BoundedBlockingQueue<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+impl<T> BoundedBlockingQueue<T> {
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "a bounded queue needs a positive capacity");
+        BoundedBlockingQueue {
+            state: Mutex::new(State {
+                items: VecDeque::with_capacity(capacity),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+    
+    /// Push, waiting for space. Fails only if the queue is closed.
+    pub fn push(&self, value: T) -> Result<(), PushError<T>> {
+        let mut state = self.lock();
+        while state.items.len() == self.capacity && !state.closed {
+            state = self.not_full.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        self.push_locked(state, value)
+    }
+    
+    /// Push without waiting.
+    pub fn try_push(&self, value: T) -> Result<(), PushError<T>> {
+        let state = self.lock();
+        if state.items.len() == self.capacity && !state.closed {
+            return Err(PushError::Full(value));
+        }
+        self.push_locked(state, value)
+    }
+    
+    /// Push, waiting at most `timeout` for space.
+    pub fn push_timeout(&self, value: T, timeout: Duration) -> Result<(), PushError<T>> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        while state.items.len() == self.capacity && !state.closed {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(PushError::Timeout(value));
+            }
+            state = self.not_full.wait_timeout(state, remaining).unwrap_or_else(PoisonError::into_inner).0;
+        }
+        self.push_locked(state, value)
+    }
+    
+    /// Pop, waiting for an item. Returns `None` once the queue is closed and drained.
+    pub fn pop(&self) -> Option<T> {
+        let mut state = self.lock();
+        while state.items.is_empty() && !state.closed {
+            state = self.not_empty.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        self.pop_locked(state).ok()
+    }
+    
+    /// Pop without waiting.
+    pub fn try_pop(&self) -> Result<T, PopError> {
+        let state = self.lock();
+        if state.items.is_empty() && !state.closed {
+            return Err(PopError::Empty);
+        }
+        self.pop_locked(state)
+    }
+    
+    /// Pop, waiting at most `timeout` for an item.
+    pub fn pop_timeout(&self, timeout: Duration) -> Result<T, PopError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        while state.items.is_empty() && !state.closed {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(PopError::Timeout);
+            }
+            state = self.not_empty.wait_timeout(state, remaining).unwrap_or_else(PoisonError::into_inner).0;
+        }
+        self.pop_locked(state)
+    }
+    
+    /// Reject further pushes and wake every waiting thread. Items already queued can still be popped.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+    
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+    
+    pub fn len(&self) -> usize {
+        self.lock().items.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.lock().items.is_empty()
+    }
+    
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    
+    // No operation panics while holding the lock, so a poisoned state is still consistent
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+    
+    /// Called with space available or the queue closed.
+    fn push_locked(&self, mut state: MutexGuard<'_, State<T>>, value: T) -> Result<(), PushError<T>> {
+        if state.closed {
+            return Err(PushError::Closed(value));
+        }
+        state.items.push_back(value);
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+    
+    /// Called with an item available or the queue closed.
+    fn pop_locked(&self, mut state: MutexGuard<'_, State<T>>) -> Result<T, PopError> {
+        let value = state.items.pop_front().ok_or(PopError::Closed)?;
+        drop(state);
+        self.not_full.notify_one();
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    
+    #[test]
+    fn test_non_blocking_operations() {
+        let queue = BoundedBlockingQueue::new(2);
+        assert_eq!(queue.try_pop(), Err(PopError::Empty));
+        assert_eq!(queue.try_push(1), Ok(()));
+        assert_eq!(queue.try_push(2), Ok(()));
+        assert_eq!(queue.try_push(3), Err(PushError::Full(3)));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.try_pop(), Ok(1));
+        assert_eq!(queue.try_push(3), Ok(()));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+    }
+    
+    #[test]
+    fn test_timeouts() {
+        let queue = BoundedBlockingQueue::new(1);
+        assert_eq!(queue.pop_timeout(Duration::from_millis(10)), Err(PopError::Timeout));
+        queue.push(1).unwrap();
+        assert_eq!(queue.push_timeout(2, Duration::from_millis(10)), Err(PushError::Timeout(2)));
+        assert_eq!(queue.pop_timeout(Duration::from_millis(10)), Ok(1));
+    }
+    
+    #[test]
+    fn test_close_semantics() {
+        let queue = BoundedBlockingQueue::new(4);
+        queue.push("a").unwrap();
+        queue.push("b").unwrap();
+        queue.close();
+        assert!(queue.is_closed());
+        assert_eq!(queue.push("c"), Err(PushError::Closed("c")));
+        assert_eq!(queue.try_push("c").map_err(PushError::into_inner), Err("c"));
+        assert_eq!(queue.pop(), Some("a"));
+        assert_eq!(queue.try_pop(), Ok("b"));
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.try_pop(), Err(PopError::Closed));
+        assert_eq!(queue.pop_timeout(Duration::from_secs(1)), Err(PopError::Closed));
+    }
+    
+    #[test]
+    fn test_close_wakes_blocked_threads() {
+        let queue = Arc::new(BoundedBlockingQueue::<i32>::new(1));
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.pop())
+        };
+        queue.push(7).unwrap();
+        // Only returns once the consumer has taken 7, leaving the queue full again
+        queue.push(8).unwrap();
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.push(9))
+        };
+        thread::sleep(Duration::from_millis(20));
+        queue.close();
+        
+        assert_eq!(consumer.join().unwrap(), Some(7));
+        assert_eq!(producer.join().unwrap(), Err(PushError::Closed(9)));
+        assert_eq!(queue.pop(), Some(8));
+        assert_eq!(queue.pop(), None);
+    }
+    
+    #[test]
+    fn test_stress_many_producers_and_consumers() {
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        const PER_PRODUCER: usize = 10_000;
+        
+        let queue = Arc::new(BoundedBlockingQueue::new(16));
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        queue.push((p, i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut seen = Vec::new();
+                    while let Some(item) = queue.pop() {
+                        seen.push(item);
+                    }
+                    seen
+                })
+            })
+            .collect();
+        
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        queue.close();
+        
+        let mut all = Vec::new();
+        for consumer in consumers {
+            let seen = consumer.join().unwrap();
+            // Each consumer sees every producer's items in the order they were pushed
+            for p in 0..PRODUCERS {
+                let from_p: Vec<usize> = seen.iter().filter(|(q, _)| *q == p).map(|&(_, i)| i).collect();
+                assert!(from_p.windows(2).all(|w| w[0] < w[1]));
+            }
+            all.extend(seen);
+        }
+        all.sort_unstable();
+        let expected: Vec<(usize, usize)> = (0..PRODUCERS).flat_map(|p| (0..PER_PRODUCER).map(move |i| (p, i))).collect();
+        assert_eq!(all, expected);
+    }
+    
+    /// Every interleaving of a producer script and a consumer script, with `close`
+    /// inserted at every point, executed one operation at a time.
+    #[test]
+    fn test_all_interleavings_with_close() {
+        const PUSHES: usize = 4;
+        const POPS: usize = 4;
+        let steps = PUSHES + POPS;
+        
+        for schedule in 0u32..(1 << steps) {
+            if schedule.count_ones() as usize != PUSHES {
+                continue;
+            }
+            for close_at in 0..=steps {
+                let queue = BoundedBlockingQueue::new(2);
+                let mut model: VecDeque<usize> = VecDeque::new();
+                let mut closed = false;
+                let mut next_value = 0;
+                
+                for step in 0..steps {
+                    if step == close_at {
+                        queue.close();
+                        closed = true;
+                    }
+                    if schedule & (1 << step) != 0 {
+                        let result = queue.try_push(next_value);
+                        let expected = if closed {
+                            Err(PushError::Closed(next_value))
+                        } else if model.len() == 2 {
+                            Err(PushError::Full(next_value))
+                        } else {
+                            model.push_back(next_value);
+                            Ok(())
+                        };
+                        assert_eq!(result, expected);
+                        next_value += 1;
+                    } else {
+                        let expected = match model.pop_front() {
+                            Some(value) => Ok(value),
+                            None if closed => Err(PopError::Closed),
+                            None => Err(PopError::Empty),
+                        };
+                        assert_eq!(queue.try_pop(), expected);
+                    }
+                    assert_eq!(queue.len(), model.len());
+                }
+            }
+        }
+    }
+}
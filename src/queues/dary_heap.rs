@@ -0,0 +1,142 @@
+//! D-ary Heap
+//! 
+//! An implicit min-heap stored in a `Vec` where every node has up to `d`
+//! children. Node `i` has children `d*i + 1 ..= d*i + d` and parent `(i - 1) / d`.
+//! - Larger `d` makes the tree shallower: cheaper push, more comparisons per pop
+//! - `d = 2` is the classic binary heap
+
+use super::heaps::Heap;
+
+pub struct DaryHeap<T> {
+    data: Vec<T>,
+    d: usize,
+}
+
+impl<T: Ord> DaryHeap<T> {
+    /// An empty heap where each node has up to `d` children. Panics if `d < 2`.
+    pub fn new(d: usize) -> Self {
+        assert!(d >= 2, "a d-ary heap needs d >= 2, got {}", d);
+        DaryHeap { data: Vec::new(), d }
+    }
+    
+    /// Build a heap from `data` in O(n).
+    pub fn from_vec(d: usize, data: Vec<T>) -> Self {
+        let mut heap = Self::new(d);
+        heap.data = data;
+        heap.rebuild();
+        heap
+    }
+    
+    pub fn arity(&self) -> usize {
+        self.d
+    }
+    
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / self.d;
+            if self.data[index] >= self.data[parent] {
+                break;
+            }
+            self.data.swap(index, parent);
+            index = parent;
+        }
+    }
+    
+    fn sift_down(&mut self, mut index: usize) {
+        let len = self.data.len();
+        loop {
+            let first = self.d * index + 1;
+            if first >= len {
+                break;
+            }
+            let last = (first + self.d).min(len);
+            let smallest = (first..last)
+                .min_by(|&a, &b| self.data[a].cmp(&self.data[b]))
+                .expect("range is non-empty");
+            if self.data[smallest] >= self.data[index] {
+                break;
+            }
+            self.data.swap(index, smallest);
+            index = smallest;
+        }
+    }
+    
+    fn rebuild(&mut self) {
+        if self.data.len() < 2 {
+            return;
+        }
+        let last_parent = (self.data.len() - 2) / self.d;
+        for index in (0..=last_parent).rev() {
+            self.sift_down(index);
+        }
+    }
+}
+
+impl<T: Ord> Heap<T> for DaryHeap<T> {
+    fn push(&mut self, value: T) {
+        self.data.push(value);
+        self.sift_up(self.data.len() - 1);
+    }
+    
+    fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let value = self.data.swap_remove(0);
+        self.sift_down(0);
+        Some(value)
+    }
+    
+    fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+    
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    
+    fn meld(&mut self, other: Self) {
+        // Re-heapifying the concatenation is O(n + m), cheaper than m pushes when m is large
+        if other.data.len() * 4 < self.data.len() {
+            for value in other.data {
+                self.push(value);
+            }
+        } else {
+            self.data.extend(other.data);
+            self.rebuild();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_dary_heap_order() {
+        for d in 2..=5 {
+            let mut heap = DaryHeap::from_vec(d, vec![9, 4, 7, 1, 8, 2, 6, 3, 5, 0]);
+            assert_eq!(heap.arity(), d);
+            assert_eq!(heap.peek(), Some(&0));
+            heap.push(-1);
+            let popped: Vec<i32> = std::iter::from_fn(|| heap.pop()).collect();
+            assert_eq!(popped, (-1..10).collect::<Vec<_>>());
+        }
+    }
+    
+    #[test]
+    fn test_dary_meld() {
+        let mut a = DaryHeap::from_vec(3, vec![5, 1, 9]);
+        let b = DaryHeap::from_vec(3, vec![4, 0]);
+        a.meld(b);
+        assert_eq!(a.len(), 5);
+        assert_eq!(a.pop(), Some(0));
+        assert_eq!(a.pop(), Some(1));
+    }
+    
+    #[test]
+    #[should_panic(expected = "d >= 2")]
+    fn test_unary_heap_rejected() {
+        DaryHeap::<i32>::new(1);
+    }
+}
@@ -0,0 +1,390 @@
+//! Grid BFS
+//! 
+//! A rectangular `Grid<T>` with neighbor iteration and breadth-first search
+//! helpers, so grid problems only have to say which cells are sources and
+//! which are passable. None of the helpers modify the grid they search.
+//! - 4-connected, 8-connected or custom neighbor offsets
+//! - Multi-source BFS distances
+//! - Connected-component labeling
+//! - Island sizes, Rotting Oranges, Walls and Gates, Shortest Path in Binary Matrix
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A cell position as `(row, col)`.
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// Row `row` has `found` cells but the first row has `expected`.
+    RaggedRow { row: usize, expected: usize, found: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::RaggedRow { row, expected, found } => {
+                write!(f, "row {} has {} cells, expected {}", row, found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// Which cells count as neighbors of a cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left, right.
+    Four,
+    /// The four above plus the diagonals.
+    Eight,
+    /// Arbitrary `(row, col)` offsets, e.g. knight moves.
+    Custom(Vec<(isize, isize)>),
+}
+
+const FOUR: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const EIGHT: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+impl Connectivity {
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        match self {
+            Connectivity::Four => &FOUR,
+            Connectivity::Eight => &EIGHT,
+            Connectivity::Custom(offsets) => offsets,
+        }
+    }
+}
+
+/// Result of `Grid::label_components`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// Component index of each included cell, `None` for excluded cells.
+    pub labels: Grid<Option<usize>>,
+    /// Number of cells in each component, indexed by label.
+    pub sizes: Vec<usize>,
+}
+
+/// A rectangular grid stored row-major in one `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..rows * cols).map(|i| f((i / cols, i % cols))).collect();
+        Grid { rows, cols, cells }
+    }
+    
+    /// Build a grid from nested rows, which must all have the same length.
+    pub fn from_rows(rows: &[Vec<T>]) -> Result<Self, GridError>
+    where
+        T: Clone,
+    {
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some((row, found)) = rows.iter().map(Vec::len).enumerate().find(|&(_, len)| len != cols) {
+            return Err(GridError::RaggedRow { row, expected: cols, found });
+        }
+        Ok(Grid {
+            rows: rows.len(),
+            cols,
+            cells: rows.concat(),
+        })
+    }
+    
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+    
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        if row < self.rows && col < self.cols {
+            self.cells.get(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+    
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        if row < self.rows && col < self.cols {
+            self.cells.get_mut(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+    
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows * self.cols).map(move |i| (i / cols, i % cols))
+    }
+    
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+    
+    /// Convert back to nested rows.
+    pub fn to_rows(&self) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        if self.cols == 0 {
+            return vec![Vec::new(); self.rows];
+        }
+        self.cells.chunks(self.cols).map(<[T]>::to_vec).collect()
+    }
+    
+    /// In-bounds neighbors of `pos` under `connectivity`.
+    pub fn neighbors<'a>(&self, pos: Pos, connectivity: &'a Connectivity) -> impl Iterator<Item = Pos> + 'a {
+        let (rows, cols) = (self.rows, self.cols);
+        connectivity.offsets().iter().filter_map(move |&(dr, dc)| {
+            let row = pos.0.checked_add_signed(dr)?;
+            let col = pos.1.checked_add_signed(dc)?;
+            (row < rows && col < cols).then_some((row, col))
+        })
+    }
+    
+    /// Breadth-first distances from every source at once. Sources get distance 0;
+    /// the search only steps onto cells for which `passable` holds.
+    pub fn bfs_distances(
+        &self,
+        sources: impl IntoIterator<Item = Pos>,
+        connectivity: &Connectivity,
+        passable: impl Fn(Pos, &T) -> bool,
+    ) -> Grid<Option<usize>> {
+        let mut dist = Grid::from_fn(self.rows, self.cols, |_| None);
+        let mut queue = VecDeque::new();
+        for source in sources {
+            if let Some(slot @ None) = dist.get_mut(source) {
+                *slot = Some(0);
+                queue.push_back(source);
+            }
+        }
+        
+        while let Some(pos) = queue.pop_front() {
+            let next_dist = dist[pos].map(|d| d + 1);
+            for next in self.neighbors(pos, connectivity) {
+                if dist[next].is_none() && passable(next, &self[next]) {
+                    dist[next] = next_dist;
+                    queue.push_back(next);
+                }
+            }
+        }
+        dist
+    }
+    
+    /// Label the connected components formed by cells where `include` holds.
+    pub fn label_components(&self, connectivity: &Connectivity, include: impl Fn(&T) -> bool) -> Components {
+        let mut labels = Grid::from_fn(self.rows, self.cols, |_| None);
+        let mut sizes = Vec::new();
+        let mut queue = VecDeque::new();
+        
+        for start in self.positions() {
+            if labels[start].is_some() || !include(&self[start]) {
+                continue;
+            }
+            let label = sizes.len();
+            let mut size = 0;
+            labels[start] = Some(label);
+            queue.push_back(start);
+            while let Some(pos) = queue.pop_front() {
+                size += 1;
+                for next in self.neighbors(pos, connectivity) {
+                    if labels[next].is_none() && include(&self[next]) {
+                        labels[next] = Some(label);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+        Components { labels, sizes }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+    
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).expect("grid position out of bounds")
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos).expect("grid position out of bounds")
+    }
+}
+
+impl<T: Clone> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = GridError;
+    
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        Grid::from_rows(&rows)
+    }
+}
+
+/// Problem: Island Sizes
+/// Sizes of the 4-connected islands of `'1'` cells, largest first.
+pub fn island_sizes(grid: &Grid<char>) -> Vec<usize> {
+    let mut sizes = grid.label_components(&Connectivity::Four, |&cell| cell == '1').sizes;
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    sizes
+}
+
+/// Problem: Rotting Oranges
+/// `0` empty, `1` fresh, `2` rotten. Each minute rot spreads to adjacent fresh oranges;
+/// return the minutes until none are fresh, or -1 if some never rot.
+pub fn oranges_rotting(grid: &Grid<i32>) -> i32 {
+    let rotten = grid.positions().filter(|&pos| grid[pos] == 2);
+    let dist = grid.bfs_distances(rotten, &Connectivity::Four, |_, &cell| cell == 1);
+    
+    let mut minutes = 0;
+    for pos in grid.positions().filter(|&pos| grid[pos] == 1) {
+        match dist[pos] {
+            Some(d) => minutes = minutes.max(d as i32),
+            None => return -1,
+        }
+    }
+    minutes
+}
+
+/// Empty room marker used by Walls and Gates.
+pub const EMPTY_ROOM: i32 = i32::MAX;
+
+/// Problem: Walls and Gates
+/// `-1` wall, `0` gate, `EMPTY_ROOM` empty. Fill each empty room with the distance to
+/// its nearest gate, leaving unreachable rooms as `EMPTY_ROOM`.
+pub fn walls_and_gates(rooms: &mut Grid<i32>) {
+    let gates = rooms.positions().filter(|&pos| rooms[pos] == 0);
+    let dist = rooms.bfs_distances(gates, &Connectivity::Four, |_, &cell| cell == EMPTY_ROOM);
+    for pos in dist.positions() {
+        if let (Some(d), EMPTY_ROOM) = (dist[pos], rooms[pos]) {
+            rooms[pos] = d as i32;
+        }
+    }
+}
+
+/// Problem: Shortest Path in Binary Matrix
+/// Number of cells on the shortest 8-connected path of `0`s from the top-left to the
+/// bottom-right corner, or -1 if there is none.
+pub fn shortest_path_binary_matrix(grid: &Grid<i32>) -> i32 {
+    if grid.rows() == 0 || grid.cols() == 0 {
+        return -1;
+    }
+    let start = (0, 0);
+    let end = (grid.rows() - 1, grid.cols() - 1);
+    if grid[start] != 0 || grid[end] != 0 {
+        return -1;
+    }
+    let dist = grid.bfs_distances([start], &Connectivity::Eight, |_, &cell| cell == 0);
+    dist[end].map_or(-1, |d| d as i32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn char_grid(rows: &[&str]) -> Grid<char> {
+        let rows: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+        Grid::from_rows(&rows).unwrap()
+    }
+    
+    #[test]
+    fn test_from_rows_rejects_ragged() {
+        let rows = vec![vec![1, 2], vec![3]];
+        assert_eq!(Grid::from_rows(&rows), Err(GridError::RaggedRow { row: 1, expected: 2, found: 1 }));
+        let grid = Grid::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(grid[(1, 0)], 3);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_rows(), vec![vec![1, 2], vec![3, 4]]);
+    }
+    
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from_fn(3, 3, |_| 0);
+        assert_eq!(grid.neighbors((0, 0), &Connectivity::Four).count(), 2);
+        assert_eq!(grid.neighbors((1, 1), &Connectivity::Eight).count(), 8);
+        let knight = Connectivity::Custom(vec![(1, 2), (2, 1), (-1, 2), (2, -1)]);
+        assert_eq!(grid.neighbors((0, 0), &knight).collect::<Vec<_>>(), vec![(1, 2), (2, 1)]);
+    }
+    
+    #[test]
+    fn test_components_leave_input_intact() {
+        let grid = char_grid(&["11000", "11000", "00100", "00011"]);
+        let before = grid.clone();
+        let four = grid.label_components(&Connectivity::Four, |&c| c == '1');
+        let eight = grid.label_components(&Connectivity::Eight, |&c| c == '1');
+        assert_eq!(four.sizes, vec![4, 1, 2]);
+        assert_eq!(eight.sizes, vec![7]);
+        assert_eq!(four.labels[(3, 4)], Some(2));
+        assert_eq!(four.labels[(0, 4)], None);
+        assert_eq!(grid, before);
+        assert_eq!(island_sizes(&grid), vec![4, 2, 1]);
+    }
+    
+    #[test]
+    fn test_multi_source_distances() {
+        let grid = char_grid(&["S..", ".#.", "..S"]);
+        let sources = grid.positions().filter(|&pos| grid[pos] == 'S');
+        let dist = grid.bfs_distances(sources, &Connectivity::Four, |_, &c| c != '#');
+        assert_eq!(
+            dist.to_rows(),
+            vec![
+                vec![Some(0), Some(1), Some(2)],
+                vec![Some(1), None, Some(1)],
+                vec![Some(2), Some(1), Some(0)],
+            ]
+        );
+    }
+    
+    #[test]
+    fn test_oranges_rotting() {
+        let grid = Grid::try_from(vec![vec![2, 1, 1], vec![1, 1, 0], vec![0, 1, 1]]).unwrap();
+        assert_eq!(oranges_rotting(&grid), 4);
+        let grid = Grid::try_from(vec![vec![2, 1, 1], vec![0, 1, 1], vec![1, 0, 1]]).unwrap();
+        assert_eq!(oranges_rotting(&grid), -1);
+        let grid = Grid::try_from(vec![vec![0, 2]]).unwrap();
+        assert_eq!(oranges_rotting(&grid), 0);
+    }
+    
+    #[test]
+    fn test_walls_and_gates() {
+        const INF: i32 = EMPTY_ROOM;
+        let mut rooms = Grid::try_from(vec![
+            vec![INF, -1, 0, INF],
+            vec![INF, INF, INF, -1],
+            vec![INF, -1, INF, -1],
+            vec![0, -1, INF, INF],
+        ])
+        .unwrap();
+        walls_and_gates(&mut rooms);
+        assert_eq!(
+            rooms.to_rows(),
+            vec![vec![3, -1, 0, 1], vec![2, 2, 1, -1], vec![1, -1, 2, -1], vec![0, -1, 3, 4]]
+        );
+    }
+    
+    #[test]
+    fn test_shortest_path_binary_matrix() {
+        let grid = Grid::try_from(vec![vec![0, 0, 0], vec![1, 1, 0], vec![1, 1, 0]]).unwrap();
+        assert_eq!(shortest_path_binary_matrix(&grid), 4);
+        let grid = Grid::try_from(vec![vec![0, 1], vec![1, 0]]).unwrap();
+        assert_eq!(shortest_path_binary_matrix(&grid), 2);
+        let grid = Grid::try_from(vec![vec![1, 0], vec![0, 0]]).unwrap();
+        assert_eq!(shortest_path_binary_matrix(&grid), -1);
+        let grid = Grid::try_from(vec![vec![0]]).unwrap();
+        assert_eq!(shortest_path_binary_matrix(&grid), 1);
+    }
+}
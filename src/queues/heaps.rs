@@ -0,0 +1,163 @@
+//! Heap Family
+//! 
+//! A common interface for the hand-written heaps in this module, plus the
+//! shared workloads that `benches/heaps.rs` times and `heaps_agree` cross-checks.
+//! - `DaryHeap`: implicit d-ary heap in a `Vec`, O(n + m) meld
+//! - `PairingHeap`: multi-way tree with O(1) push and meld, amortized O(log n) pop
+//! - `BinomialHeap`: forest of binomial trees, O(log n) push, pop and meld
+
+use super::binomial_heap::BinomialHeap;
+use super::dary_heap::DaryHeap;
+use super::pairing_heap::PairingHeap;
+
+/// A min-heap: `pop` and `peek` return the smallest element.
+pub trait Heap<T: Ord> {
+    fn push(&mut self, value: T);
+    
+    fn pop(&mut self) -> Option<T>;
+    
+    fn peek(&self) -> Option<&T>;
+    
+    fn len(&self) -> usize;
+    
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Move every element of `other` into `self`.
+    fn meld(&mut self, other: Self)
+    where
+        Self: Sized;
+}
+
+/// Deterministic pseudo-random keys (xorshift) so every heap sees the same input.
+pub fn keys(n: usize, seed: u64) -> Vec<u64> {
+    let mut state = seed | 1;
+    (0..n)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % 1_000_000
+        })
+        .collect()
+}
+
+/// An order-sensitive checksum of a key sequence.
+fn checksum(keys: impl IntoIterator<Item = u64>) -> u64 {
+    keys.into_iter().fold(0u64, |checksum, key| checksum.wrapping_mul(31).wrapping_add(key))
+}
+
+/// Push every key, then pop them all. Returns a checksum of the popped
+/// sequence, which matches `checksum` of the sorted input exactly when the
+/// heap popped in order.
+pub fn push_then_pop<H: Heap<u64>>(mut heap: H, input: &[u64]) -> u64 {
+    for &key in input {
+        heap.push(key);
+    }
+    checksum(std::iter::from_fn(|| heap.pop()))
+}
+
+/// Build one singleton heap per key and meld them pairwise into one, like a
+/// tournament. Returns the size of the final heap.
+pub fn meld_singletons<H: Heap<u64>>(make: impl Fn() -> H, input: &[u64]) -> usize {
+    let mut heaps: Vec<H> = input
+        .iter()
+        .map(|&key| {
+            let mut heap = make();
+            heap.push(key);
+            heap
+        })
+        .collect();
+    while heaps.len() > 1 {
+        let mut next = Vec::with_capacity(heaps.len() / 2 + 1);
+        let mut iter = heaps.into_iter();
+        while let Some(mut left) = iter.next() {
+            if let Some(right) = iter.next() {
+                left.meld(right);
+            }
+            next.push(left);
+        }
+        heaps = next;
+    }
+    heaps.pop().map_or(0, |heap| heap.len())
+}
+
+/// Run both workloads on one heap type, returning the pop checksum, or `None`
+/// if melding lost elements.
+fn run_workloads<H: Heap<u64>>(make: impl Fn() -> H, input: &[u64]) -> Option<u64> {
+    let checksum = push_then_pop(make(), input);
+    (meld_singletons(make, input) == input.len()).then_some(checksum)
+}
+
+/// Problem: Heap Agreement
+/// Run every heap implementation through the same workloads on `n` keys; all
+/// must pop the keys in sorted order. Timings live in `benches/heaps.rs`.
+pub fn heaps_agree(n: usize) -> bool {
+    let input = keys(n, 0x5eed);
+    let mut sorted = input.clone();
+    sorted.sort_unstable();
+    let expected = Some(checksum(sorted));
+    let results = [
+        run_workloads(|| DaryHeap::new(2), &input),
+        run_workloads(|| DaryHeap::new(4), &input),
+        run_workloads(|| DaryHeap::new(8), &input),
+        run_workloads(PairingHeap::new, &input),
+        run_workloads(BinomialHeap::new, &input),
+    ];
+    results.iter().all(|&result| result == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    /// Apply the same operations to a heap and a sorted `Vec` reference.
+    fn check_against_reference<H: Heap<i32>>(make: impl Fn() -> H, ops: &[(u8, i32)]) -> Result<(), TestCaseError> {
+        let mut heap = make();
+        let mut reference: Vec<i32> = Vec::new();
+        for &(op, value) in ops {
+            match op % 4 {
+                0 | 1 => {
+                    heap.push(value);
+                    reference.push(value);
+                }
+                2 => {
+                    reference.sort_unstable_by(|a, b| b.cmp(a));
+                    prop_assert_eq!(heap.pop(), reference.pop());
+                }
+                _ => {
+                    let mut other = make();
+                    for offset in 0..3 {
+                        other.push(value.wrapping_add(offset));
+                        reference.push(value.wrapping_add(offset));
+                    }
+                    heap.meld(other);
+                }
+            }
+            prop_assert_eq!(heap.len(), reference.len());
+            prop_assert_eq!(heap.peek().copied(), reference.iter().min().copied());
+        }
+        reference.sort_unstable();
+        let drained: Vec<i32> = std::iter::from_fn(|| heap.pop()).collect();
+        prop_assert_eq!(drained, reference);
+        prop_assert!(heap.is_empty());
+        Ok(())
+    }
+    
+    #[test]
+    fn test_heaps_agree() {
+        assert!(heaps_agree(500));
+        assert!(heaps_agree(0));
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_heaps_match_sorted_reference(ops in proptest::collection::vec((any::<u8>(), any::<i32>()), 0..200), d in 2usize..6) {
+            check_against_reference(|| DaryHeap::new(d), &ops)?;
+            check_against_reference(PairingHeap::new, &ops)?;
+            check_against_reference(BinomialHeap::new, &ops)?;
+        }
+    }
+}
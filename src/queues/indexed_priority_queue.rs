@@ -0,0 +1,291 @@
+//! Indexed Priority Queue
+//! 
+//! A binary heap of `(key, priority)` pairs plus a map from each key to its
+//! slot in the heap. Knowing where a key lives lets us change its priority or
+//! remove it in O(log n), which is what Dijkstra and schedulers need.
+//! - `push`, `pop`, `change_priority`, `remove` in O(log n)
+//! - `contains` and `priority` lookups in O(1)
+
+use super::priority_queue::HeapOrder;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Problem: Indexed Priority Queue (decrease-key)
+/// Each key appears at most once; its priority can be updated in place.
+pub struct IndexedPriorityQueue<K, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+    order: HeapOrder,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedPriorityQueue<K, P> {
+    /// An empty queue that pops the largest priority first.
+    pub fn new() -> Self {
+        Self::with_order(HeapOrder::Max)
+    }
+    
+    /// An empty queue that pops the smallest priority first.
+    pub fn new_min() -> Self {
+        Self::with_order(HeapOrder::Min)
+    }
+    
+    pub fn with_order(order: HeapOrder) -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+            order,
+        }
+    }
+    
+    /// Insert `key`, or update its priority if it is already queued.
+    /// Returns the previous priority in the latter case.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if self.positions.contains_key(&key) {
+            return self.change_priority(&key, priority);
+        }
+        let index = self.heap.len();
+        self.positions.insert(key.clone(), index);
+        self.heap.push((key, priority));
+        self.sift_up(index);
+        None
+    }
+    
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        Some(self.remove_at(0))
+    }
+    
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(key, priority)| (key, priority))
+    }
+    
+    /// Set a new priority for `key`, returning the old one, or `None` if `key` is not queued.
+    pub fn change_priority(&mut self, key: &K, priority: P) -> Option<P> {
+        let index = *self.positions.get(key)?;
+        let old = std::mem::replace(&mut self.heap[index].1, priority);
+        self.repair(index);
+        Some(old)
+    }
+    
+    /// Remove `key` from the queue, returning its priority.
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let index = *self.positions.get(key)?;
+        Some(self.remove_at(index).1)
+    }
+    
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+    
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|&index| &self.heap[index].1)
+    }
+    
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    
+    /// Swap the last entry into `index`, pop the removed entry and restore the heap.
+    fn remove_at(&mut self, index: usize) -> (K, P) {
+        let last = self.heap.len() - 1;
+        self.swap(index, last);
+        let (key, priority) = self.heap.pop().expect("remove_at is only called on a non-empty heap");
+        self.positions.remove(&key);
+        if index < self.heap.len() {
+            self.repair(index);
+        }
+        (key, priority)
+    }
+    
+    /// Move the entry at `index` up or down, whichever direction its priority requires.
+    fn repair(&mut self, index: usize) {
+        if index > 0 && self.higher(index, (index - 1) / 2) {
+            self.sift_up(index);
+        } else {
+            self.sift_down(index);
+        }
+    }
+    
+    fn higher(&self, a: usize, b: usize) -> bool {
+        let ordering = self.heap[a].1.cmp(&self.heap[b].1);
+        let ordering = match self.order {
+            HeapOrder::Max => ordering,
+            HeapOrder::Min => ordering.reverse(),
+        };
+        ordering == Ordering::Greater
+    }
+    
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        *self.positions.get_mut(&self.heap[a].0).expect("every heap entry has a position") = a;
+        *self.positions.get_mut(&self.heap[b].0).expect("every heap entry has a position") = b;
+    }
+    
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.higher(index, parent) {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+    
+    fn sift_down(&mut self, mut index: usize) {
+        let len = self.heap.len();
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut best = index;
+            if left < len && self.higher(left, best) {
+                best = left;
+            }
+            if right < len && self.higher(right, best) {
+                best = right;
+            }
+            if best == index {
+                break;
+            }
+            self.swap(index, best);
+            index = best;
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Problem: Dijkstra's Shortest Paths
+/// Distances from `source` over a weighted adjacency list, using decrease-key.
+/// A path whose length would overflow `u64` is never taken.
+pub fn dijkstra(graph: &[Vec<(usize, u64)>], source: usize) -> Vec<Option<u64>> {
+    let mut dist = vec![None; graph.len()];
+    let mut queue = IndexedPriorityQueue::new_min();
+    queue.push(source, 0u64);
+    
+    while let Some((node, d)) = queue.pop() {
+        dist[node] = Some(d);
+        for &(next, weight) in &graph[node] {
+            if dist[next].is_some() {
+                continue;
+            }
+            let Some(candidate) = d.checked_add(weight) else { continue };
+            match queue.priority(&next) {
+                Some(&current) if current <= candidate => {}
+                _ => {
+                    queue.push(next, candidate);
+                }
+            }
+        }
+    }
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_change_priority_and_remove() {
+        let mut queue = IndexedPriorityQueue::new_min();
+        for (key, priority) in [("a", 5), ("b", 3), ("c", 8), ("d", 1)] {
+            queue.push(key, priority);
+        }
+        assert_eq!(queue.peek(), Some((&"d", &1)));
+        
+        assert_eq!(queue.change_priority(&"c", 0), Some(8));
+        assert_eq!(queue.peek(), Some((&"c", &0)));
+        assert_eq!(queue.push("c", 9), Some(0));
+        
+        assert_eq!(queue.remove(&"d"), Some(1));
+        assert!(!queue.contains(&"d"));
+        assert_eq!(queue.remove(&"d"), None);
+        assert_eq!(queue.change_priority(&"z", 1), None);
+        
+        let order: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(order, vec![("b", 3), ("a", 5), ("c", 9)]);
+        assert!(queue.is_empty());
+    }
+    
+    #[test]
+    fn test_max_order() {
+        let mut queue = IndexedPriorityQueue::new();
+        queue.push(1, 10);
+        queue.push(2, 20);
+        queue.change_priority(&1, 30);
+        assert_eq!(queue.pop(), Some((1, 30)));
+        assert_eq!(queue.pop(), Some((2, 20)));
+        assert_eq!(queue.pop(), None);
+    }
+    
+    #[test]
+    fn test_dijkstra() {
+        // 0 -> 1 (4), 0 -> 2 (1), 2 -> 1 (2), 1 -> 3 (1), 2 -> 3 (5); node 4 unreachable
+        let graph = vec![
+            vec![(1, 4), (2, 1)],
+            vec![(3, 1)],
+            vec![(1, 2), (3, 5)],
+            vec![],
+            vec![(0, 1)],
+        ];
+        assert_eq!(dijkstra(&graph, 0), vec![Some(0), Some(3), Some(1), Some(4), None]);
+    }
+    
+    #[test]
+    fn test_dijkstra_skips_overflowing_paths() {
+        // 0 -> 1 -> 2 would overflow; 0 -> 2 directly is the only usable path
+        let graph = vec![vec![(1, u64::MAX - 1), (2, u64::MAX)], vec![(2, 5)], vec![]];
+        assert_eq!(dijkstra(&graph, 0), vec![Some(0), Some(u64::MAX - 1), Some(u64::MAX)]);
+        let graph = vec![vec![(1, u64::MAX)], vec![(2, 1)], vec![]];
+        assert_eq!(dijkstra(&graph, 0), vec![Some(0), Some(u64::MAX), None]);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_sorted_reference(ops in proptest::collection::vec((0u8..4, 0u8..16, any::<i16>()), 0..300)) {
+            let mut queue = IndexedPriorityQueue::new_min();
+            let mut model: HashMap<u8, i16> = HashMap::new();
+            
+            for (op, key, priority) in ops {
+                match op {
+                    0 => prop_assert_eq!(queue.push(key, priority), model.insert(key, priority)),
+                    1 => {
+                        let expected = model.get_mut(&key).map(|p| std::mem::replace(p, priority));
+                        prop_assert_eq!(queue.change_priority(&key, priority), expected);
+                    }
+                    2 => prop_assert_eq!(queue.remove(&key), model.remove(&key)),
+                    _ => {
+                        // Any key holding the minimum priority is a valid answer
+                        let mut reference: Vec<(i16, u8)> = model.iter().map(|(&k, &p)| (p, k)).collect();
+                        reference.sort();
+                        match queue.pop() {
+                            Some((k, p)) => {
+                                prop_assert_eq!(p, reference[0].0);
+                                prop_assert_eq!(model.remove(&k), Some(p));
+                            }
+                            None => prop_assert!(reference.is_empty()),
+                        }
+                    }
+                }
+                prop_assert_eq!(queue.len(), model.len());
+            }
+            
+            let mut expected: Vec<i16> = model.values().copied().collect();
+            expected.sort();
+            let drained: Vec<i16> = std::iter::from_fn(|| queue.pop()).map(|(_, p)| p).collect();
+            prop_assert_eq!(drained, expected);
+        }
+    }
+}
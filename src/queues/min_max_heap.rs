@@ -0,0 +1,217 @@
+//! Min-Max Heap
+//! 
+//! A double-ended priority queue in one implicit tree. Levels alternate: nodes
+//! on even levels (the root is level 0) are no larger than anything below
+//! them, nodes on odd levels are no smaller. So the minimum is the root and
+//! the maximum is one of its two children.
+//! - O(1) `peek_min` / `peek_max`
+//! - O(log n) `push`, `pop_min`, `pop_max`
+
+/// Problem: Double-Ended Priority Queue
+/// Efficient access to both the smallest and the largest element.
+pub struct DoubleEndedPriorityQueue<T> {
+    data: Vec<T>,
+}
+
+fn is_min_level(index: usize) -> bool {
+    (index + 1).ilog2().is_multiple_of(2)
+}
+
+impl<T: Ord> DoubleEndedPriorityQueue<T> {
+    pub fn new() -> Self {
+        DoubleEndedPriorityQueue { data: Vec::new() }
+    }
+    
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+        let index = self.data.len() - 1;
+        if index == 0 {
+            return;
+        }
+        
+        // A new leaf may belong on the opposite kind of level: first compare with the parent
+        let parent = (index - 1) / 2;
+        if is_min_level(index) {
+            if self.data[index] > self.data[parent] {
+                self.data.swap(index, parent);
+                self.bubble_up(parent, true);
+            } else {
+                self.bubble_up(index, false);
+            }
+        } else if self.data[index] < self.data[parent] {
+            self.data.swap(index, parent);
+            self.bubble_up(parent, false);
+        } else {
+            self.bubble_up(index, true);
+        }
+    }
+    
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(self.remove_at(0))
+    }
+    
+    pub fn pop_max(&mut self) -> Option<T> {
+        let index = self.max_index()?;
+        Some(self.remove_at(index))
+    }
+    
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+    
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|index| &self.data[index])
+    }
+    
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    
+    fn max_index(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(if self.data[1] >= self.data[2] { 1 } else { 2 }),
+        }
+    }
+    
+    fn remove_at(&mut self, index: usize) -> T {
+        let value = self.data.swap_remove(index);
+        if index < self.data.len() {
+            self.trickle_down(index);
+        }
+        value
+    }
+    
+    /// `true` if `a` belongs above `b` on a max level (`max`) or a min level (`!max`).
+    fn before(&self, a: usize, b: usize, max: bool) -> bool {
+        if max {
+            self.data[a] > self.data[b]
+        } else {
+            self.data[a] < self.data[b]
+        }
+    }
+    
+    /// Move `index` up through grandparents, which are on the same kind of level.
+    fn bubble_up(&mut self, mut index: usize, max: bool) {
+        while index >= 3 {
+            let grandparent = ((index - 1) / 2 - 1) / 2;
+            if !self.before(index, grandparent, max) {
+                break;
+            }
+            self.data.swap(index, grandparent);
+            index = grandparent;
+        }
+    }
+    
+    fn trickle_down(&mut self, mut index: usize) {
+        let max = !is_min_level(index);
+        loop {
+            // The best of the children and grandchildren of `index`
+            let first_child = 2 * index + 1;
+            let first_grandchild = 4 * index + 3;
+            let candidates = (first_child..(first_child + 2))
+                .chain(first_grandchild..(first_grandchild + 4))
+                .filter(|&i| i < self.data.len());
+            let best = candidates.reduce(|best, i| if self.before(i, best, max) { i } else { best });
+            let Some(best) = best else {
+                return;
+            };
+            if !self.before(best, index, max) {
+                return;
+            }
+            
+            self.data.swap(best, index);
+            if best < first_grandchild {
+                return;
+            }
+            
+            // The displaced value may now be out of order with its new parent on the other kind of level
+            let parent = (best - 1) / 2;
+            if self.before(parent, best, max) {
+                self.data.swap(parent, best);
+            }
+            index = best;
+        }
+    }
+}
+
+impl<T: Ord> Default for DoubleEndedPriorityQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for DoubleEndedPriorityQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        for value in iter {
+            queue.push(value);
+        }
+        queue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_both_ends() {
+        let mut queue: DoubleEndedPriorityQueue<i32> = [5, 1, 9, 3, 7, 2, 8].into_iter().collect();
+        assert_eq!(queue.peek_min(), Some(&1));
+        assert_eq!(queue.peek_max(), Some(&9));
+        assert_eq!(queue.pop_max(), Some(9));
+        assert_eq!(queue.pop_min(), Some(1));
+        assert_eq!(queue.pop_max(), Some(8));
+        assert_eq!(queue.pop_min(), Some(2));
+        assert_eq!(queue.len(), 3);
+    }
+    
+    #[test]
+    fn test_small_sizes() {
+        let mut queue = DoubleEndedPriorityQueue::new();
+        assert_eq!(queue.pop_max(), None);
+        queue.push(4);
+        assert_eq!(queue.peek_max(), Some(&4));
+        assert_eq!(queue.peek_min(), Some(&4));
+        queue.push(2);
+        assert_eq!(queue.pop_max(), Some(4));
+        assert_eq!(queue.pop_max(), Some(2));
+        assert!(queue.is_empty());
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_sorted_vec(ops in proptest::collection::vec((0u8..3, any::<i16>()), 0..300)) {
+            let mut queue = DoubleEndedPriorityQueue::new();
+            let mut reference: Vec<i16> = Vec::new();
+            for (op, value) in ops {
+                match op {
+                    0 => {
+                        queue.push(value);
+                        let at = reference.partition_point(|&x| x < value);
+                        reference.insert(at, value);
+                    }
+                    1 => {
+                        let expected = if reference.is_empty() { None } else { Some(reference.remove(0)) };
+                        prop_assert_eq!(queue.pop_min(), expected);
+                    }
+                    _ => prop_assert_eq!(queue.pop_max(), reference.pop()),
+                }
+                prop_assert_eq!(queue.peek_min(), reference.first());
+                prop_assert_eq!(queue.peek_max(), reference.last());
+                prop_assert_eq!(queue.len(), reference.len());
+            }
+        }
+    }
+}
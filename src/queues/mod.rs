@@ -2,191 +2,308 @@
 //! 
 //! This module contains common queue problems and their solutions:
 //! - Implement Queue using Stacks
-//! - Implement Stack using Queues
+//! - Implement Stack using Queues (two-queue and single-queue variants)
 //! - Circular Queue
-//! - Priority Queue
+//! - Priority Queue (max/min, key and comparator ordering)
+//! - Indexed Priority Queue (decrease-key) and Dijkstra
+//! - Hand-written heaps: d-ary, pairing and binomial, benchmarked in `benches/heaps.rs`
+//! - Double-ended priority queue (min-max heap)
+//! - Running median and Sliding Window Median
 //! - Sliding Window Maximum
 //! - BFS problems
+//! - Generic grid BFS: distances, components, rotting oranges, walls and gates
+//! - Implicit-state BFS (one- and bidirectional) behind Open the Lock and Perfect Squares
+//! - Thread-safe bounded blocking queue and SPSC ring buffer
+//! - Rate limiters (sliding log, token bucket), hit counter and fair schedulers
+//! - Persistent (immutable) Queue
+//! - Generic Ring Buffer (overwrite and growable modes, circular deque)
+
+pub mod binomial_heap;
+pub mod blocking_queue;
+pub mod dary_heap;
+pub mod grid;
+pub mod heaps;
+pub mod indexed_priority_queue;
+pub mod min_max_heap;
+pub mod pairing_heap;
+pub mod persistent_queue;
+pub mod priority_queue;
+pub mod rate_limit;
+pub mod ring_buffer;
+pub mod running_median;
+pub mod scheduler;
+pub mod spsc;
+pub mod state_bfs;
 
 use crate::{measure_time, assert_result};
-use std::collections::{VecDeque, BinaryHeap, HashMap};
+use binomial_heap::BinomialHeap;
+use blocking_queue::BoundedBlockingQueue;
+use dary_heap::DaryHeap;
+use grid::{island_sizes, oranges_rotting, shortest_path_binary_matrix, walls_and_gates, Connectivity, Grid, EMPTY_ROOM};
+use heaps::{heaps_agree, Heap};
+use indexed_priority_queue::{dijkstra, IndexedPriorityQueue};
+use min_max_heap::DoubleEndedPriorityQueue;
+use pairing_heap::PairingHeap;
+use persistent_queue::PersistentQueue;
+pub use priority_queue::PriorityQueue;
+use priority_queue::{HeapOrder, TotalF64};
+use rate_limit::{HitCounter, MockClock, SlidingLogLimiter, TokenBucket};
+use ring_buffer::{OverflowPolicy, RingBuffer};
+use running_median::{median_sliding_window, RunningMedian};
+use scheduler::{RoundRobinScheduler, WeightedFairScheduler};
+pub use state_bfs::{num_squares, open_lock};
+use state_bfs::open_lock_sequence;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 /// Problem: Implement Queue using Stacks
 /// Implement a first in first out (FIFO) queue using only two stacks.
-pub struct MyQueue {
-    input: Vec<i32>,
-    output: Vec<i32>,
+/// Each element is moved from `input` to `output` at most once, so every
+/// operation is O(1) amortized; `stats` exposes the counts behind that bound.
+pub struct MyQueue<T> {
+    input: Vec<T>,
+    output: Vec<T>,
+    stats: QueueStats,
+}
+
+/// Operation counters for `MyQueue`, used to illustrate amortized analysis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    pub pushes: usize,
+    pub pops: usize,
+    /// Elements moved from the input stack to the output stack.
+    pub transfers: usize,
+    /// Times the output stack was refilled.
+    pub refills: usize,
+}
+
+impl QueueStats {
+    /// Average number of stack operations per queue operation.
+    pub fn amortized_cost(&self) -> f64 {
+        let operations = self.pushes + self.pops;
+        if operations == 0 {
+            0.0
+        } else {
+            // Each push and pop touches a stack once, each transfer touches both
+            (operations + 2 * self.transfers) as f64 / operations as f64
+        }
+    }
 }
 
-impl MyQueue {
+impl<T> MyQueue<T> {
     pub fn new() -> Self {
         MyQueue {
             input: Vec::new(),
             output: Vec::new(),
+            stats: QueueStats::default(),
         }
     }
     
-    pub fn push(&mut self, x: i32) {
+    pub fn push(&mut self, x: T) {
+        self.stats.pushes += 1;
         self.input.push(x);
     }
     
-    pub fn pop(&mut self) -> i32 {
-        if self.output.is_empty() {
-            while let Some(x) = self.input.pop() {
-                self.output.push(x);
-            }
+    pub fn pop(&mut self) -> Option<T> {
+        self.refill();
+        let value = self.output.pop();
+        if value.is_some() {
+            self.stats.pops += 1;
         }
-        self.output.pop().unwrap()
+        value
+    }
+    
+    pub fn peek(&mut self) -> Option<&T> {
+        self.refill();
+        self.output.last()
+    }
+    
+    pub fn empty(&self) -> bool {
+        self.input.is_empty() && self.output.is_empty()
+    }
+    
+    /// Same as `empty`, under the standard library's name.
+    pub fn is_empty(&self) -> bool {
+        self.empty()
+    }
+    
+    pub fn len(&self) -> usize {
+        self.input.len() + self.output.len()
     }
     
-    pub fn peek(&mut self) -> i32 {
-        if self.output.is_empty() {
+    pub fn stats(&self) -> QueueStats {
+        self.stats
+    }
+    
+    fn refill(&mut self) {
+        if self.output.is_empty() && !self.input.is_empty() {
+            self.stats.refills += 1;
             while let Some(x) = self.input.pop() {
+                self.stats.transfers += 1;
                 self.output.push(x);
             }
         }
-        *self.output.last().unwrap()
     }
-    
-    pub fn empty(&self) -> bool {
-        self.input.is_empty() && self.output.is_empty()
+}
+
+impl<T> Default for MyQueue<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 /// Problem: Implement Stack using Queues
 /// Implement a last in first out (LIFO) stack using only two queues.
-pub struct MyStack {
-    queue1: VecDeque<i32>,
-    queue2: VecDeque<i32>,
+/// The most recent element is kept outside the queues, so `push` and `top`
+/// are O(1) and `pop` pays O(n) to expose the next one.
+pub struct MyStack<T> {
+    queue1: VecDeque<T>,
+    queue2: VecDeque<T>,
+    last: Option<T>,
 }
 
-impl MyStack {
+impl<T> MyStack<T> {
     pub fn new() -> Self {
         MyStack {
             queue1: VecDeque::new(),
             queue2: VecDeque::new(),
+            last: None,
         }
     }
     
-    pub fn push(&mut self, x: i32) {
-        self.queue2.push_back(x);
-        while let Some(val) = self.queue1.pop_front() {
-            self.queue2.push_back(val);
+    pub fn push(&mut self, x: T) {
+        if let Some(prev) = self.last.replace(x) {
+            self.queue1.push_back(prev);
         }
-        std::mem::swap(&mut self.queue1, &mut self.queue2);
     }
     
-    pub fn pop(&mut self) -> i32 {
-        self.queue1.pop_front().unwrap()
+    pub fn pop(&mut self) -> Option<T> {
+        let top = self.last.take()?;
+        
+        // Move everything but the newest queued element across; that one becomes the new top
+        while self.queue1.len() > 1 {
+            self.queue2.push_back(self.queue1.pop_front().unwrap());
+        }
+        self.last = self.queue1.pop_front();
+        std::mem::swap(&mut self.queue1, &mut self.queue2);
+        
+        Some(top)
     }
     
-    pub fn top(&self) -> i32 {
-        *self.queue1.front().unwrap()
+    pub fn top(&self) -> Option<&T> {
+        self.last.as_ref()
     }
     
     pub fn empty(&self) -> bool {
-        self.queue1.is_empty()
+        self.last.is_none()
+    }
+    
+    /// Same as `empty`, under the standard library's name.
+    pub fn is_empty(&self) -> bool {
+        self.empty()
+    }
+    
+    pub fn len(&self) -> usize {
+        self.queue1.len() + self.last.is_some() as usize
     }
 }
 
-/// Problem: Circular Queue
-/// Design your implementation of the circular queue.
-pub struct MyCircularQueue {
-    data: Vec<i32>,
-    head: usize,
-    tail: usize,
-    size: usize,
-    capacity: usize,
+impl<T> Default for MyStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl MyCircularQueue {
-    pub fn new(k: i32) -> Self {
-        MyCircularQueue {
-            data: vec![0; k as usize],
-            head: 0,
-            tail: 0,
-            size: 0,
-            capacity: k as usize,
+/// Problem: Implement Stack using a Single Queue
+/// After each push, rotate the queue so the new element is at the front:
+/// `push` is O(n), `pop` and `top` are O(1).
+pub struct SingleQueueStack<T> {
+    queue: VecDeque<T>,
+}
+
+impl<T> SingleQueueStack<T> {
+    pub fn new() -> Self {
+        SingleQueueStack {
+            queue: VecDeque::new(),
         }
     }
     
-    pub fn en_queue(&mut self, value: i32) -> bool {
-        if self.is_full() {
-            return false;
+    pub fn push(&mut self, x: T) {
+        self.queue.push_back(x);
+        for _ in 1..self.queue.len() {
+            let front = self.queue.pop_front().unwrap();
+            self.queue.push_back(front);
         }
-        
-        self.data[self.tail] = value;
-        self.tail = (self.tail + 1) % self.capacity;
-        self.size += 1;
-        true
     }
     
-    pub fn de_queue(&mut self) -> bool {
-        if self.is_empty() {
-            return false;
-        }
-        
-        self.head = (self.head + 1) % self.capacity;
-        self.size -= 1;
-        true
+    pub fn pop(&mut self) -> Option<T> {
+        self.queue.pop_front()
     }
     
-    pub fn front(&self) -> i32 {
-        if self.is_empty() {
-            -1
-        } else {
-            self.data[self.head]
-        }
+    pub fn top(&self) -> Option<&T> {
+        self.queue.front()
     }
     
-    pub fn rear(&self) -> i32 {
-        if self.is_empty() {
-            -1
-        } else {
-            self.data[(self.tail + self.capacity - 1) % self.capacity]
-        }
+    pub fn empty(&self) -> bool {
+        self.queue.is_empty()
     }
     
+    /// Same as `empty`, under the standard library's name.
     pub fn is_empty(&self) -> bool {
-        self.size == 0
+        self.empty()
     }
     
-    pub fn is_full(&self) -> bool {
-        self.size == self.capacity
+    pub fn len(&self) -> usize {
+        self.queue.len()
     }
 }
 
-/// Problem: Priority Queue Implementation
-/// A simple priority queue using BinaryHeap
-pub struct PriorityQueue<T> {
-    heap: BinaryHeap<T>,
+impl<T> Default for SingleQueueStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl<T: Ord> PriorityQueue<T> {
-    pub fn new() -> Self {
-        PriorityQueue {
-            heap: BinaryHeap::new(),
+/// Problem: Circular Queue
+/// Design your implementation of the circular queue.
+/// Keeps LeetCode's `-1` convention for an empty `front`/`rear`; new code
+/// should use `RingBuffer`, which this delegates to.
+pub struct MyCircularQueue {
+    buffer: RingBuffer<i32>,
+}
+
+impl MyCircularQueue {
+    /// A non-positive `k` gives a queue that is always full and empty.
+    pub fn new(k: i32) -> Self {
+        MyCircularQueue {
+            buffer: RingBuffer::new(k.max(0) as usize),
         }
     }
     
-    pub fn push(&mut self, item: T) {
-        self.heap.push(item);
+    pub fn en_queue(&mut self, value: i32) -> bool {
+        self.buffer.push_back(value).is_ok()
     }
     
-    pub fn pop(&mut self) -> Option<T> {
-        self.heap.pop()
+    pub fn de_queue(&mut self) -> bool {
+        self.buffer.pop_front().is_some()
     }
     
-    pub fn peek(&self) -> Option<&T> {
-        self.heap.peek()
+    pub fn front(&self) -> i32 {
+        self.buffer.front().copied().unwrap_or(-1)
     }
     
-    pub fn len(&self) -> usize {
-        self.heap.len()
+    pub fn rear(&self) -> i32 {
+        self.buffer.back().copied().unwrap_or(-1)
     }
     
     pub fn is_empty(&self) -> bool {
-        self.heap.is_empty()
+        self.buffer.is_empty()
+    }
+    
+    pub fn is_full(&self) -> bool {
+        self.buffer.is_full()
     }
 }
 
@@ -231,115 +348,9 @@ pub fn max_sliding_window_deque(nums: &[i32], k: usize) -> Vec<i32> {
 }
 
 /// Problem: Number of Islands (BFS)
-/// Count the number of islands in a 2D grid.
-pub fn num_islands(grid: &mut Vec<Vec<char>>) -> i32 {
-    if grid.is_empty() || grid[0].is_empty() {
-        return 0;
-    }
-    
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let mut count = 0;
-    
-    for i in 0..rows {
-        for j in 0..cols {
-            if grid[i][j] == '1' {
-                count += 1;
-                bfs_islands(grid, i, j);
-            }
-        }
-    }
-    
-    count
-}
-
-fn bfs_islands(grid: &mut Vec<Vec<char>>, row: usize, col: usize) {
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let mut queue = VecDeque::new();
-    queue.push_back((row, col));
-    grid[row][col] = '0';
-    
-    let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    
-    while let Some((r, c)) = queue.pop_front() {
-        for (dr, dc) in directions.iter() {
-            let new_r = r as i32 + dr;
-            let new_c = c as i32 + dc;
-            
-            if new_r >= 0 && new_r < rows as i32 && 
-               new_c >= 0 && new_c < cols as i32 && 
-               grid[new_r as usize][new_c as usize] == '1' {
-                grid[new_r as usize][new_c as usize] = '0';
-                queue.push_back((new_r as usize, new_c as usize));
-            }
-        }
-    }
-}
-
-/// Problem: Open the Lock
-/// Find the minimum number of turns required to open the lock.
-pub fn open_lock(deadends: Vec<String>, target: String) -> i32 {
-    let mut visited = std::collections::HashSet::new();
-    let deadends: std::collections::HashSet<String> = deadends.into_iter().collect();
-    
-    if deadends.contains("0000") {
-        return -1;
-    }
-    
-    let mut queue = VecDeque::new();
-    queue.push_back(("0000".to_string(), 0));
-    visited.insert("0000".to_string());
-    
-    while let Some((current, turns)) = queue.pop_front() {
-        if current == target {
-            return turns;
-        }
-        
-        for i in 0..4 {
-            for delta in [-1, 1] {
-                let mut chars: Vec<char> = current.chars().collect();
-                let digit = chars[i].to_digit(10).unwrap() as i32;
-                let new_digit = (digit + delta + 10) % 10;
-                chars[i] = std::char::from_digit(new_digit as u32, 10).unwrap();
-                let next = chars.into_iter().collect::<String>();
-                
-                if !visited.contains(&next) && !deadends.contains(&next) {
-                    visited.insert(next.clone());
-                    queue.push_back((next, turns + 1));
-                }
-            }
-        }
-    }
-    
-    -1
-}
-
-/// Problem: Perfect Squares
-/// Find the least number of perfect square numbers that sum to n.
-pub fn num_squares(n: i32) -> i32 {
-    let mut queue = VecDeque::new();
-    let mut visited = std::collections::HashSet::new();
-    
-    queue.push_back((n, 0));
-    visited.insert(n);
-    
-    while let Some((current, steps)) = queue.pop_front() {
-        if current == 0 {
-            return steps;
-        }
-        
-        let sqrt = (current as f64).sqrt() as i32;
-        for i in 1..=sqrt {
-            let next = current - i * i;
-            if !visited.contains(&next) {
-                visited.insert(next);
-                queue.push_back((next, steps + 1));
-            }
-        }
-    }
-    
-    0
+/// Count the number of islands in a 2D grid without modifying it.
+pub fn num_islands(grid: &Grid<char>) -> i32 {
+    grid.label_components(&Connectivity::Four, |&cell| cell == '1').sizes.len() as i32
 }
 
 /// Run all queue problem examples
@@ -351,16 +362,26 @@ pub fn run_examples() {
     queue.push(1);
     queue.push(2);
     queue.push(3);
-    println!("MyQueue: push 1,2,3 -> peek: {}", queue.peek());
-    assert_result(queue.peek(), 1, "MyQueue Peek");
+    println!("MyQueue: push 1,2,3 -> peek: {:?}", queue.peek());
+    assert_result(queue.peek().copied(), Some(1), "MyQueue Peek");
+    while queue.pop().is_some() {}
+    println!("MyQueue: drained -> {:?}, amortized cost {:.2}", queue.stats(), queue.stats().amortized_cost());
+    assert_result(queue.pop(), None, "MyQueue Pop Empty");
     
     // MyStack
     let mut stack = MyStack::new();
     stack.push(1);
     stack.push(2);
     stack.push(3);
-    println!("MyStack: push 1,2,3 -> top: {}", stack.top());
-    assert_result(stack.top(), 3, "MyStack Top");
+    println!("MyStack: push 1,2,3 -> top: {:?}", stack.top());
+    assert_result(stack.top().copied(), Some(3), "MyStack Top");
+    
+    // Single Queue Stack
+    let mut stack = SingleQueueStack::new();
+    stack.push("a");
+    stack.push("b");
+    println!("Single Queue Stack: push a,b -> pop: {:?}, len: {}", stack.pop(), stack.len());
+    assert_result(stack.top().copied(), Some("a"), "Single Queue Stack Top");
     
     // Circular Queue
     let mut cq = MyCircularQueue::new(3);
@@ -370,6 +391,86 @@ pub fn run_examples() {
     println!("Circular Queue: enqueue 1,2,3 -> rear: {}", cq.rear());
     assert_result(cq.rear(), 3, "Circular Queue Rear");
     
+    // Ring Buffer
+    let mut ring = RingBuffer::new(3).with_policy(OverflowPolicy::OverwriteOldest);
+    let evicted: Vec<Option<i32>> = (1..=5).map(|i| ring.push_back(i).unwrap()).collect();
+    let (first, second) = ring.as_slices();
+    let halves: (Vec<i32>, Vec<i32>) = (first.copied().collect(), second.copied().collect());
+    println!("Ring Buffer: push 1..=5 into capacity 3 -> {:?}, evicted {:?}, halves {:?}", ring, evicted, halves);
+    assert_result(ring.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5], "Ring Buffer Overwrite");
+    assert_result(ring.front(), Some(&3), "Ring Buffer Front");
+    
+    // Bounded Blocking Queue
+    let pipeline = Arc::new(BoundedBlockingQueue::new(4));
+    let producer = {
+        let pipeline = Arc::clone(&pipeline);
+        thread::spawn(move || {
+            for i in 1..=100 {
+                pipeline.push(i).unwrap();
+            }
+            pipeline.close();
+        })
+    };
+    let total: i32 = measure_time("Blocking Queue 100 items", || std::iter::from_fn(|| pipeline.pop()).sum());
+    producer.join().unwrap();
+    println!("Bounded Blocking Queue: producer thread sends 1..=100 through capacity 4 -> sum {}", total);
+    assert_result(total, 5050, "Bounded Blocking Queue");
+    
+    // SPSC Ring Buffer
+    let (mut tx, mut rx) = spsc::channel(8);
+    let producer = thread::spawn(move || {
+        for i in 1..=1000u64 {
+            let mut value = i;
+            while let Err(rejected) = tx.push(value) {
+                value = rejected;
+                thread::yield_now();
+            }
+        }
+    });
+    let mut received = Vec::with_capacity(1000);
+    while received.len() < 1000 {
+        match rx.pop() {
+            Some(value) => received.push(value),
+            None => thread::yield_now(),
+        }
+    }
+    producer.join().unwrap();
+    println!("SPSC Ring Buffer: 1000 items through capacity 8, in order: {}", received.windows(2).all(|w| w[0] < w[1]));
+    assert_result(received.iter().sum::<u64>(), 500_500, "SPSC Ring Buffer");
+    
+    // Rate Limiters (driven by a mock clock)
+    let clock = MockClock::new();
+    let mut log_limiter = SlidingLogLimiter::new(clock.clone(), 3, Duration::from_secs(1));
+    let mut bucket = TokenBucket::new(clock.clone(), 3, Duration::from_millis(250));
+    let mut counter = HitCounter::new(clock.clone(), 2);
+    let mut decisions = Vec::new();
+    for _ in 0..8 {
+        counter.hit();
+        decisions.push((log_limiter.try_acquire(), bucket.try_acquire(1)));
+        clock.advance(Duration::from_millis(200));
+    }
+    let log_allowed = decisions.iter().filter(|d| d.0).count();
+    let bucket_allowed = decisions.iter().filter(|d| d.1).count();
+    println!("Rate Limiters: 8 requests 200ms apart -> sliding log allowed {}, token bucket allowed {}, hits in last 2s {}", log_allowed, bucket_allowed, counter.hits());
+    assert_result((log_allowed, bucket_allowed), (6, 8), "Rate Limiters");
+    
+    // Schedulers
+    let mut round_robin = RoundRobinScheduler::new();
+    let mut fair = WeightedFairScheduler::new();
+    fair.set_weight("gold", 2);
+    for i in 0..3 {
+        round_robin.enqueue("a", i);
+        fair.enqueue("gold", i);
+        fair.enqueue("free", i);
+    }
+    round_robin.enqueue("b", 0);
+    let rr_order: Vec<&str> = std::iter::from_fn(|| round_robin.next_task()).map(|(flow, _)| flow).collect();
+    let fair_order: Vec<&str> = std::iter::from_fn(|| fair.next_task()).map(|(flow, _)| flow).collect();
+    println!("Round Robin: {:?}", rr_order);
+    println!("Weighted Fair (gold=2, free=1): {:?}", fair_order);
+    assert_result(rr_order, vec!["a", "b", "a", "a"], "Round Robin Scheduler");
+    assert_result(fair_order, vec!["gold", "free", "gold", "gold", "free", "free"], "Weighted Fair Scheduler");
+    
     // Priority Queue
     let mut pq = PriorityQueue::new();
     pq.push(3);
@@ -379,6 +480,78 @@ pub fn run_examples() {
     println!("Priority Queue: push 3,1,4,2 -> peek: {}", pq.peek().unwrap());
     assert_result(*pq.peek().unwrap(), 4, "Priority Queue Peek");
     
+    let mut tasks = PriorityQueue::with_key(HeapOrder::Min, |task: &(&str, u32)| task.1);
+    tasks.extend([("write", 3), ("review", 1), ("deploy", 5), ("test", 2)]);
+    let order: Vec<&str> = tasks.drain().map(|task| task.0).collect();
+    println!("Priority Queue by deadline (min): {:?}", order);
+    assert_result(order, vec!["review", "test", "write", "deploy"], "Priority Queue Min By Key");
+    
+    let scores = vec![2.5, -1.0, 9.75, 0.5].into_iter().map(TotalF64).collect();
+    let sorted: Vec<f64> = PriorityQueue::from_vec(scores, HeapOrder::Max).into_sorted_vec().into_iter().map(|x| x.0).collect();
+    println!("Priority Queue heapify f64 scores -> {:?}", sorted);
+    assert_result(sorted, vec![9.75, 2.5, 0.5, -1.0], "Priority Queue Float Heapify");
+    
+    // Indexed Priority Queue
+    let mut ipq = IndexedPriorityQueue::new_min();
+    ipq.push("alpha", 7);
+    ipq.push("beta", 4);
+    ipq.push("gamma", 9);
+    ipq.change_priority(&"gamma", 1);
+    ipq.remove(&"beta");
+    println!("Indexed Priority Queue: gamma 9 -> 1, remove beta -> peek {:?}", ipq.peek());
+    assert_result(ipq.pop(), Some(("gamma", 1)), "Indexed Priority Queue Decrease Key");
+    
+    let graph = vec![vec![(1, 4), (2, 1)], vec![(3, 1)], vec![(1, 2), (3, 5)], vec![]];
+    let dist = measure_time("Dijkstra", || dijkstra(&graph, 0));
+    println!("Dijkstra from 0: {:?}", dist);
+    assert_result(dist, vec![Some(0), Some(3), Some(1), Some(4)], "Dijkstra");
+    
+    // Heap Family
+    let mut dary = DaryHeap::from_vec(4, vec![6, 2, 9, 4]);
+    let mut pairing = PairingHeap::new();
+    let mut binomial = BinomialHeap::new();
+    for x in [8, 1, 5] {
+        pairing.push(x);
+        binomial.push(x);
+    }
+    let mut other = BinomialHeap::new();
+    other.push(3);
+    binomial.meld(other);
+    println!("Heaps: 4-ary peek {:?}, pairing peek {:?}, binomial after meld {:?}", dary.peek(), pairing.peek(), binomial.tree_orders());
+    assert_result(dary.pop(), Some(2), "D-ary Heap Pop");
+    assert_result(pairing.pop(), Some(1), "Pairing Heap Pop");
+    assert_result(binomial.len(), 4, "Binomial Heap Meld");
+    assert_result(heaps_agree(1_000), true, "Heap Agreement");
+    
+    // Double-Ended Priority Queue
+    let mut depq: DoubleEndedPriorityQueue<i32> = [5, 1, 9, 3, 7].into_iter().collect();
+    let ends = (depq.pop_min(), depq.pop_max());
+    println!("Double-Ended Priority Queue: [5, 1, 9, 3, 7] -> pop_min/pop_max {:?}, remaining {}..{}", ends, depq.peek_min().unwrap(), depq.peek_max().unwrap());
+    assert_result(ends, (Some(1), Some(9)), "Double-Ended Priority Queue");
+    
+    // Running Median
+    let mut running = RunningMedian::new();
+    for x in [5, 15, 1, 3] {
+        running.insert(x);
+    }
+    running.remove(&15);
+    println!("Running Median: insert 5,15,1,3 then remove 15 -> {:?}", running.median());
+    assert_result(running.median(), Some(3.0), "Running Median");
+    
+    let nums = vec![1, 3, -1, -3, 5, 3, 6, 7];
+    let medians = measure_time("Sliding Window Median", || median_sliding_window(&nums, 3));
+    println!("Sliding Window Median: {:?}, k=3 -> {:?}", nums, medians);
+    assert_result(medians, vec![1.0, -1.0, -1.0, 3.0, 5.0, 6.0], "Sliding Window Median");
+    
+    // Persistent Queue
+    let base = PersistentQueue::new().push(1).push(2).push(3);
+    let (front, rest) = base.pop().unwrap();
+    let branch = rest.push(4);
+    let values: Vec<i32> = branch.iter().map(|v| *v).collect();
+    println!("Persistent Queue: pop {} then push 4 -> {:?}, original len {}", front, values, base.len());
+    assert_result(values, vec![2, 3, 4], "Persistent Queue Branch");
+    assert_result(base.len(), 3, "Persistent Queue Snapshot");
+    
     // Sliding Window Maximum
     let nums = vec![1, 3, -1, -3, 5, 3, 6, 7];
     let k = 3;
@@ -387,16 +560,43 @@ pub fn run_examples() {
     assert_result(result, vec![3, 3, 5, 5, 6, 7], "Max Sliding Window Deque");
     
     // Number of Islands
-    let mut grid = vec![
+    let grid = Grid::try_from(vec![
         vec!['1', '1', '0', '0', '0'],
         vec!['1', '1', '0', '0', '0'],
         vec!['0', '0', '1', '0', '0'],
         vec!['0', '0', '0', '1', '1'],
-    ];
-    let result = measure_time("Number of Islands", || num_islands(&mut grid));
+    ]).unwrap();
+    let result = measure_time("Number of Islands", || num_islands(&grid));
     println!("Number of Islands: {}", result);
     assert_result(result, 3, "Number of Islands");
     
+    let sizes = island_sizes(&grid);
+    println!("Island Sizes: {:?}", sizes);
+    assert_result(sizes, vec![4, 2, 1], "Island Sizes");
+    
+    // Rotting Oranges
+    let oranges = Grid::try_from(vec![vec![2, 1, 1], vec![1, 1, 0], vec![0, 1, 1]]).unwrap();
+    let result = measure_time("Rotting Oranges", || oranges_rotting(&oranges));
+    println!("Rotting Oranges: {:?} -> {} minutes", oranges.to_rows(), result);
+    assert_result(result, 4, "Rotting Oranges");
+    
+    // Walls and Gates
+    let mut rooms = Grid::try_from(vec![
+        vec![EMPTY_ROOM, -1, 0, EMPTY_ROOM],
+        vec![EMPTY_ROOM, EMPTY_ROOM, EMPTY_ROOM, -1],
+        vec![EMPTY_ROOM, -1, EMPTY_ROOM, -1],
+        vec![0, -1, EMPTY_ROOM, EMPTY_ROOM],
+    ]).unwrap();
+    walls_and_gates(&mut rooms);
+    println!("Walls and Gates: {:?}", rooms.to_rows());
+    assert_result(rooms.to_rows()[0].clone(), vec![3, -1, 0, 1], "Walls and Gates");
+    
+    // Shortest Path in Binary Matrix
+    let matrix = Grid::try_from(vec![vec![0, 0, 0], vec![1, 1, 0], vec![1, 1, 0]]).unwrap();
+    let result = measure_time("Shortest Path in Binary Matrix", || shortest_path_binary_matrix(&matrix));
+    println!("Shortest Path in Binary Matrix: {:?} -> {}", matrix.to_rows(), result);
+    assert_result(result, 4, "Shortest Path in Binary Matrix");
+    
     // Open the Lock
     let deadends = vec!["0201".to_string(), "0101".to_string(), "0102".to_string(), "1212".to_string(), "2002".to_string()];
     let target = "0202".to_string();
@@ -404,6 +604,11 @@ pub fn run_examples() {
     println!("Open the Lock: target=0202 -> {}", result);
     assert_result(result, 6, "Open the Lock");
     
+    let deadends = vec!["0201".to_string(), "0101".to_string(), "0102".to_string(), "1212".to_string(), "2002".to_string()];
+    let sequence = open_lock_sequence(&deadends, "0202").unwrap_or_default();
+    println!("Open the Lock path: {}", sequence.join(" -> "));
+    assert_result(sequence.len(), 7, "Open the Lock Path");
+    
     // Perfect Squares
     let n = 12;
     let result = measure_time("Perfect Squares", || num_squares(n));
@@ -414,16 +619,39 @@ pub fn run_examples() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     
     #[test]
     fn test_my_queue() {
         let mut queue = MyQueue::new();
         queue.push(1);
         queue.push(2);
-        assert_eq!(queue.peek(), 1);
-        assert_eq!(queue.pop(), 1);
-        assert_eq!(queue.pop(), 2);
+        assert_eq!(queue.peek(), Some(&1));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
         assert_eq!(queue.empty(), true);
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.peek(), None);
+    }
+    
+    #[test]
+    fn test_my_queue_amortization() {
+        let mut queue = MyQueue::new();
+        for i in 0..100 {
+            queue.push(i);
+            if i % 3 == 0 {
+                queue.pop();
+            }
+        }
+        while queue.pop().is_some() {}
+        
+        let stats = queue.stats();
+        assert_eq!(stats.pushes, 100);
+        assert_eq!(stats.pops, 100);
+        // Every element is transferred exactly once, however the operations interleave
+        assert_eq!(stats.transfers, 100);
+        assert!(stats.refills <= stats.pops);
+        assert_eq!(stats.amortized_cost(), 2.0);
     }
     
     #[test]
@@ -431,10 +659,63 @@ mod tests {
         let mut stack = MyStack::new();
         stack.push(1);
         stack.push(2);
-        assert_eq!(stack.top(), 2);
-        assert_eq!(stack.pop(), 2);
-        assert_eq!(stack.pop(), 1);
+        assert_eq!(stack.top(), Some(&2));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
         assert_eq!(stack.empty(), true);
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.top(), None);
+    }
+    
+    #[test]
+    fn test_single_queue_stack() {
+        let mut stack = SingleQueueStack::new();
+        stack.push("a".to_string());
+        stack.push("b".to_string());
+        stack.push("c".to_string());
+        assert_eq!(stack.top().map(String::as_str), Some("c"));
+        assert_eq!(stack.pop().as_deref(), Some("c"));
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack.pop().as_deref(), Some("b"));
+        assert_eq!(stack.pop().as_deref(), Some("a"));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.empty());
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_queue_and_stacks_match_vecdeque(ops in proptest::collection::vec(proptest::option::of(any::<i32>()), 0..200)) {
+            let mut queue = MyQueue::new();
+            let mut stack = MyStack::new();
+            let mut single = SingleQueueStack::new();
+            let mut fifo = VecDeque::new();
+            let mut lifo = VecDeque::new();
+            
+            // `Some(x)` pushes x, `None` pops
+            for op in ops {
+                match op {
+                    Some(x) => {
+                        queue.push(x);
+                        stack.push(x);
+                        single.push(x);
+                        fifo.push_back(x);
+                        lifo.push_back(x);
+                    }
+                    None => {
+                        prop_assert_eq!(queue.pop(), fifo.pop_front());
+                        let expected = lifo.pop_back();
+                        prop_assert_eq!(stack.pop(), expected);
+                        prop_assert_eq!(single.pop(), expected);
+                    }
+                }
+                prop_assert_eq!(queue.peek(), fifo.front());
+                prop_assert_eq!(stack.top(), lifo.back());
+                prop_assert_eq!(single.top(), lifo.back());
+                prop_assert_eq!(queue.len(), fifo.len());
+                prop_assert_eq!(stack.len(), lifo.len());
+                prop_assert_eq!(single.len(), lifo.len());
+            }
+        }
     }
     
     #[test]
@@ -451,6 +732,15 @@ mod tests {
         assert_eq!(cq.rear(), 4);
     }
     
+    #[test]
+    fn test_circular_queue_non_positive_capacity() {
+        let mut cq = MyCircularQueue::new(-2);
+        assert!(!cq.en_queue(1));
+        assert_eq!(cq.front(), -1);
+        assert_eq!(cq.rear(), -1);
+        assert!(!cq.de_queue());
+    }
+    
     #[test]
     fn test_priority_queue() {
         let mut pq = PriorityQueue::new();
@@ -471,13 +761,16 @@ mod tests {
     
     #[test]
     fn test_num_islands() {
-        let mut grid = vec![
+        let grid = Grid::try_from(vec![
             vec!['1', '1', '0', '0', '0'],
             vec!['1', '1', '0', '0', '0'],
             vec!['0', '0', '1', '0', '0'],
             vec!['0', '0', '0', '1', '1'],
-        ];
-        assert_eq!(num_islands(&mut grid), 3);
+        ]).unwrap();
+        let before = grid.clone();
+        assert_eq!(num_islands(&grid), 3);
+        assert_eq!(grid, before);
+        assert_eq!(num_islands(&Grid::from_fn(0, 0, |_| '1')), 0);
     }
     
     #[test]
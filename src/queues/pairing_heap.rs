@@ -0,0 +1,142 @@
+//! Pairing Heap
+//! 
+//! A min-heap-ordered multi-way tree. `push` and `meld` just link two roots;
+//! `pop` removes the root and rebuilds with the two-pass pairing scheme:
+//! link children in pairs left to right, then fold the pairs right to left.
+//! - O(1) push, peek and meld
+//! - Amortized O(log n) pop
+
+use super::heaps::Heap;
+
+struct Node<T> {
+    value: T,
+    children: Vec<Box<Node<T>>>,
+}
+
+pub struct PairingHeap<T> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+}
+
+/// Make the root with the larger value a child of the other.
+fn link<T: Ord>(mut a: Box<Node<T>>, mut b: Box<Node<T>>) -> Box<Node<T>> {
+    if a.value <= b.value {
+        a.children.push(b);
+        a
+    } else {
+        b.children.push(a);
+        b
+    }
+}
+
+fn merge_pairs<T: Ord>(children: Vec<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+    let mut paired = Vec::with_capacity(children.len() / 2 + 1);
+    let mut iter = children.into_iter();
+    while let Some(first) = iter.next() {
+        match iter.next() {
+            Some(second) => paired.push(link(first, second)),
+            None => paired.push(first),
+        }
+    }
+    paired.into_iter().rev().reduce(|acc, node| link(node, acc))
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        PairingHeap { root: None, len: 0 }
+    }
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Heap<T> for PairingHeap<T> {
+    fn push(&mut self, value: T) {
+        let node = Box::new(Node {
+            value,
+            children: Vec::new(),
+        });
+        self.root = Some(match self.root.take() {
+            Some(root) => link(root, node),
+            None => node,
+        });
+        self.len += 1;
+    }
+    
+    fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let Node { value, children } = *root;
+        self.root = merge_pairs(children);
+        self.len -= 1;
+        Some(value)
+    }
+    
+    fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+    
+    fn len(&self) -> usize {
+        self.len
+    }
+    
+    fn meld(&mut self, mut other: Self) {
+        self.root = match (self.root.take(), other.root.take()) {
+            (Some(a), Some(b)) => Some(link(a, b)),
+            (a, b) => a.or(b),
+        };
+        self.len += other.len;
+    }
+}
+
+// Pairing heaps can degenerate into long chains; free nodes iteratively
+impl<T> Drop for PairingHeap<T> {
+    fn drop(&mut self) {
+        let mut pending: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = pending.pop() {
+            pending.append(&mut node.children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_pairing_heap_order() {
+        let mut heap = PairingHeap::new();
+        for x in [5, 3, 8, 1, 9, 2, 7] {
+            heap.push(x);
+        }
+        assert_eq!(heap.peek(), Some(&1));
+        let popped: Vec<i32> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![1, 2, 3, 5, 7, 8, 9]);
+        assert!(heap.pop().is_none());
+    }
+    
+    #[test]
+    fn test_pairing_meld() {
+        let mut a = PairingHeap::new();
+        let mut b = PairingHeap::new();
+        a.push(4);
+        b.push(2);
+        b.push(6);
+        a.meld(b);
+        a.meld(PairingHeap::new());
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.pop(), Some(2));
+    }
+    
+    #[test]
+    fn test_deep_heap_drops() {
+        // Decreasing pushes build a chain one node deep per element
+        let mut heap = PairingHeap::new();
+        for x in (0..200_000).rev() {
+            heap.push(x);
+        }
+        assert_eq!(heap.len(), 200_000);
+    }
+}
@@ -0,0 +1,283 @@
+//! Persistent Queue
+//! 
+//! Okasaki's real-time queue: an immutable FIFO queue whose `push` and `pop`
+//! return new versions sharing structure with the old ones. The front is a
+//! lazily rotated stream and a schedule forces one suspension per operation,
+//! so every operation is worst-case O(1) even when old versions are reused.
+
+use crate::stacks::persistent_stack::PersistentStack;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Stream<T> = Rc<Suspension<T>>;
+
+enum Cell<T> {
+    Nil,
+    Cons(Rc<T>, Stream<T>),
+}
+
+// Cells only hold shared pointers, so cloning them never needs `T: Clone`
+impl<T> Clone for Cell<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Cell::Nil => Cell::Nil,
+            Cell::Cons(value, next) => Cell::Cons(value.clone(), next.clone()),
+        }
+    }
+}
+
+/// A memoized stream cell. The only deferred computation is one step of a rotation.
+enum State<T> {
+    Rotate(Stream<T>, PersistentStack<Rc<T>>, Stream<T>),
+    Forced(Cell<T>),
+}
+
+struct Suspension<T> {
+    state: RefCell<State<T>>,
+}
+
+fn stream<T>(state: State<T>) -> Stream<T> {
+    Rc::new(Suspension {
+        state: RefCell::new(state),
+    })
+}
+
+fn nil<T>() -> Stream<T> {
+    stream(State::Forced(Cell::Nil))
+}
+
+/// Evaluate a stream cell once and remember the result.
+///
+/// `rotate(f, r, a)` lazily computes `f ++ reverse(r) ++ a` where `|r| = |f| + 1`.
+fn force<T>(s: &Stream<T>) -> Cell<T> {
+    let (front, rear, acc) = match &*s.state.borrow() {
+        State::Forced(cell) => return cell.clone(),
+        State::Rotate(front, rear, acc) => (front.clone(), rear.clone(), acc.clone()),
+    };
+    
+    let (y, rest) = rear.pop().expect("rotation invariant: rear is one longer than front");
+    let cell = match force(&front) {
+        Cell::Nil => Cell::Cons(y.clone(), acc),
+        Cell::Cons(x, front_tail) => {
+            let acc = stream(State::Forced(Cell::Cons(y.clone(), acc)));
+            Cell::Cons(x, stream(State::Rotate(front_tail, rest, acc)))
+        }
+    };
+    
+    *s.state.borrow_mut() = State::Forced(cell.clone());
+    cell
+}
+
+// Streams can be long chains of uniquely owned cells; free them iteratively
+impl<T> Drop for Suspension<T> {
+    fn drop(&mut self) {
+        fn take_children<T>(state: &mut State<T>, pending: &mut Vec<Stream<T>>) {
+            match std::mem::replace(state, State::Forced(Cell::Nil)) {
+                State::Forced(Cell::Cons(_, next)) => pending.push(next),
+                State::Rotate(front, _, acc) => {
+                    pending.push(front);
+                    pending.push(acc);
+                }
+                State::Forced(Cell::Nil) => {}
+            }
+        }
+        
+        let mut pending = Vec::new();
+        take_children(self.state.get_mut(), &mut pending);
+        while let Some(next) = pending.pop() {
+            if let Ok(mut suspension) = Rc::try_unwrap(next) {
+                take_children(suspension.state.get_mut(), &mut pending);
+            }
+        }
+    }
+}
+
+/// Immutable FIFO queue with worst-case O(1) push, pop and snapshot (clone).
+pub struct PersistentQueue<T> {
+    front: Stream<T>,
+    rear: PersistentStack<Rc<T>>,
+    schedule: Stream<T>,
+    len: usize,
+}
+
+impl<T> PersistentQueue<T> {
+    pub fn new() -> Self {
+        let front = nil();
+        PersistentQueue {
+            schedule: front.clone(),
+            front,
+            rear: PersistentStack::new(),
+            len: 0,
+        }
+    }
+    
+    /// Return a new version with `value` at the back; `self` is left untouched.
+    pub fn push(&self, value: T) -> Self {
+        Self::exec(self.front.clone(), self.rear.push(Rc::new(value)), self.schedule.clone(), self.len + 1)
+    }
+    
+    /// Return the front element and the version without it, or `None` when empty.
+    pub fn pop(&self) -> Option<(Rc<T>, Self)> {
+        match force(&self.front) {
+            Cell::Nil => None,
+            Cell::Cons(value, front) => {
+                let rest = Self::exec(front, self.rear.clone(), self.schedule.clone(), self.len - 1);
+                Some((value, rest))
+            }
+        }
+    }
+    
+    pub fn peek(&self) -> Option<Rc<T>> {
+        match force(&self.front) {
+            Cell::Nil => None,
+            Cell::Cons(value, _) => Some(value),
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Iterate from front to back without consuming this version.
+    pub fn iter(&self) -> impl Iterator<Item = Rc<T>> {
+        let mut current = self.clone();
+        std::iter::from_fn(move || {
+            let (value, rest) = current.pop()?;
+            current = rest;
+            Some(value)
+        })
+    }
+    
+    /// Force one scheduled suspension, starting a new rotation when the schedule runs out.
+    fn exec(front: Stream<T>, rear: PersistentStack<Rc<T>>, schedule: Stream<T>, len: usize) -> Self {
+        match force(&schedule) {
+            Cell::Cons(_, schedule) => PersistentQueue {
+                front,
+                rear,
+                schedule,
+                len,
+            },
+            Cell::Nil => {
+                let front = stream(State::Rotate(front, rear, nil()));
+                PersistentQueue {
+                    schedule: front.clone(),
+                    front,
+                    rear: PersistentStack::new(),
+                    len,
+                }
+            }
+        }
+    }
+}
+
+impl<T> Default for PersistentQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for PersistentQueue<T> {
+    fn clone(&self) -> Self {
+        PersistentQueue {
+            front: self.front.clone(),
+            rear: self.rear.clone(),
+            schedule: self.schedule.clone(),
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::VecDeque;
+    
+    fn to_vec(queue: &PersistentQueue<i32>) -> Vec<i32> {
+        queue.iter().map(|value| *value).collect()
+    }
+    
+    #[test]
+    fn test_fifo_order() {
+        let mut queue = PersistentQueue::new();
+        for i in 1..=5 {
+            queue = queue.push(i);
+        }
+        assert_eq!(queue.len(), 5);
+        assert_eq!(queue.peek().as_deref(), Some(&1));
+        
+        let mut popped = Vec::new();
+        while let Some((value, rest)) = queue.pop() {
+            popped.push(*value);
+            queue = rest;
+        }
+        assert_eq!(popped, vec![1, 2, 3, 4, 5]);
+        assert!(queue.is_empty());
+    }
+    
+    #[test]
+    fn test_old_versions_remain_valid() {
+        let base = PersistentQueue::new().push(1).push(2).push(3);
+        let (_, popped) = base.pop().unwrap();
+        let branch_a = popped.push(10);
+        let branch_b = popped.push(20).push(30);
+        
+        assert_eq!(to_vec(&base), vec![1, 2, 3]);
+        assert_eq!(to_vec(&popped), vec![2, 3]);
+        assert_eq!(to_vec(&branch_a), vec![2, 3, 10]);
+        assert_eq!(to_vec(&branch_b), vec![2, 3, 20, 30]);
+        
+        // Popping the same version twice gives the same answer
+        assert_eq!(*base.pop().unwrap().0, 1);
+        assert_eq!(*base.pop().unwrap().0, 1);
+    }
+    
+    #[test]
+    fn test_snapshot_is_constant_time() {
+        let mut queue = PersistentQueue::new();
+        for i in 0..100_000 {
+            queue = queue.push(i);
+        }
+        
+        let snapshot = queue.clone();
+        assert!(Rc::ptr_eq(&queue.front, &snapshot.front));
+        assert!(Rc::ptr_eq(&queue.schedule, &snapshot.schedule));
+        assert_eq!(Rc::strong_count(&queue.front), 2);
+        
+        let (first, rest) = snapshot.pop().unwrap();
+        assert_eq!(*first, 0);
+        assert_eq!(rest.len(), 99_999);
+        assert_eq!(queue.len(), 100_000);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_vecdeque_across_versions(ops in proptest::collection::vec((0usize..4, any::<i32>()), 0..200)) {
+            // Keep a handful of versions alive and apply each operation to one of them
+            let mut versions = vec![(PersistentQueue::new(), VecDeque::new())];
+            for (choice, value) in ops {
+                let (queue, model) = versions[choice % versions.len()].clone();
+                if value % 3 == 0 {
+                    let popped = queue.pop().map(|(v, rest)| (*v, rest));
+                    let mut model = model;
+                    prop_assert_eq!(popped.as_ref().map(|(v, _)| *v), model.pop_front());
+                    if let Some((_, rest)) = popped {
+                        versions.push((rest, model));
+                    }
+                } else {
+                    let mut model = model;
+                    model.push_back(value);
+                    versions.push((queue.push(value), model));
+                }
+            }
+            for (queue, model) in &versions {
+                prop_assert_eq!(to_vec(queue), model.iter().copied().collect::<Vec<_>>());
+                prop_assert_eq!(queue.len(), model.len());
+            }
+        }
+    }
+}
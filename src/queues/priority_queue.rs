@@ -0,0 +1,450 @@
+//! Priority Queue
+//! 
+//! A binary heap with a pluggable ordering:
+//! - Max-heap (default) or min-heap over `T: Ord`
+//! - Ordering by a key function or by a comparator closure
+//! - O(n) heapify from a `Vec`, sorted extraction, draining and `peek_mut`
+//! - `TotalF64` so floating-point priorities can be used directly
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// Whether the greatest or the smallest element comes out first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapOrder {
+    Max,
+    Min,
+}
+
+impl HeapOrder {
+    fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            HeapOrder::Max => ordering,
+            HeapOrder::Min => ordering.reverse(),
+        }
+    }
+}
+
+/// Decides which of two elements has the higher priority.
+pub trait Compare<T> {
+    /// `Greater` means `a` should be popped before `b`.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Orders elements by their `Ord` implementation.
+#[derive(Debug, Clone, Copy)]
+pub struct OrdCompare(HeapOrder);
+
+impl<T: Ord> Compare<T> for OrdCompare {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.apply(a.cmp(b))
+    }
+}
+
+/// Orders elements by a key extracted from them.
+#[derive(Clone, Copy)]
+pub struct KeyCompare<F> {
+    key: F,
+    order: HeapOrder,
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> Compare<T> for KeyCompare<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.order.apply((self.key)(a).cmp(&(self.key)(b)))
+    }
+}
+
+/// Orders elements with a comparator closure; `Greater` pops first.
+#[derive(Clone, Copy)]
+pub struct FnCompare<F>(F);
+
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for FnCompare<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+/// An `f64` with a total order (`f64::total_cmp`), usable as a heap priority.
+/// NaN sorts above positive infinity, and `-0.0` below `0.0`.
+#[derive(Debug, Clone, Copy)]
+pub struct TotalF64(pub f64);
+
+impl PartialEq for TotalF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TotalF64 {}
+
+impl PartialOrd for TotalF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl From<f64> for TotalF64 {
+    fn from(value: f64) -> Self {
+        TotalF64(value)
+    }
+}
+
+/// Problem: Priority Queue Implementation
+/// A binary heap stored in a `Vec`, ordered by a `Compare` strategy.
+pub struct PriorityQueue<T, C = OrdCompare> {
+    data: Vec<T>,
+    compare: C,
+}
+
+impl<T: Ord> PriorityQueue<T> {
+    /// An empty max-heap.
+    pub fn new() -> Self {
+        Self::with_order(HeapOrder::Max)
+    }
+    
+    /// An empty min-heap.
+    pub fn new_min() -> Self {
+        Self::with_order(HeapOrder::Min)
+    }
+    
+    pub fn with_order(order: HeapOrder) -> Self {
+        PriorityQueue {
+            data: Vec::new(),
+            compare: OrdCompare(order),
+        }
+    }
+    
+    /// Build a heap from `data` in O(n).
+    pub fn from_vec(data: Vec<T>, order: HeapOrder) -> Self {
+        let mut queue = PriorityQueue {
+            data,
+            compare: OrdCompare(order),
+        };
+        queue.rebuild();
+        queue
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> PriorityQueue<T, KeyCompare<F>> {
+    /// An empty heap ordered by `key`, largest or smallest key first.
+    pub fn with_key(order: HeapOrder, key: F) -> Self {
+        PriorityQueue {
+            data: Vec::new(),
+            compare: KeyCompare { key, order },
+        }
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> PriorityQueue<T, FnCompare<F>> {
+    /// An empty heap ordered by `compare`; elements comparing `Greater` pop first.
+    pub fn with_comparator(compare: F) -> Self {
+        PriorityQueue {
+            data: Vec::new(),
+            compare: FnCompare(compare),
+        }
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T, C> {
+    pub fn push(&mut self, item: T) {
+        self.data.push(item);
+        self.sift_up(self.data.len() - 1);
+    }
+    
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let item = self.data.swap_remove(0);
+        if !self.data.is_empty() {
+            self.sift_down(0, self.data.len());
+        }
+        Some(item)
+    }
+    
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+    
+    /// Mutable access to the top element; the heap is repaired when the guard is dropped.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.data.is_empty() {
+            None
+        } else {
+            Some(PeekMut { queue: self })
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    
+    /// Elements in heap (not priority) order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+    
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    
+    /// Remove every element, yielding them in priority order.
+    pub fn drain(&mut self) -> Drain<'_, T, C> {
+        Drain { queue: self }
+    }
+    
+    /// Consume the heap, returning its elements in priority order (what `pop` would yield).
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        // Heap sort: move the top to the end of the shrinking heap, then reverse
+        let mut end = self.data.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data.reverse();
+        self.data
+    }
+    
+    /// Consume the heap, returning its elements in heap order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+    
+    fn higher(&self, a: usize, b: usize) -> bool {
+        self.compare.compare(&self.data[a], &self.data[b]) == Ordering::Greater
+    }
+    
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.higher(index, parent) {
+                break;
+            }
+            self.data.swap(index, parent);
+            index = parent;
+        }
+    }
+    
+    /// Restore the heap property below `index`, considering only `data[..end]`.
+    fn sift_down(&mut self, mut index: usize, end: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut best = index;
+            if left < end && self.higher(left, best) {
+                best = left;
+            }
+            if right < end && self.higher(right, best) {
+                best = right;
+            }
+            if best == index {
+                break;
+            }
+            self.data.swap(index, best);
+            index = best;
+        }
+    }
+    
+    fn rebuild(&mut self) {
+        let len = self.data.len();
+        for index in (0..len / 2).rev() {
+            self.sift_down(index, len);
+        }
+    }
+}
+
+impl<T: Ord> Default for PriorityQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for PriorityQueue<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self::from_vec(data, HeapOrder::Max)
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for PriorityQueue<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let old_len = self.data.len();
+        self.data.extend(iter);
+        let added = self.data.len() - old_len;
+        
+        // Rebuilding costs O(n); pushing one by one costs O(k log n). Pick the cheaper.
+        let log_len = (usize::BITS - self.data.len().leading_zeros()) as usize;
+        if added * log_len > self.data.len() {
+            self.rebuild();
+        } else {
+            for index in old_len..self.data.len() {
+                self.sift_up(index);
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for PriorityQueue<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+/// Guard returned by `PriorityQueue::peek_mut`.
+pub struct PeekMut<'a, T, C: Compare<T>> {
+    queue: &'a mut PriorityQueue<T, C>,
+}
+
+impl<'a, T, C: Compare<T>> PeekMut<'a, T, C> {
+    /// Remove the peeked element from the heap.
+    pub fn pop(this: Self) -> T {
+        let value = this.queue.pop().expect("PeekMut is only created for non-empty heaps");
+        std::mem::forget(this);
+        value
+    }
+}
+
+impl<T, C: Compare<T>> Deref for PeekMut<'_, T, C> {
+    type Target = T;
+    
+    fn deref(&self) -> &T {
+        &self.queue.data[0]
+    }
+}
+
+impl<T, C: Compare<T>> DerefMut for PeekMut<'_, T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.queue.data[0]
+    }
+}
+
+impl<T, C: Compare<T>> Drop for PeekMut<'_, T, C> {
+    fn drop(&mut self) {
+        let len = self.queue.data.len();
+        self.queue.sift_down(0, len);
+    }
+}
+
+/// Iterator returned by `PriorityQueue::drain`.
+pub struct Drain<'a, T, C: Compare<T>> {
+    queue: &'a mut PriorityQueue<T, C>,
+}
+
+impl<T, C: Compare<T>> Iterator for Drain<'_, T, C> {
+    type Item = T;
+    
+    fn next(&mut self) -> Option<T> {
+        self.queue.pop()
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<T, C: Compare<T>> Drop for Drain<'_, T, C> {
+    fn drop(&mut self) {
+        self.queue.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_min_heap() {
+        let mut pq = PriorityQueue::new_min();
+        for x in [5, 1, 4, 2, 3] {
+            pq.push(x);
+        }
+        assert_eq!(pq.peek(), Some(&1));
+        assert_eq!(pq.drain().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert!(pq.is_empty());
+    }
+    
+    #[test]
+    fn test_key_and_comparator() {
+        let mut by_len = PriorityQueue::with_key(HeapOrder::Min, |s: &&str| s.len());
+        by_len.extend(["ccc", "a", "bb"]);
+        assert_eq!(by_len.pop(), Some("a"));
+        
+        // Earliest deadline first, ties broken by the larger weight
+        let mut tasks = PriorityQueue::with_comparator(|a: &(u32, u32), b: &(u32, u32)| {
+            b.0.cmp(&a.0).then(a.1.cmp(&b.1))
+        });
+        tasks.extend([(5, 1), (2, 1), (2, 9), (7, 3)]);
+        assert_eq!(tasks.into_sorted_vec(), vec![(2, 9), (2, 1), (5, 1), (7, 3)]);
+    }
+    
+    #[test]
+    fn test_from_vec_and_sorted_vec() {
+        let pq = PriorityQueue::from_vec(vec![3, 1, 4, 1, 5, 9, 2, 6], HeapOrder::Max);
+        assert_eq!(pq.peek(), Some(&9));
+        assert_eq!(pq.into_sorted_vec(), vec![9, 6, 5, 4, 3, 2, 1, 1]);
+        
+        let pq: PriorityQueue<i32> = vec![2, 7, 1].into();
+        assert_eq!(pq.len(), 3);
+        assert_eq!(PriorityQueue::from_vec(vec![2, 7, 1], HeapOrder::Min).into_sorted_vec(), vec![1, 2, 7]);
+    }
+    
+    #[test]
+    fn test_peek_mut() {
+        let mut pq = PriorityQueue::from_vec(vec![10, 5, 8], HeapOrder::Max);
+        if let Some(mut top) = pq.peek_mut() {
+            *top = 1;
+        }
+        assert_eq!(pq.peek(), Some(&8));
+        
+        let top = pq.peek_mut().map(PeekMut::pop);
+        assert_eq!(top, Some(8));
+        assert_eq!(pq.into_sorted_vec(), vec![5, 1]);
+        assert!(PriorityQueue::<i32>::new().peek_mut().is_none());
+    }
+    
+    #[test]
+    fn test_drain_partially_consumed_clears() {
+        let mut pq = PriorityQueue::from_vec(vec![1, 2, 3], HeapOrder::Max);
+        assert_eq!(pq.drain().next(), Some(3));
+        assert!(pq.is_empty());
+    }
+    
+    #[test]
+    fn test_f64_priorities() {
+        let mut pq = PriorityQueue::new_min();
+        for x in [2.5, -1.0, f64::INFINITY, 0.0, -0.0] {
+            pq.push(TotalF64(x));
+        }
+        let sorted: Vec<f64> = pq.into_sorted_vec().into_iter().map(|x| x.0).collect();
+        assert_eq!(sorted, vec![-1.0, -0.0, 0.0, 2.5, f64::INFINITY]);
+        assert!(sorted[1].is_sign_negative());
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_pop_order_matches_sort(values in proptest::collection::vec(any::<i32>(), 0..100), extra in proptest::collection::vec(any::<i32>(), 0..100)) {
+            let mut max = PriorityQueue::from_vec(values.clone(), HeapOrder::Max);
+            let mut min = PriorityQueue::from_vec(values.clone(), HeapOrder::Min);
+            max.extend(extra.iter().copied());
+            min.extend(extra.iter().copied());
+            
+            let mut expected: Vec<i32> = values.into_iter().chain(extra).collect();
+            expected.sort();
+            prop_assert_eq!(min.drain().collect::<Vec<_>>(), expected.clone());
+            expected.reverse();
+            prop_assert_eq!(max.into_sorted_vec(), expected);
+        }
+    }
+}
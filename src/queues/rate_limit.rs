@@ -0,0 +1,362 @@
+//! Rate Limiting
+//! 
+//! Queue-backed rate limiters and counters that read time from an injectable
+//! `Clock`, so tests can drive them with a `MockClock` instead of sleeping.
+//! - Sliding-log limiter: at most `limit` events in any window, exact
+//! - Token bucket: bursts up to `capacity`, refilled one token per interval
+//! - Hit counter: hits in the last N seconds, bucketed per second
+
+use super::ring_buffer::RingBuffer;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// A time source, measured from an arbitrary starting point. It should be
+/// monotonic, but the limiters below tolerate it going backwards.
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+/// Real time, measured from when the clock was created.
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Manually advanced time. Clones share the same time, so a test can keep one
+/// handle and give another to the code under test.
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+    
+    /// Jump to `to`, which may be earlier than the current time.
+    pub fn set(&self, to: Duration) {
+        self.now.set(to);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+/// Problem: Sliding Log Rate Limiter
+/// Allow at most `limit` events in any trailing `window`, remembering each accepted event's time.
+pub struct SlidingLogLimiter<C> {
+    clock: C,
+    window: Duration,
+    /// Times of accepted events inside the window; never holds more than `limit`.
+    log: RingBuffer<Duration>,
+}
+
+impl<C: Clock> SlidingLogLimiter<C> {
+    pub fn new(clock: C, limit: usize, window: Duration) -> Self {
+        SlidingLogLimiter {
+            clock,
+            window,
+            log: RingBuffer::new(limit),
+        }
+    }
+    
+    /// Record an event if the limit allows it.
+    pub fn try_acquire(&mut self) -> bool {
+        let now = self.evict();
+        self.log.push_back(now).is_ok()
+    }
+    
+    /// Events that would still be accepted right now.
+    pub fn remaining(&mut self) -> usize {
+        self.evict();
+        self.log.capacity() - self.log.len()
+    }
+    
+    /// How long until the next event would be accepted.
+    pub fn retry_after(&mut self) -> Duration {
+        let now = self.evict();
+        match self.log.front() {
+            Some(&oldest) if self.log.is_full() => oldest + self.window - now,
+            _ => Duration::ZERO,
+        }
+    }
+    
+    /// Drop events that have left the window and return the current time.
+    fn evict(&mut self) -> Duration {
+        let now = self.clock.now();
+        while self.log.front().is_some_and(|&time| now.saturating_sub(time) >= self.window) {
+            self.log.pop_front();
+        }
+        now
+    }
+}
+
+/// Problem: Token Bucket Rate Limiter
+/// Holds up to `capacity` tokens and gains one every `refill_interval`; each event spends tokens.
+pub struct TokenBucket<C> {
+    clock: C,
+    capacity: u64,
+    refill_interval: Duration,
+    tokens: u64,
+    /// Time up to which refills have been credited.
+    last_refill: Duration,
+}
+
+impl<C: Clock> TokenBucket<C> {
+    /// A full bucket. Panics if `refill_interval` is zero.
+    pub fn new(clock: C, capacity: u64, refill_interval: Duration) -> Self {
+        assert!(!refill_interval.is_zero(), "refill interval must be positive");
+        let last_refill = clock.now();
+        TokenBucket {
+            clock,
+            capacity,
+            refill_interval,
+            tokens: capacity,
+            last_refill,
+        }
+    }
+    
+    /// Spend `tokens` if that many are available; otherwise spend nothing.
+    pub fn try_acquire(&mut self, tokens: u64) -> bool {
+        self.refill();
+        if self.tokens >= tokens {
+            self.tokens -= tokens;
+            true
+        } else {
+            false
+        }
+    }
+    
+    pub fn available(&mut self) -> u64 {
+        self.refill();
+        self.tokens
+    }
+    
+    /// How long until `tokens` are available, or `None` if the bucket can never hold that many.
+    pub fn time_until(&mut self, tokens: u64) -> Option<Duration> {
+        if tokens > self.capacity {
+            return None;
+        }
+        self.refill();
+        let missing = tokens.saturating_sub(self.tokens);
+        if missing == 0 {
+            return Some(Duration::ZERO);
+        }
+        let elapsed = self.clock.now().saturating_sub(self.last_refill).as_nanos();
+        Some(nanos(self.refill_interval.as_nanos() * u128::from(missing) - elapsed))
+    }
+    
+    /// Credit whole intervals since the last refill, carrying the partial interval forward.
+    fn refill(&mut self) {
+        let now = self.clock.now();
+        if now < self.last_refill {
+            // The clock went backwards: restart the current interval rather than stall
+            self.last_refill = now;
+        }
+        let intervals = (now - self.last_refill).as_nanos() / self.refill_interval.as_nanos();
+        let room = u128::from(self.capacity - self.tokens);
+        if intervals >= room {
+            // A full bucket does not bank time towards future tokens
+            self.tokens = self.capacity;
+            self.last_refill = now;
+        } else {
+            self.tokens += intervals as u64;
+            self.last_refill += nanos(self.refill_interval.as_nanos() * intervals);
+        }
+    }
+}
+
+fn nanos(n: u128) -> Duration {
+    Duration::from_nanos(u64::try_from(n).unwrap_or(u64::MAX))
+}
+
+/// Problem: Design Hit Counter
+/// Count hits in the trailing `window_secs` seconds, using one bucket per second.
+pub struct HitCounter<C> {
+    clock: C,
+    window_secs: u64,
+    /// `(second, hits in that second)`, oldest first.
+    buckets: VecDeque<(u64, u64)>,
+    total: u64,
+}
+
+impl<C: Clock> HitCounter<C> {
+    pub fn new(clock: C, window_secs: u64) -> Self {
+        HitCounter {
+            clock,
+            window_secs,
+            buckets: VecDeque::new(),
+            total: 0,
+        }
+    }
+    
+    pub fn hit(&mut self) {
+        let second = self.evict();
+        match self.buckets.back_mut() {
+            // A clock that went backwards counts towards the newest bucket, keeping them in order
+            Some((last, count)) if *last >= second => *count += 1,
+            _ => self.buckets.push_back((second, 1)),
+        }
+        self.total += 1;
+    }
+    
+    /// Hits in the current second and the `window_secs - 1` seconds before it.
+    pub fn hits(&mut self) -> u64 {
+        self.evict();
+        self.total
+    }
+    
+    fn evict(&mut self) -> u64 {
+        let second = self.clock.now().as_secs();
+        while let Some(&(oldest, count)) = self.buckets.front() {
+            if second.saturating_sub(oldest) < self.window_secs {
+                break;
+            }
+            self.total -= count;
+            self.buckets.pop_front();
+        }
+        second
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+    
+    #[test]
+    fn test_sliding_log_limiter() {
+        let clock = MockClock::new();
+        let mut limiter = SlidingLogLimiter::new(clock.clone(), 3, ms(1000));
+        assert!(limiter.try_acquire());
+        clock.advance(ms(200));
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        assert_eq!(limiter.remaining(), 0);
+        assert_eq!(limiter.retry_after(), ms(800));
+        
+        // The first event leaves the window exactly 1000ms after it happened
+        clock.advance(ms(799));
+        assert!(!limiter.try_acquire());
+        clock.advance(ms(1));
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        
+        clock.advance(ms(5000));
+        assert_eq!(limiter.remaining(), 3);
+        assert_eq!(limiter.retry_after(), Duration::ZERO);
+    }
+    
+    #[test]
+    fn test_token_bucket() {
+        let clock = MockClock::new();
+        let mut bucket = TokenBucket::new(clock.clone(), 5, ms(100));
+        assert!(bucket.try_acquire(5));
+        assert!(!bucket.try_acquire(1));
+        assert_eq!(bucket.time_until(2), Some(ms(200)));
+        
+        // Partial intervals carry over instead of being lost
+        clock.advance(ms(150));
+        assert_eq!(bucket.available(), 1);
+        assert_eq!(bucket.time_until(2), Some(ms(50)));
+        clock.advance(ms(50));
+        assert_eq!(bucket.available(), 2);
+        assert!(!bucket.try_acquire(3));
+        assert_eq!(bucket.available(), 2);
+        
+        // Idle time beyond a full bucket is not banked
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(bucket.available(), 5);
+        assert!(bucket.try_acquire(5));
+        clock.advance(ms(99));
+        assert_eq!(bucket.available(), 0);
+        assert_eq!(bucket.time_until(6), None);
+    }
+    
+    #[test]
+    fn test_hit_counter() {
+        let clock = MockClock::new();
+        let mut counter = HitCounter::new(clock.clone(), 300);
+        clock.set(Duration::from_secs(1));
+        counter.hit();
+        clock.set(Duration::from_secs(2));
+        counter.hit();
+        clock.set(Duration::from_secs(3));
+        counter.hit();
+        counter.hit();
+        assert_eq!(counter.hits(), 4);
+        clock.set(Duration::from_secs(300));
+        counter.hit();
+        assert_eq!(counter.hits(), 5);
+        clock.set(Duration::from_secs(301));
+        assert_eq!(counter.hits(), 4);
+        clock.set(Duration::from_secs(303));
+        assert_eq!(counter.hits(), 1);
+        clock.set(Duration::from_secs(1000));
+        assert_eq!(counter.hits(), 0);
+    }
+    
+    #[test]
+    fn test_clock_going_backwards() {
+        let clock = MockClock::new();
+        clock.set(Duration::from_secs(100));
+        let mut bucket = TokenBucket::new(clock.clone(), 2, Duration::from_secs(1));
+        let mut counter = HitCounter::new(clock.clone(), 300);
+        let mut limiter = SlidingLogLimiter::new(clock.clone(), 1, Duration::from_secs(10));
+        assert!(bucket.try_acquire(2));
+        counter.hit();
+        assert!(limiter.try_acquire());
+        
+        clock.set(Duration::from_secs(50));
+        assert_eq!(bucket.available(), 0);
+        assert_eq!(bucket.time_until(1), Some(Duration::from_secs(1)));
+        counter.hit();
+        assert_eq!(counter.hits(), 2);
+        assert!(!limiter.try_acquire());
+        
+        // The bucket refills from the new time instead of waiting to reach 100s again
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(bucket.available(), 1);
+        clock.set(Duration::from_secs(400));
+        assert_eq!(counter.hits(), 0);
+        assert!(limiter.try_acquire());
+    }
+    
+    #[test]
+    fn test_system_clock_moves_forward() {
+        let clock = SystemClock::new();
+        let first = clock.now();
+        assert!(clock.now() >= first);
+    }
+}
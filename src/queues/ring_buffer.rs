@@ -0,0 +1,444 @@
+//! Ring Buffer
+//! 
+//! A generic circular buffer that replaces `MyCircularQueue`'s `-1` sentinels:
+//! - `Option` returns from every accessor
+//! - Reject, overwrite-oldest or grow behaviour when full
+//! - Indexing, iterators and `as_slices` over the two contiguous halves of storage
+//! - Operations at both ends, so it doubles as a circular deque
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// What `push_back` / `push_front` do when the buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Refuse the new element.
+    Reject,
+    /// Evict the element at the opposite end to make room.
+    OverwriteOldest,
+    /// Double the capacity.
+    Grow,
+}
+
+/// Fixed-capacity (or optionally growable) circular buffer.
+///
+/// Slots `head, head + 1, ..., head + len - 1` (modulo the capacity) are
+/// `Some`; every other slot is `None`.
+pub struct RingBuffer<T> {
+    buf: Box<[Option<T>]>,
+    head: usize,
+    len: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T> RingBuffer<T> {
+    /// Create an empty buffer that rejects pushes once `capacity` elements are stored.
+    pub fn new(capacity: usize) -> Self {
+        RingBuffer {
+            buf: Self::allocate(capacity),
+            head: 0,
+            len: 0,
+            policy: OverflowPolicy::Reject,
+        }
+    }
+    
+    pub fn with_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+    
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+    
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+    
+    /// Append to the back.
+    ///
+    /// Returns `Ok(None)` when stored, `Ok(Some(evicted))` when the oldest element
+    /// was overwritten, and `Err(value)` when the buffer is full and rejects it.
+    pub fn push_back(&mut self, value: T) -> Result<Option<T>, T> {
+        let evicted = if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Reject => return Err(value),
+                // Nothing can be stored, so the new element is itself the one evicted
+                OverflowPolicy::OverwriteOldest if self.capacity() == 0 => return Ok(Some(value)),
+                OverflowPolicy::OverwriteOldest => self.pop_front(),
+                OverflowPolicy::Grow => {
+                    self.grow();
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let slot = self.physical(self.len);
+        self.buf[slot] = Some(value);
+        self.len += 1;
+        Ok(evicted)
+    }
+    
+    /// Prepend to the front; when overwriting, the element at the back is evicted.
+    pub fn push_front(&mut self, value: T) -> Result<Option<T>, T> {
+        let evicted = if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Reject => return Err(value),
+                OverflowPolicy::OverwriteOldest if self.capacity() == 0 => return Ok(Some(value)),
+                OverflowPolicy::OverwriteOldest => self.pop_back(),
+                OverflowPolicy::Grow => {
+                    self.grow();
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        self.head = (self.head + self.capacity() - 1) % self.capacity();
+        self.buf[self.head] = Some(value);
+        self.len += 1;
+        Ok(evicted)
+    }
+    
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = self.buf[self.head].take();
+        self.head = (self.head + 1) % self.capacity();
+        self.len -= 1;
+        value
+    }
+    
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let slot = self.physical(self.len);
+        self.buf[slot].take()
+    }
+    
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+    
+    pub fn back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|last| self.get(last))
+    }
+    
+    /// Element `index` positions from the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        self.buf[self.physical(index)].as_ref()
+    }
+    
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let slot = self.physical(index);
+        self.buf[slot].as_mut()
+    }
+    
+    /// The contents in order, as the part up to the end of storage and the
+    /// wrapped-around part, each iterated front to back.
+    pub fn as_slices(&self) -> (Half<'_, T>, Half<'_, T>) {
+        let (first, second) = self.ranges();
+        (self.buf[first].iter().flatten(), self.buf[second].iter().flatten())
+    }
+    
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (first, second) = self.as_slices();
+        first.chain(second)
+    }
+    
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (first, second) = self.ranges();
+        // The wrapped-around part always ends at or before `head`
+        let (low, high) = self.buf.split_at_mut(first.start);
+        high[..first.len()].iter_mut().chain(low[second].iter_mut()).flatten()
+    }
+    
+    pub fn clear(&mut self) {
+        self.buf.iter_mut().for_each(|slot| *slot = None);
+        self.head = 0;
+        self.len = 0;
+    }
+    
+    /// Double the capacity, moving the elements to the start of the new storage.
+    fn grow(&mut self) {
+        let mut buf = Self::allocate((self.capacity() * 2).max(4));
+        for (i, slot) in buf.iter_mut().enumerate().take(self.len) {
+            *slot = self.buf[self.physical(i)].take();
+        }
+        self.buf = buf;
+        self.head = 0;
+    }
+    
+    fn allocate(capacity: usize) -> Box<[Option<T>]> {
+        (0..capacity).map(|_| None).collect()
+    }
+    
+    fn physical(&self, index: usize) -> usize {
+        (self.head + index) % self.capacity()
+    }
+    
+    fn ranges(&self) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let end = self.head + self.len;
+        if end <= self.capacity() {
+            (self.head..end, 0..0)
+        } else {
+            (self.head..self.capacity(), 0..end - self.capacity())
+        }
+    }
+}
+
+impl<T: Clone> Clone for RingBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut copy = RingBuffer::new(self.capacity()).with_policy(self.policy);
+        for value in self.iter() {
+            let _ = copy.push_back(value.clone());
+        }
+        copy
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RingBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Index<usize> for RingBuffer<T> {
+    type Output = T;
+    
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("RingBuffer index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for RingBuffer<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("RingBuffer index out of bounds")
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Borrowing iterator over a `RingBuffer`, front to back.
+pub type Iter<'a, T> = std::iter::Chain<Half<'a, T>, Half<'a, T>>;
+
+/// One contiguous half of a `RingBuffer`'s contents, from `as_slices`.
+pub type Half<'a, T> = std::iter::Flatten<std::slice::Iter<'a, Option<T>>>;
+
+/// Mutable iterator over a `RingBuffer`, front to back.
+pub type IterMut<'a, T> = std::iter::Flatten<std::iter::Chain<std::slice::IterMut<'a, Option<T>>, std::slice::IterMut<'a, Option<T>>>>;
+
+/// Owning iterator that pops from the front.
+pub struct IntoIter<T> {
+    buffer: RingBuffer<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    
+    fn next(&mut self) -> Option<T> {
+        self.buffer.pop_front()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.buffer.pop_back()
+    }
+}
+
+impl<T> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { buffer: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+    
+    #[test]
+    fn test_reject_when_full() {
+        let mut buffer = RingBuffer::new(3);
+        assert_eq!(buffer.push_back(1), Ok(None));
+        assert_eq!(buffer.push_back(2), Ok(None));
+        assert_eq!(buffer.push_back(3), Ok(None));
+        assert_eq!(buffer.push_back(4), Err(4));
+        assert!(buffer.is_full());
+        assert_eq!(buffer.front(), Some(&1));
+        assert_eq!(buffer.back(), Some(&3));
+        assert_eq!(buffer.pop_front(), Some(1));
+        assert_eq!(buffer.push_back(4), Ok(None));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+    
+    #[test]
+    fn test_empty_returns_none() {
+        let mut buffer: RingBuffer<i32> = RingBuffer::new(2);
+        assert_eq!(buffer.front(), None);
+        assert_eq!(buffer.back(), None);
+        assert_eq!(buffer.pop_front(), None);
+        assert_eq!(buffer.pop_back(), None);
+        assert_eq!(buffer.get(0), None);
+    }
+    
+    #[test]
+    fn test_zero_capacity() {
+        let mut reject = RingBuffer::new(0);
+        assert_eq!(reject.push_back('a'), Err('a'));
+        assert!(reject.is_full() && reject.is_empty());
+        
+        let mut overwrite = RingBuffer::new(0).with_policy(OverflowPolicy::OverwriteOldest);
+        assert_eq!(overwrite.push_back('a'), Ok(Some('a')));
+        assert_eq!(overwrite.push_front('b'), Ok(Some('b')));
+        
+        let mut grow = RingBuffer::new(0).with_policy(OverflowPolicy::Grow);
+        assert_eq!(grow.push_back('a'), Ok(None));
+        assert_eq!(grow.capacity(), 4);
+    }
+    
+    #[test]
+    fn test_overwrite_oldest() {
+        let mut buffer = RingBuffer::new(3).with_policy(OverflowPolicy::OverwriteOldest);
+        for i in 1..=3 {
+            assert_eq!(buffer.push_back(i), Ok(None));
+        }
+        assert_eq!(buffer.push_back(4), Ok(Some(1)));
+        assert_eq!(buffer.push_back(5), Ok(Some(2)));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(buffer.push_front(0), Ok(Some(5)));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![0, 3, 4]);
+    }
+    
+    #[test]
+    fn test_grow_keeps_order() {
+        let mut buffer = RingBuffer::new(2).with_policy(OverflowPolicy::Grow);
+        buffer.push_back(1).unwrap();
+        buffer.push_back(2).unwrap();
+        buffer.pop_front();
+        buffer.push_back(3).unwrap();
+        buffer.push_front(0).unwrap();
+        buffer.push_back(4).unwrap();
+        assert_eq!(buffer.capacity(), 4);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![0, 2, 3, 4]);
+    }
+    
+    #[test]
+    fn test_as_slices_and_indexing() {
+        let mut buffer = RingBuffer::new(4);
+        for i in 0..4 {
+            buffer.push_back(i).unwrap();
+        }
+        buffer.pop_front();
+        buffer.pop_front();
+        buffer.push_back(4).unwrap();
+        let (first, second) = buffer.as_slices();
+        assert_eq!((first.copied().collect::<Vec<_>>(), second.copied().collect::<Vec<_>>()), (vec![2, 3], vec![4]));
+        assert_eq!(buffer[0], 2);
+        assert_eq!(buffer[2], 4);
+        
+        buffer[1] = 30;
+        for value in buffer.iter_mut() {
+            *value += 1;
+        }
+        *buffer.iter_mut().next().unwrap() *= 10;
+        *buffer.iter_mut().next_back().unwrap() *= 10;
+        assert_eq!(buffer.into_iter().collect::<Vec<_>>(), vec![30, 31, 50]);
+    }
+    
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_index_out_of_bounds() {
+        let mut buffer = RingBuffer::new(2);
+        buffer.push_back(1).unwrap();
+        let _ = buffer[1];
+    }
+    
+    #[test]
+    fn test_deque_operations() {
+        let mut deque = RingBuffer::new(3);
+        deque.push_back(2).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_back(3).unwrap();
+        assert_eq!(deque.push_front(0), Err(0));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert!(deque.is_empty());
+    }
+    
+    #[test]
+    fn test_drops_every_element_once() {
+        let tracker = Rc::new(());
+        {
+            let mut buffer = RingBuffer::new(3).with_policy(OverflowPolicy::Grow);
+            for _ in 0..10 {
+                buffer.push_back(Rc::clone(&tracker)).unwrap();
+            }
+            drop(buffer.pop_front());
+            let cloned = buffer.clone();
+            assert_eq!(Rc::strong_count(&tracker), 19);
+            drop(cloned);
+        }
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_grow_matches_vecdeque(ops in proptest::collection::vec((0u8..4, any::<i16>()), 0..300)) {
+            let mut buffer = RingBuffer::new(1).with_policy(OverflowPolicy::Grow);
+            let mut model = VecDeque::new();
+            for (op, value) in ops {
+                match op {
+                    0 => {
+                        prop_assert_eq!(buffer.push_back(value), Ok(None));
+                        model.push_back(value);
+                    }
+                    1 => {
+                        prop_assert_eq!(buffer.push_front(value), Ok(None));
+                        model.push_front(value);
+                    }
+                    2 => prop_assert_eq!(buffer.pop_front(), model.pop_front()),
+                    _ => prop_assert_eq!(buffer.pop_back(), model.pop_back()),
+                }
+                prop_assert_eq!(buffer.len(), model.len());
+                prop_assert!(buffer.iter().eq(model.iter()));
+            }
+        }
+    }
+}
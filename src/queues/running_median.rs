@@ -0,0 +1,230 @@
+//! Running Median
+//! 
+//! Median maintenance with two heaps: a max-heap holding the lower half and a
+//! min-heap holding the upper half, kept within one element of each other.
+//! Removal is lazy: removed values are recorded and discarded once they reach
+//! the top of their heap.
+//! - O(log n) amortized insert and remove, O(1) median
+//! - Sliding Window Median
+
+use super::priority_queue::{Compare, PriorityQueue};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct RunningMedian<T> {
+    low: PriorityQueue<T>,
+    high: PriorityQueue<T>,
+    /// Live elements on each side, excluding ones awaiting lazy removal.
+    low_len: usize,
+    high_len: usize,
+    /// Removed values still physically present in one of the heaps.
+    pending: HashMap<T, usize>,
+    /// How many copies of each value are currently in the multiset.
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Ord + Hash + Clone> RunningMedian<T> {
+    pub fn new() -> Self {
+        RunningMedian {
+            low: PriorityQueue::new(),
+            high: PriorityQueue::new_min(),
+            low_len: 0,
+            high_len: 0,
+            pending: HashMap::new(),
+            counts: HashMap::new(),
+        }
+    }
+    
+    pub fn insert(&mut self, value: T) {
+        *self.counts.entry(value.clone()).or_insert(0) += 1;
+        if self.low.peek().is_none_or(|top| value <= *top) {
+            self.low.push(value);
+            self.low_len += 1;
+        } else {
+            self.high.push(value);
+            self.high_len += 1;
+        }
+        self.rebalance();
+    }
+    
+    /// Remove one copy of `value`; returns `false` if it was not present.
+    pub fn remove(&mut self, value: &T) -> bool {
+        match self.counts.get_mut(value) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => return false,
+        }
+        *self.pending.entry(value.clone()).or_insert(0) += 1;
+        
+        // A value equal to the lower top may live in either heap; charging it to the
+        // lower half is still correct because both tops then hold that same value
+        if self.low.peek().is_some_and(|top| value <= top) {
+            self.low_len -= 1;
+            prune(&mut self.low, &mut self.pending);
+        } else {
+            self.high_len -= 1;
+            prune(&mut self.high, &mut self.pending);
+        }
+        self.rebalance();
+        true
+    }
+    
+    pub fn len(&self) -> usize {
+        self.low_len + self.high_len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// The lower and upper median; they are the same element when `len` is odd.
+    pub fn medians(&self) -> Option<(&T, &T)> {
+        let low = self.low.peek()?;
+        if self.low_len > self.high_len {
+            Some((low, low))
+        } else {
+            Some((low, self.high.peek()?))
+        }
+    }
+    
+    /// Keep `low_len == high_len` or `low_len == high_len + 1`.
+    fn rebalance(&mut self) {
+        if self.low_len > self.high_len + 1 {
+            let value = self.low.pop().expect("low half is non-empty");
+            self.high.push(value);
+            self.low_len -= 1;
+            self.high_len += 1;
+            prune(&mut self.low, &mut self.pending);
+        } else if self.high_len > self.low_len {
+            let value = self.high.pop().expect("high half is non-empty");
+            self.low.push(value);
+            self.high_len -= 1;
+            self.low_len += 1;
+            prune(&mut self.high, &mut self.pending);
+        }
+    }
+}
+
+impl<T: Ord + Hash + Clone + Copy + Into<f64>> RunningMedian<T> {
+    /// The median as a float, averaging the two middle values when `len` is even.
+    pub fn median(&self) -> Option<f64> {
+        self.medians().map(|(low, high)| ((*low).into() + (*high).into()) / 2.0)
+    }
+}
+
+impl<T: Ord + Hash + Clone> Default for RunningMedian<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pop values off the top of `heap` that have already been removed.
+fn prune<T, C>(heap: &mut PriorityQueue<T, C>, pending: &mut HashMap<T, usize>)
+where
+    T: Hash + Eq,
+    C: Compare<T>,
+{
+    while let Some(top) = heap.peek() {
+        match pending.get_mut(top) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                heap.pop();
+            }
+            _ => break,
+        }
+    }
+}
+
+/// Problem: Sliding Window Median
+/// Return the median of every window of size `k`.
+pub fn median_sliding_window(nums: &[i32], k: usize) -> Vec<f64> {
+    if k == 0 || nums.len() < k {
+        return vec![];
+    }
+    let mut window = RunningMedian::new();
+    let mut result = Vec::with_capacity(nums.len() - k + 1);
+    for (i, &num) in nums.iter().enumerate() {
+        window.insert(num);
+        if i >= k {
+            window.remove(&nums[i - k]);
+        }
+        if i + 1 >= k {
+            result.push(window.median().expect("window holds k elements"));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    fn sorted_median(values: &[i32]) -> f64 {
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            sorted[mid] as f64
+        } else {
+            (sorted[mid - 1] as f64 + sorted[mid] as f64) / 2.0
+        }
+    }
+    
+    #[test]
+    fn test_insert_and_remove() {
+        let mut median = RunningMedian::new();
+        assert_eq!(median.medians(), None);
+        for x in [5, 15, 1, 3] {
+            median.insert(x);
+        }
+        assert_eq!(median.medians(), Some((&3, &5)));
+        assert_eq!(median.median(), Some(4.0));
+        
+        assert!(median.remove(&5));
+        assert!(!median.remove(&42));
+        assert_eq!(median.median(), Some(3.0));
+        assert!(median.remove(&3));
+        assert!(median.remove(&1));
+        assert_eq!(median.median(), Some(15.0));
+        assert!(median.remove(&15));
+        assert!(median.is_empty());
+    }
+    
+    #[test]
+    fn test_median_sliding_window() {
+        let nums = vec![1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(median_sliding_window(&nums, 3), vec![1.0, -1.0, -1.0, 3.0, 5.0, 6.0]);
+        assert_eq!(median_sliding_window(&[2, 2, 2, 2], 2), vec![2.0, 2.0, 2.0]);
+        assert_eq!(median_sliding_window(&[i32::MAX, i32::MAX], 2), vec![i32::MAX as f64]);
+        assert!(median_sliding_window(&[1], 2).is_empty());
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_sliding_window_matches_sorting(nums in proptest::collection::vec(-20i32..20, 1..80), k in 1usize..10) {
+            let expected: Vec<f64> = nums.windows(k).map(sorted_median).collect();
+            prop_assert_eq!(median_sliding_window(&nums, k), expected);
+        }
+        
+        #[test]
+        fn prop_random_removals_match_sorting(ops in proptest::collection::vec((any::<bool>(), -10i32..10), 0..200)) {
+            let mut median = RunningMedian::new();
+            let mut reference: Vec<i32> = Vec::new();
+            for (insert, value) in ops {
+                if insert {
+                    median.insert(value);
+                    reference.push(value);
+                } else {
+                    let position = reference.iter().position(|&x| x == value);
+                    prop_assert_eq!(median.remove(&value), position.is_some());
+                    if let Some(position) = position {
+                        reference.swap_remove(position);
+                    }
+                }
+                prop_assert_eq!(median.len(), reference.len());
+                let expected = if reference.is_empty() { None } else { Some(sorted_median(&reference)) };
+                prop_assert_eq!(median.median(), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Task Schedulers
+//! 
+//! Decide which flow's task runs next when several flows (tenants, clients,
+//! connections) each have their own FIFO of pending tasks.
+//! - Round robin: one task per non-empty flow in turn
+//! - Weighted fair queueing: flows get service in proportion to their weights,
+//!   ordered by virtual finish time in a `PriorityQueue`
+
+use super::priority_queue::PriorityQueue;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Problem: Round-Robin Scheduler
+/// Serve flows in turn, one task each, skipping flows with nothing queued.
+pub struct RoundRobinScheduler<K, T> {
+    queues: HashMap<K, VecDeque<T>>,
+    /// Flows with pending tasks, in the order they will next be served.
+    active: VecDeque<K>,
+    len: usize,
+}
+
+impl<K: Hash + Eq + Clone, T> RoundRobinScheduler<K, T> {
+    pub fn new() -> Self {
+        RoundRobinScheduler {
+            queues: HashMap::new(),
+            active: VecDeque::new(),
+            len: 0,
+        }
+    }
+    
+    pub fn enqueue(&mut self, flow: K, task: T) {
+        let queue = self.queues.entry(flow.clone()).or_default();
+        if queue.is_empty() {
+            self.active.push_back(flow);
+        }
+        queue.push_back(task);
+        self.len += 1;
+    }
+    
+    pub fn next_task(&mut self) -> Option<(K, T)> {
+        let flow = self.active.pop_front()?;
+        let queue = self.queues.get_mut(&flow).expect("active flows have a queue");
+        let task = queue.pop_front().expect("active flows have pending tasks");
+        if !queue.is_empty() {
+            self.active.push_back(flow.clone());
+        }
+        self.len -= 1;
+        Some((flow, task))
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: Hash + Eq + Clone, T> Default for RoundRobinScheduler<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fixed-point scale for virtual times, so `cost / weight` stays exact enough in integers.
+const VIRTUAL_SCALE: u128 = 1 << 32;
+
+/// A queued task tagged with its virtual finish time; `seq` breaks ties in arrival order.
+struct Tagged<K, T> {
+    finish: u128,
+    seq: u64,
+    flow: K,
+    task: T,
+}
+
+impl<K, T> PartialEq for Tagged<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.finish, self.seq) == (other.finish, other.seq)
+    }
+}
+
+impl<K, T> Eq for Tagged<K, T> {}
+
+impl<K, T> PartialOrd for Tagged<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, T> Ord for Tagged<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.finish, self.seq).cmp(&(other.finish, other.seq))
+    }
+}
+
+/// Problem: Weighted Fair Queueing
+/// Serve tasks in order of virtual finish time: a task of `cost` on a flow of
+/// `weight` finishes `cost / weight` after the later of "now" (in virtual time)
+/// and the flow's previous task.
+pub struct WeightedFairScheduler<K, T> {
+    queue: PriorityQueue<Tagged<K, T>>,
+    weights: HashMap<K, u32>,
+    last_finish: HashMap<K, u128>,
+    virtual_time: u128,
+    seq: u64,
+}
+
+impl<K: Hash + Eq + Clone, T> WeightedFairScheduler<K, T> {
+    pub fn new() -> Self {
+        WeightedFairScheduler {
+            queue: PriorityQueue::new_min(),
+            weights: HashMap::new(),
+            last_finish: HashMap::new(),
+            virtual_time: 0,
+            seq: 0,
+        }
+    }
+    
+    /// Set a flow's weight; flows default to weight 1. A weight of 0 is treated as 1.
+    pub fn set_weight(&mut self, flow: K, weight: u32) {
+        self.weights.insert(flow, weight.max(1));
+    }
+    
+    /// Queue a task costing one unit of service.
+    pub fn enqueue(&mut self, flow: K, task: T) {
+        self.enqueue_with_cost(flow, task, 1);
+    }
+    
+    pub fn enqueue_with_cost(&mut self, flow: K, task: T, cost: u64) {
+        let weight = u128::from(self.weights.get(&flow).copied().unwrap_or(1));
+        let start = self.last_finish.get(&flow).map_or(self.virtual_time, |&last| last.max(self.virtual_time));
+        let finish = start + u128::from(cost) * VIRTUAL_SCALE / weight;
+        self.last_finish.insert(flow.clone(), finish);
+        self.queue.push(Tagged {
+            finish,
+            seq: self.seq,
+            flow,
+            task,
+        });
+        self.seq += 1;
+    }
+    
+    pub fn next_task(&mut self) -> Option<(K, T)> {
+        let Tagged { finish, flow, task, .. } = self.queue.pop()?;
+        self.virtual_time = self.virtual_time.max(finish);
+        Some((flow, task))
+    }
+    
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+impl<K: Hash + Eq + Clone, T> Default for WeightedFairScheduler<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queues::rate_limit::{MockClock, TokenBucket};
+    use std::time::Duration;
+    
+    #[test]
+    fn test_round_robin() {
+        let mut scheduler = RoundRobinScheduler::new();
+        for task in 1..=3 {
+            scheduler.enqueue("a", task);
+        }
+        scheduler.enqueue("b", 10);
+        scheduler.enqueue("c", 20);
+        scheduler.enqueue("c", 21);
+        
+        let order: Vec<_> = std::iter::from_fn(|| scheduler.next_task()).collect();
+        assert_eq!(order, vec![("a", 1), ("b", 10), ("c", 20), ("a", 2), ("c", 21), ("a", 3)]);
+        assert!(scheduler.is_empty());
+        
+        // A flow that drained and comes back joins the end of the rotation
+        scheduler.enqueue("b", 11);
+        assert_eq!(scheduler.next_task(), Some(("b", 11)));
+    }
+    
+    #[test]
+    fn test_weighted_fair_shares() {
+        let mut scheduler = WeightedFairScheduler::new();
+        scheduler.set_weight("gold", 3);
+        scheduler.set_weight("bronze", 1);
+        for i in 0..30 {
+            scheduler.enqueue("gold", i);
+            scheduler.enqueue("bronze", i);
+        }
+        
+        // While both flows are backlogged, gold gets three turns per bronze turn
+        let first: Vec<&str> = (0..20).map(|_| scheduler.next_task().unwrap().0).collect();
+        assert_eq!(first.iter().filter(|&&flow| flow == "gold").count(), 15);
+        assert_eq!(scheduler.len(), 40);
+    }
+    
+    #[test]
+    fn test_weighted_fair_cost_and_idle_flows() {
+        let mut scheduler = WeightedFairScheduler::new();
+        scheduler.enqueue_with_cost("bulk", "big", 4);
+        scheduler.enqueue("chat", "m1");
+        scheduler.enqueue("chat", "m2");
+        let order: Vec<_> = std::iter::from_fn(|| scheduler.next_task()).map(|(_, task)| task).collect();
+        assert_eq!(order, vec!["m1", "m2", "big"]);
+        
+        // An idle flow does not build up credit while it is away
+        for task in ["b0", "b1", "b2", "b3", "b4"] {
+            scheduler.enqueue("busy", task);
+        }
+        for _ in 0..3 {
+            scheduler.next_task();
+        }
+        scheduler.enqueue("returning", "r0");
+        assert_eq!(scheduler.next_task(), Some(("busy", "b3")));
+        assert_eq!(scheduler.next_task(), Some(("returning", "r0")));
+    }
+    
+    #[test]
+    fn test_rate_limited_dispatch_under_mock_clock() {
+        // Dispatch from the scheduler only when the token bucket allows, tick by tick
+        let clock = MockClock::new();
+        let mut bucket = TokenBucket::new(clock.clone(), 2, Duration::from_millis(100));
+        let mut scheduler = RoundRobinScheduler::new();
+        for i in 0..3 {
+            scheduler.enqueue("x", i);
+            scheduler.enqueue("y", i);
+        }
+        
+        let mut dispatched_at = Vec::new();
+        for tick in 0..6 {
+            while !scheduler.is_empty() && bucket.try_acquire(1) {
+                let (flow, _) = scheduler.next_task().unwrap();
+                dispatched_at.push((tick, flow));
+            }
+            clock.advance(Duration::from_millis(100));
+        }
+        assert_eq!(
+            dispatched_at,
+            vec![(0, "x"), (0, "y"), (1, "x"), (2, "y"), (3, "x"), (4, "y")]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! SPSC Ring Buffer
+//! 
+//! A bounded single-producer single-consumer queue: a fixed array of slots
+//! plus two atomic counters. Only the producer advances `tail` and only the
+//! consumer advances `head`, so each side publishes its progress with a
+//! `Release` store and observes the other's with an `Acquire` load.
+//! - `channel(capacity)` returns a `Producer` and a `Consumer` that can move to different threads
+//! - Counters run modulo `2 * capacity`, so full and empty stay distinguishable
+//! - Each slot sits behind its own `Mutex`, which the counters keep uncontended
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+struct Shared<T> {
+    /// `Some` from `head` up to `tail`, `None` everywhere else.
+    slots: Box<[Mutex<Option<T>>]>,
+    /// Pop counter modulo `2 * capacity`; written only by the consumer.
+    head: AtomicUsize,
+    /// Push counter modulo `2 * capacity`; written only by the producer.
+    tail: AtomicUsize,
+}
+
+impl<T> Shared<T> {
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+    
+    /// The slot for `counter`. The other side never touches it at the same time,
+    /// so the lock is free; a poisoned lock still holds a valid `Option`.
+    fn slot(&self, counter: usize) -> MutexGuard<'_, Option<T>> {
+        let slot = &self.slots[counter % self.capacity()];
+        slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    
+    fn next(&self, counter: usize) -> usize {
+        if counter + 1 == 2 * self.capacity() {
+            0
+        } else {
+            counter + 1
+        }
+    }
+    
+    /// Number of items between the two counters.
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        (tail + 2 * self.capacity() - head) % (2 * self.capacity())
+    }
+}
+
+/// The sending half of an SPSC channel.
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+    /// Local copy of `tail`; only this side writes it.
+    tail: usize,
+}
+
+/// The receiving half of an SPSC channel.
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+    /// Local copy of `head`; only this side writes it.
+    head: usize,
+}
+
+/// Problem: Single-Producer Single-Consumer Queue
+/// Create a channel that holds at most `capacity` items. Panics if `capacity` is zero.
+pub fn channel<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    assert!(capacity > 0, "an SPSC ring needs a positive capacity");
+    assert!(capacity <= usize::MAX / 2, "SPSC ring capacity too large");
+    let shared = Arc::new(Shared {
+        slots: (0..capacity).map(|_| Mutex::new(None)).collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+            tail: 0,
+        },
+        Consumer { shared, head: 0 },
+    )
+}
+
+impl<T> Producer<T> {
+    /// Push `value`, handing it back if the ring is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let head = self.shared.head.load(Ordering::Acquire);
+        if self.shared.distance(head, self.tail) == self.shared.capacity() {
+            return Err(value);
+        }
+        *self.shared.slot(self.tail) = Some(value);
+        self.tail = self.shared.next(self.tail);
+        self.shared.tail.store(self.tail, Ordering::Release);
+        Ok(())
+    }
+    
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+    
+    /// Items currently queued, as seen by the producer (may be stale by the time it returns).
+    pub fn len(&self) -> usize {
+        self.shared.distance(self.shared.head.load(Ordering::Acquire), self.tail)
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Pop the oldest item, or `None` if the ring is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        if self.head == tail {
+            return None;
+        }
+        let value = self.shared.slot(self.head).take();
+        self.head = self.shared.next(self.head);
+        self.shared.head.store(self.head, Ordering::Release);
+        value
+    }
+    
+    /// Items currently queued, as seen by the consumer (may be stale by the time it returns).
+    pub fn len(&self) -> usize {
+        self.shared.distance(self.head, self.shared.tail.load(Ordering::Acquire))
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+    use std::thread;
+    
+    #[test]
+    fn test_fifo_and_capacity() {
+        let (mut tx, mut rx) = channel(3);
+        assert_eq!(rx.pop(), None);
+        for i in 0..3 {
+            tx.push(i).unwrap();
+        }
+        assert_eq!(tx.push(3), Err(3));
+        assert_eq!(tx.len(), 3);
+        assert_eq!(rx.pop(), Some(0));
+        tx.push(3).unwrap();
+        assert_eq!((0..4).map(|_| rx.pop()).collect::<Vec<_>>(), vec![Some(1), Some(2), Some(3), None]);
+        assert!(rx.is_empty());
+        assert_eq!(tx.capacity(), 3);
+    }
+    
+    #[test]
+    fn test_remaining_items_are_dropped() {
+        let item = Rc::new(());
+        {
+            let (mut tx, mut rx) = channel(4);
+            for _ in 0..3 {
+                tx.push(Rc::clone(&item)).unwrap();
+            }
+            drop(rx.pop());
+            assert_eq!(Rc::strong_count(&item), 3);
+        }
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+    
+    #[test]
+    fn test_stress_across_threads() {
+        const ITEMS: usize = 100_000;
+        let (mut tx, mut rx) = channel(64);
+        
+        let producer = thread::spawn(move || {
+            for i in 0..ITEMS {
+                let mut value = i;
+                while let Err(rejected) = tx.push(value) {
+                    value = rejected;
+                    thread::yield_now();
+                }
+            }
+        });
+        
+        let mut expected = 0;
+        while expected < ITEMS {
+            match rx.pop() {
+                Some(value) => {
+                    assert_eq!(value, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(rx.pop(), None);
+    }
+    
+    #[test]
+    fn test_stress_heap_values() {
+        let (mut tx, mut rx) = channel(8);
+        let producer = thread::spawn(move || {
+            for i in 0..20_000 {
+                let mut value = vec![i; 4];
+                while let Err(rejected) = tx.push(value) {
+                    value = rejected;
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut received = 0;
+        while received < 20_000 {
+            match rx.pop() {
+                Some(value) => {
+                    assert_eq!(value, vec![received; 4]);
+                    received += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+    }
+    
+    /// Every interleaving of 6 pushes and 6 pops on a capacity-2 ring, checked against a model.
+    #[test]
+    fn test_all_interleavings() {
+        const PUSHES: usize = 6;
+        const POPS: usize = 6;
+        let steps = PUSHES + POPS;
+        
+        for schedule in 0u32..(1 << steps) {
+            if schedule.count_ones() as usize != PUSHES {
+                continue;
+            }
+            let (mut tx, mut rx) = channel(2);
+            let mut model = VecDeque::new();
+            let mut next_value = 0;
+            for step in 0..steps {
+                if schedule & (1 << step) != 0 {
+                    let expected = if model.len() == 2 {
+                        Err(next_value)
+                    } else {
+                        model.push_back(next_value);
+                        Ok(())
+                    };
+                    assert_eq!(tx.push(next_value), expected);
+                    next_value += 1;
+                } else {
+                    assert_eq!(rx.pop(), model.pop_front());
+                }
+                assert_eq!(tx.len(), model.len());
+                assert_eq!(rx.len(), model.len());
+            }
+        }
+    }
+    
+    #[test]
+    fn test_counter_wraparound() {
+        let (mut tx, mut rx) = channel(3);
+        for round in 0..10 {
+            for i in 0..3 {
+                tx.push(round * 3 + i).unwrap();
+            }
+            assert_eq!(tx.push(-1), Err(-1));
+            assert_eq!(rx.len(), 3);
+            for i in 0..3 {
+                assert_eq!(rx.pop(), Some(round * 3 + i));
+            }
+            assert_eq!(rx.pop(), None);
+            // Offset the counters so every phase of the 2 * capacity cycle gets exercised
+            tx.push(0).unwrap();
+            assert_eq!(rx.pop(), Some(0));
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Implicit-State BFS
+//! 
+//! Breadth-first search over states that are generated on demand instead of
+//! stored as a graph: the caller supplies a start state, a goal test and a
+//! neighbor function. Both searches reconstruct the path they found and report
+//! how many states they expanded.
+//! - `bfs_shortest`: one-directional BFS with an arbitrary goal predicate
+//! - `bidirectional_bfs`: searches from both ends of a symmetric move relation
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Outcome of a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<S> {
+    /// States from start to goal inclusive, or `None` if the goal is unreachable.
+    pub path: Option<Vec<S>>,
+    /// Number of states whose neighbors were generated.
+    pub explored: usize,
+}
+
+impl<S> SearchResult<S> {
+    /// Number of moves on the path.
+    pub fn distance(&self) -> Option<usize> {
+        self.path.as_ref().map(|path| path.len() - 1)
+    }
+}
+
+/// Follow parent links back from `state` to the root of the search.
+fn trace_back<S: Hash + Eq + Clone>(parents: &HashMap<S, Option<S>>, state: &S) -> Vec<S> {
+    let mut path = vec![state.clone()];
+    let mut current = state;
+    while let Some(Some(parent)) = parents.get(current) {
+        path.push(parent.clone());
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
+/// Problem: Shortest Path over Implicit States
+/// Breadth-first search from `start` to the nearest state satisfying `is_goal`.
+pub fn bfs_shortest<S, I>(start: S, is_goal: impl Fn(&S) -> bool, mut neighbors: impl FnMut(&S) -> I) -> SearchResult<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents: HashMap<S, Option<S>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    let mut explored = 0;
+    parents.insert(start.clone(), None);
+    queue.push_back(start);
+    
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            return SearchResult {
+                path: Some(trace_back(&parents, &state)),
+                explored,
+            };
+        }
+        explored += 1;
+        for next in neighbors(&state) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(state.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    SearchResult { path: None, explored }
+}
+
+/// One side of a bidirectional search.
+struct Frontier<S> {
+    parents: HashMap<S, Option<S>>,
+    depth: HashMap<S, usize>,
+    level: Vec<S>,
+}
+
+impl<S: Hash + Eq + Clone> Frontier<S> {
+    fn new(root: S) -> Self {
+        Frontier {
+            parents: HashMap::from([(root.clone(), None)]),
+            depth: HashMap::from([(root.clone(), 0)]),
+            level: vec![root],
+        }
+    }
+    
+    /// Expand one whole level. Returns the best state where the two searches meet,
+    /// as `(total distance, meeting state)`.
+    fn expand<I: IntoIterator<Item = S>>(
+        &mut self,
+        other: &Frontier<S>,
+        neighbors: &mut impl FnMut(&S) -> I,
+        explored: &mut usize,
+    ) -> Option<(usize, S)> {
+        let mut best: Option<(usize, S)> = None;
+        let mut next_level = Vec::new();
+        for state in std::mem::take(&mut self.level) {
+            *explored += 1;
+            let next_depth = self.depth[&state] + 1;
+            for next in neighbors(&state) {
+                if self.parents.contains_key(&next) {
+                    continue;
+                }
+                if let Some(&other_depth) = other.depth.get(&next) {
+                    let total = next_depth + other_depth;
+                    if best.as_ref().is_none_or(|(d, _)| total < *d) {
+                        best = Some((total, next.clone()));
+                    }
+                }
+                self.parents.insert(next.clone(), Some(state.clone()));
+                self.depth.insert(next.clone(), next_depth);
+                next_level.push(next);
+            }
+        }
+        self.level = next_level;
+        best
+    }
+}
+
+/// Problem: Bidirectional BFS
+/// Shortest path from `start` to `goal`, growing the smaller frontier each round.
+/// `neighbors` must be symmetric: `b` is a neighbor of `a` iff `a` is a neighbor of `b`.
+pub fn bidirectional_bfs<S, I>(start: S, goal: S, mut neighbors: impl FnMut(&S) -> I) -> SearchResult<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    if start == goal {
+        return SearchResult {
+            path: Some(vec![start]),
+            explored: 0,
+        };
+    }
+    
+    let mut forward = Frontier::new(start);
+    let mut backward = Frontier::new(goal);
+    let mut explored = 0;
+    
+    while !forward.level.is_empty() && !backward.level.is_empty() {
+        let forward_turn = forward.level.len() <= backward.level.len();
+        let meeting = if forward_turn {
+            forward.expand(&backward, &mut neighbors, &mut explored)
+        } else {
+            backward.expand(&forward, &mut neighbors, &mut explored)
+        };
+        
+        if let Some((_, meet)) = meeting {
+            let mut path = trace_back(&forward.parents, &meet);
+            let mut tail = trace_back(&backward.parents, &meet);
+            tail.reverse();
+            path.extend(tail.into_iter().skip(1));
+            return SearchResult {
+                path: Some(path),
+                explored,
+            };
+        }
+    }
+    SearchResult { path: None, explored }
+}
+
+/// Parse a 4-digit lock combination into its numeric value.
+fn parse_combination(combination: &str) -> Option<u16> {
+    if combination.len() != 4 || !combination.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    combination.parse().ok()
+}
+
+/// The 8 combinations one wheel turn away from `state`.
+fn lock_moves(state: u16) -> impl Iterator<Item = u16> {
+    [1u16, 10, 100, 1000].into_iter().flat_map(move |place| {
+        let digit = state / place % 10;
+        let base = state - digit * place;
+        [(digit + 1) % 10, (digit + 9) % 10].map(|d| base + d * place)
+    })
+}
+
+/// Problem: Open the Lock (path)
+/// The sequence of combinations from "0000" to `target` avoiding `deadends`, or `None`.
+/// States are encoded as `u16` values 0..=9999 and searched bidirectionally.
+pub fn open_lock_sequence(deadends: &[String], target: &str) -> Option<Vec<String>> {
+    let target = parse_combination(target)?;
+    let mut dead = vec![false; 10_000];
+    for deadend in deadends {
+        if let Some(state) = parse_combination(deadend) {
+            dead[state as usize] = true;
+        }
+    }
+    if dead[0] || dead[target as usize] {
+        return None;
+    }
+    
+    let result = bidirectional_bfs(0u16, target, |&state| lock_moves(state).filter(|&next| !dead[next as usize]));
+    result
+        .path
+        .map(|path| path.into_iter().map(|state| format!("{:04}", state)).collect())
+}
+
+/// Problem: Open the Lock
+/// Find the minimum number of turns required to open the lock.
+pub fn open_lock(deadends: Vec<String>, target: String) -> i32 {
+    open_lock_sequence(&deadends, &target).map_or(-1, |path| path.len() as i32 - 1)
+}
+
+/// What is left after subtracting each perfect square up to `remaining`,
+/// largest square first. Bounded by `isqrt` so squaring never overflows.
+fn subtract_squares(remaining: i32) -> impl Iterator<Item = i32> {
+    (1..=remaining.max(0).isqrt()).rev().map(move |i| remaining - i * i)
+}
+
+/// Problem: Perfect Squares
+/// Find the least number of perfect square numbers that sum to n.
+pub fn num_squares(n: i32) -> i32 {
+    let result = bfs_shortest(n, |&remaining| remaining == 0, |&remaining| subtract_squares(remaining));
+    result.distance().map_or(0, |d| d as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_bfs_shortest_path() {
+        // Reach 10 from 1 using "+1" and "*2"
+        let result = bfs_shortest(1u32, |&x| x == 10, |&x| [x + 1, x * 2].into_iter().filter(|&y| y <= 10));
+        assert_eq!(result.path, Some(vec![1, 2, 4, 5, 10]));
+        assert_eq!(result.distance(), Some(4));
+        assert!(result.explored > 0);
+        
+        let unreachable = bfs_shortest(1u32, |&x| x == 0, |&x| [x + 1].into_iter().filter(|&y| y < 5));
+        assert_eq!(unreachable.path, None);
+        assert_eq!(unreachable.explored, 4);
+    }
+    
+    #[test]
+    fn test_open_lock_sequence() {
+        let deadends: Vec<String> = ["0201", "0101", "0102", "1212", "2002"].iter().map(|s| s.to_string()).collect();
+        let path = open_lock_sequence(&deadends, "0202").unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first().map(String::as_str), Some("0000"));
+        assert_eq!(path.last().map(String::as_str), Some("0202"));
+        for step in path.windows(2) {
+            let differing = step[0].chars().zip(step[1].chars()).filter(|(a, b)| a != b).count();
+            assert_eq!(differing, 1);
+            assert!(!deadends.contains(&step[1]));
+        }
+        
+        let trapped: Vec<String> = ["8887", "8889", "8878", "8898", "8788", "8988", "7888", "9888"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(open_lock(trapped, "8888".to_string()), -1);
+        assert_eq!(open_lock(vec!["0000".to_string()], "8888".to_string()), -1);
+        assert_eq!(open_lock(vec![], "0000".to_string()), 0);
+        assert_eq!(open_lock(vec![], "12a4".to_string()), -1);
+    }
+    
+    #[test]
+    fn test_bidirectional_explores_less() {
+        let forward = bfs_shortest(0u16, |&s| s == 5555, |&s| lock_moves(s));
+        let both = bidirectional_bfs(0u16, 5555, |&s| lock_moves(s));
+        assert_eq!(forward.distance(), Some(20));
+        assert_eq!(both.distance(), Some(20));
+        assert!(both.explored < forward.explored);
+    }
+    
+    #[test]
+    fn test_num_squares_on_generic_bfs() {
+        assert_eq!(num_squares(12), 3);
+        assert_eq!(num_squares(13), 2);
+        assert_eq!(num_squares(1), 1);
+        assert_eq!(num_squares(0), 0);
+    }
+    
+    #[test]
+    fn test_num_squares_large_n() {
+        let largest = 46_340 * 46_340;
+        assert_eq!(num_squares(largest), 1);
+        // The largest square below i32::MAX is 46340^2; 46341^2 would overflow
+        let remainders: Vec<i32> = subtract_squares(i32::MAX).collect();
+        assert_eq!(remainders.len(), 46_340);
+        assert_eq!(remainders[0], i32::MAX - largest);
+        assert_eq!(remainders.last(), Some(&(i32::MAX - 1)));
+        assert_eq!(subtract_squares(0).count(), 0);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_bidirectional_matches_bfs(edges in proptest::collection::vec((0u8..30, 0u8..30), 0..60), start in 0u8..30, goal in 0u8..30) {
+            let mut adjacency = vec![Vec::new(); 30];
+            for &(a, b) in &edges {
+                adjacency[a as usize].push(b);
+                adjacency[b as usize].push(a);
+            }
+            let next = |s: &u8| adjacency[*s as usize].clone();
+            let one = bfs_shortest(start, |&s| s == goal, next);
+            let two = bidirectional_bfs(start, goal, next);
+            prop_assert_eq!(one.distance(), two.distance());
+            
+            if let Some(path) = two.path {
+                prop_assert_eq!(path.first(), Some(&start));
+                prop_assert_eq!(path.last(), Some(&goal));
+                for step in path.windows(2) {
+                    prop_assert!(adjacency[step[0] as usize].contains(&step[1]));
+                }
+            }
+        }
+    }
+}
@@ -7,8 +7,21 @@
 //! - Largest Rectangle in Histogram
 //! - Next Greater Element
 //! - Monotonic Stack problems
+//! - Path Normalization (relative, Windows, strict mode)
+//! - Hardened Decode String and its inverse encoder
+//! - Persistent (immutable) Stack
+//! - Stack-based Virtual Machine (bytecode, assembler, interpreter)
+
+pub mod path_normalizer;
+pub mod persistent_stack;
+pub mod string_codec;
+pub mod vm;
 
 use crate::{measure_time, assert_result};
+use path_normalizer::{PathNormalizer, PathStyle};
+use persistent_stack::PersistentStack;
+use string_codec::{decode_iter, decode_with_limits, encode_string, DecodeLimits};
+use vm::{assemble, compile_rpn, Vm};
 
 /// Problem: Valid Parentheses
 /// Check if a string of parentheses is valid.
@@ -83,6 +96,12 @@ impl MinStack {
     }
 }
 
+impl Default for MinStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Problem: Evaluate Reverse Polish Notation
 /// Evaluate the value of an arithmetic expression in Reverse Polish Notation.
 pub fn eval_rpn(tokens: &[String]) -> i32 {
@@ -212,6 +231,7 @@ pub fn simplify_path(path: &str) -> String {
 
 /// Problem: Decode String
 /// Decode an encoded string.
+/// Assumes well-formed input; see `string_codec::decode_with_limits` for untrusted input.
 pub fn decode_string(s: &str) -> String {
     let mut stack = Vec::new();
     let mut current_string = String::new();
@@ -263,12 +283,36 @@ pub fn run_examples() {
     println!("Min Stack: push 3,5,2,1 -> min: {}", min_stack.get_min().unwrap());
     assert_result(*min_stack.get_min().unwrap(), 1, "Min Stack");
     
+    // Persistent Stack
+    let base = PersistentStack::new().push(1).push(2);
+    let snapshot = base.clone();
+    let (top, popped) = base.pop().unwrap();
+    let branch = popped.push(3);
+    println!("Persistent Stack: snapshot {:?}, popped {} -> {:?}, branch {:?}", snapshot, top, popped, branch);
+    assert_result(snapshot.iter().copied().collect::<Vec<_>>(), vec![2, 1], "Persistent Stack Snapshot");
+    assert_result(branch.iter().copied().collect::<Vec<_>>(), vec![3, 1], "Persistent Stack Branch");
+    
     // Evaluate RPN
     let tokens = vec!["2".to_string(), "1".to_string(), "+".to_string(), "3".to_string(), "*".to_string()];
     let result = measure_time("Evaluate RPN", || eval_rpn(&tokens));
     println!("Evaluate RPN: {:?} -> {}", tokens, result);
     assert_result(result, 9, "Evaluate RPN");
     
+    // Stack VM
+    let program = compile_rpn(&tokens).unwrap();
+    let mut vm = Vm::new(program).trace(true);
+    let result = measure_time("Stack VM (RPN)", || vm.run());
+    for entry in vm.trace_entries() {
+        println!("{}", entry);
+    }
+    println!("Stack VM (RPN): {:?} -> {:?}, stack {:?}", tokens, result, vm.stack());
+    assert_result(vm.stack().to_vec(), vec![9], "Stack VM (RPN)");
+    let source = "PUSH 0\nloop: JMP loop";
+    let mut vm = Vm::new(assemble(source).unwrap()).step_limit(1000);
+    let result = vm.run();
+    println!("Stack VM (infinite loop): {:?}", result);
+    assert_result(result.is_err(), true, "Stack VM Step Limit");
+    
     // Largest Rectangle in Histogram
     let heights = vec![2, 1, 5, 6, 2, 3];
     let result = measure_time("Largest Rectangle", || largest_rectangle_area(&heights));
@@ -293,11 +337,40 @@ pub fn run_examples() {
     println!("Simplify Path: '{}' -> '{}'", path, result);
     assert_result(result, "/home/foo".to_string(), "Simplify Path");
     
+    // Path Normalizer
+    let normalizer = PathNormalizer::new(PathStyle::Windows);
+    let path = "C:\\src\\..\\lib/./util";
+    let result = measure_time("Normalize Path", || normalizer.normalize(path));
+    println!("Normalize Path: '{}' -> {:?}", path, result);
+    assert_result(result, Ok("C:\\lib\\util".to_string()), "Normalize Path");
+    let result = PathNormalizer::new(PathStyle::Unix).relative_path("/a/b/c", "/a/d");
+    println!("Relative Path: '/a/b/c' -> '/a/d' = {:?}", result);
+    assert_result(result, Ok("../../d".to_string()), "Relative Path");
+    let result = PathNormalizer::new(PathStyle::Unix).strict(true).normalize("/a/../..");
+    println!("Strict Normalize: '/a/../..' -> {:?}", result);
+    assert_result(result.is_err(), true, "Strict Normalize");
+    
     // Decode String
     let s = "3[a]2[bc]";
     let result = measure_time("Decode String", || decode_string(s));
     println!("Decode String: '{}' -> '{}'", s, result);
     assert_result(result, "aaabcbc".to_string(), "Decode String");
+    
+    // Hardened Decode String
+    let limits = DecodeLimits::new(1000, 8);
+    let s = "999999[999999[a]]";
+    let result = measure_time("Decode With Limits", || decode_with_limits(s, &limits));
+    println!("Decode With Limits: '{}' -> {:?}", s, result);
+    assert_result(result.is_err(), true, "Decode With Limits");
+    let prefix: String = decode_iter(s, &limits).map(|it| it.take(5).collect()).unwrap_or_default();
+    println!("Decode Iter: '{}' -> first 5 chars '{}'", s, prefix);
+    assert_result(prefix, "aaaaa".to_string(), "Decode Iter");
+    
+    // Encode String
+    let s = "abbbabbbcabbbabbbc";
+    let result = measure_time("Encode String", || encode_string(s));
+    println!("Encode String: '{}' -> {:?}", s, result);
+    assert_result(result, Some("2[2[abbb]c]".to_string()), "Encode String");
 }
 
 #[cfg(test)]
@@ -0,0 +1,251 @@
+//! Path Normalizer
+//! 
+//! A stack-based path normalizer that goes beyond `simplify_path`:
+//! - Relative paths (leading `..` components are preserved)
+//! - Windows-style separators and drive prefixes (`C:\`)
+//! - Relative-path computation between two paths
+//! - Strict mode that reports paths escaping their root
+
+use std::fmt;
+
+/// Separator and prefix conventions used when parsing and rendering paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStyle {
+    /// `/` separators, absolute paths start with `/`.
+    Unix,
+    /// `\` or `/` separators, optional drive prefix such as `C:`.
+    Windows,
+}
+
+/// Errors reported by `PathNormalizer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// A `..` component climbed above the root (strict mode only).
+    EscapesRoot(String),
+    /// The two paths do not share a root, so no relative path exists.
+    MismatchedRoots(String, String),
+    /// The base path has leading `..` components that cannot be undone.
+    UnresolvableBase(String),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::EscapesRoot(path) => write!(f, "path '{}' escapes its root", path),
+            PathError::MismatchedRoots(from, to) => {
+                write!(f, "paths '{}' and '{}' do not share a root", from, to)
+            }
+            PathError::UnresolvableBase(path) => {
+                write!(f, "cannot compute a path relative to '{}'", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// A path split into its prefix, root marker and normalized components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParsedPath {
+    drive: Option<String>,
+    rooted: bool,
+    components: Vec<String>,
+}
+
+/// Normalizes paths with a stack of components.
+///
+/// `..` pops the previous component; on a relative path with nothing left to
+/// pop it is kept, and on an absolute path it is dropped (or rejected in strict mode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathNormalizer {
+    style: PathStyle,
+    strict: bool,
+}
+
+impl PathNormalizer {
+    pub fn new(style: PathStyle) -> Self {
+        PathNormalizer {
+            style,
+            strict: false,
+        }
+    }
+    
+    /// Report `..` above the root (or above the start of a relative path) as an error.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    
+    /// Normalize a path, resolving `.` and `..` and collapsing repeated separators.
+    pub fn normalize(&self, path: &str) -> Result<String, PathError> {
+        let parsed = self.parse(path)?;
+        Ok(self.render(&parsed))
+    }
+    
+    /// Compute the path that leads from directory `from` to `to`.
+    pub fn relative_path(&self, from: &str, to: &str) -> Result<String, PathError> {
+        let base = self.parse(from)?;
+        let target = self.parse(to)?;
+        
+        let same_drive = match (&base.drive, &target.drive) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            _ => false,
+        };
+        if !same_drive || base.rooted != target.rooted {
+            return Err(PathError::MismatchedRoots(from.to_string(), to.to_string()));
+        }
+        
+        let common = base
+            .components
+            .iter()
+            .zip(target.components.iter())
+            .take_while(|(a, b)| self.same_component(a, b))
+            .count();
+        
+        // Climbing out of a `..` would require knowing the name of the parent directory
+        if base.components[common..].iter().any(|c| c == "..") {
+            return Err(PathError::UnresolvableBase(from.to_string()));
+        }
+        
+        let mut components = vec!["..".to_string(); base.components.len() - common];
+        components.extend(target.components[common..].iter().cloned());
+        
+        if components.is_empty() {
+            Ok(".".to_string())
+        } else {
+            Ok(components.join(self.separator()))
+        }
+    }
+    
+    fn parse(&self, path: &str) -> Result<ParsedPath, PathError> {
+        let (drive, rest) = match self.style {
+            PathStyle::Windows => split_drive(path),
+            PathStyle::Unix => (None, path),
+        };
+        let rooted = rest.starts_with(|c| self.is_separator(c));
+        let mut stack: Vec<String> = Vec::new();
+        
+        for component in rest.split(|c| self.is_separator(c)) {
+            match component {
+                "" | "." => continue,
+                ".." => {
+                    if stack.last().is_some_and(|last| last != "..") {
+                        stack.pop();
+                    } else if self.strict {
+                        return Err(PathError::EscapesRoot(path.to_string()));
+                    } else if !rooted {
+                        stack.push("..".to_string());
+                    }
+                }
+                _ => stack.push(component.to_string()),
+            }
+        }
+        
+        Ok(ParsedPath {
+            drive,
+            rooted,
+            components: stack,
+        })
+    }
+    
+    fn render(&self, path: &ParsedPath) -> String {
+        let mut result = path.drive.clone().unwrap_or_default();
+        if path.rooted {
+            result.push_str(self.separator());
+        }
+        result.push_str(&path.components.join(self.separator()));
+        
+        if result.is_empty() {
+            ".".to_string()
+        } else {
+            result
+        }
+    }
+    
+    fn is_separator(&self, c: char) -> bool {
+        match self.style {
+            PathStyle::Unix => c == '/',
+            PathStyle::Windows => c == '/' || c == '\\',
+        }
+    }
+    
+    fn separator(&self) -> &'static str {
+        match self.style {
+            PathStyle::Unix => "/",
+            PathStyle::Windows => "\\",
+        }
+    }
+    
+    fn same_component(&self, a: &str, b: &str) -> bool {
+        match self.style {
+            PathStyle::Unix => a == b,
+            PathStyle::Windows => a.eq_ignore_ascii_case(b),
+        }
+    }
+}
+
+/// Split a leading drive prefix such as `c:` off a Windows path, upper-casing the letter.
+fn split_drive(path: &str) -> (Option<String>, &str) {
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        let drive = format!("{}:", (bytes[0] as char).to_ascii_uppercase());
+        (Some(drive), &path[2..])
+    } else {
+        (None, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_unix_absolute() {
+        let normalizer = PathNormalizer::new(PathStyle::Unix);
+        assert_eq!(normalizer.normalize("/home//foo/").unwrap(), "/home/foo");
+        assert_eq!(normalizer.normalize("/a/./b/../../c/").unwrap(), "/c");
+        assert_eq!(normalizer.normalize("/../").unwrap(), "/");
+    }
+    
+    #[test]
+    fn test_unix_relative() {
+        let normalizer = PathNormalizer::new(PathStyle::Unix);
+        assert_eq!(normalizer.normalize("../../a/b/../c").unwrap(), "../../a/c");
+        assert_eq!(normalizer.normalize("a/..").unwrap(), ".");
+        assert_eq!(normalizer.normalize("a/../../b").unwrap(), "../b");
+        assert_eq!(normalizer.normalize("").unwrap(), ".");
+    }
+    
+    #[test]
+    fn test_windows_paths() {
+        let normalizer = PathNormalizer::new(PathStyle::Windows);
+        assert_eq!(normalizer.normalize("c:\\Users\\..\\Windows/System32").unwrap(), "C:\\Windows\\System32");
+        assert_eq!(normalizer.normalize("C:foo\\..\\..\\bar").unwrap(), "C:..\\bar");
+        assert_eq!(normalizer.normalize("\\a\\.\\b").unwrap(), "\\a\\b");
+        assert_eq!(normalizer.normalize("D:\\..").unwrap(), "D:\\");
+    }
+    
+    #[test]
+    fn test_strict_mode() {
+        let normalizer = PathNormalizer::new(PathStyle::Unix).strict(true);
+        assert_eq!(normalizer.normalize("/a/../b").unwrap(), "/b");
+        assert_eq!(normalizer.normalize("/a/../.."), Err(PathError::EscapesRoot("/a/../..".to_string())));
+        assert!(normalizer.normalize("../a").is_err());
+    }
+    
+    #[test]
+    fn test_relative_path() {
+        let unix = PathNormalizer::new(PathStyle::Unix);
+        assert_eq!(unix.relative_path("/a/b/c", "/a/d").unwrap(), "../../d");
+        assert_eq!(unix.relative_path("/a", "/a/b/c").unwrap(), "b/c");
+        assert_eq!(unix.relative_path("/a/b", "/a/b/").unwrap(), ".");
+        assert_eq!(unix.relative_path("../x", "../y/z").unwrap(), "../y/z");
+        assert!(matches!(unix.relative_path("/a", "b"), Err(PathError::MismatchedRoots(_, _))));
+        assert!(matches!(unix.relative_path("../a", "b"), Err(PathError::UnresolvableBase(_))));
+        
+        let windows = PathNormalizer::new(PathStyle::Windows);
+        assert_eq!(windows.relative_path("C:\\Src\\App", "c:\\src\\lib\\x.rs").unwrap(), "..\\lib\\x.rs");
+        assert!(windows.relative_path("C:\\a", "D:\\a").is_err());
+    }
+}
@@ -0,0 +1,169 @@
+//! Persistent Stack
+//! 
+//! An immutable stack built as an `Rc`-linked cons list. `push` and `pop`
+//! return new versions that share every existing node with the old one, so
+//! taking a snapshot is just cloning a pointer.
+
+use std::rc::Rc;
+
+struct Node<T> {
+    value: T,
+    next: Option<Rc<Node<T>>>,
+}
+
+/// Immutable stack with O(1) push, pop and snapshot (clone).
+pub struct PersistentStack<T> {
+    head: Option<Rc<Node<T>>>,
+    len: usize,
+}
+
+impl<T> PersistentStack<T> {
+    pub fn new() -> Self {
+        PersistentStack { head: None, len: 0 }
+    }
+    
+    /// Return a new version with `value` on top; `self` is left untouched.
+    pub fn push(&self, value: T) -> Self {
+        PersistentStack {
+            head: Some(Rc::new(Node {
+                value,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+    
+    /// Return the top element and the version without it, or `None` when empty.
+    pub fn pop(&self) -> Option<(&T, Self)> {
+        self.head.as_ref().map(|node| {
+            let rest = PersistentStack {
+                head: node.next.clone(),
+                len: self.len - 1,
+            };
+            (&node.value, rest)
+        })
+    }
+    
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+    
+    /// Iterate from top to bottom.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+impl<T> Default for PersistentStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Cloning only bumps the reference count of the top node, so no `T: Clone` bound is needed
+impl<T> Clone for PersistentStack<T> {
+    fn clone(&self) -> Self {
+        PersistentStack {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+// Unlink uniquely owned nodes one at a time so long stacks don't overflow the call stack
+impl<T> Drop for PersistentStack<T> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => current = node.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for PersistentStack<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Borrowing iterator over a `PersistentStack`, top first.
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.value
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_push_pop() {
+        let empty = PersistentStack::new();
+        let one = empty.push(1);
+        let two = one.push(2);
+        
+        assert_eq!(two.peek(), Some(&2));
+        assert_eq!(two.len(), 2);
+        let (top, rest) = two.pop().unwrap();
+        assert_eq!(*top, 2);
+        assert_eq!(rest.iter().collect::<Vec<_>>(), vec![&1]);
+        assert!(empty.pop().is_none());
+    }
+    
+    #[test]
+    fn test_old_versions_remain_valid() {
+        let base = PersistentStack::new().push(1).push(2);
+        let left = base.push(3);
+        let right = base.pop().unwrap().1.push(4);
+        
+        assert_eq!(base.iter().copied().collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(left.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(right.iter().copied().collect::<Vec<_>>(), vec![4, 1]);
+        drop(base);
+        assert_eq!(left.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+    
+    #[test]
+    fn test_snapshot_shares_structure() {
+        let mut stack = PersistentStack::new();
+        for i in 0..100_000 {
+            stack = stack.push(i);
+        }
+        
+        // A snapshot is one pointer copy, no matter how large the stack is
+        let snapshot = stack.clone();
+        let head = stack.head.as_ref().unwrap();
+        assert!(Rc::ptr_eq(head, snapshot.head.as_ref().unwrap()));
+        assert_eq!(Rc::strong_count(head), 2);
+        
+        // New versions reuse the old nodes as their tail
+        let pushed = snapshot.push(-1);
+        assert!(Rc::ptr_eq(pushed.head.as_ref().unwrap().next.as_ref().unwrap(), head));
+        let (_, popped) = snapshot.pop().unwrap();
+        assert!(Rc::ptr_eq(popped.head.as_ref().unwrap(), head.next.as_ref().unwrap()));
+        assert_eq!(popped.len(), 99_999);
+    }
+}
@@ -0,0 +1,367 @@
+//! Hardened Decode String
+//! 
+//! A safer take on `decode_string` for untrusted input:
+//! - Parsing with an explicit stack, reporting unmatched brackets and overflowing counts
+//! - Configurable limits on output size and nesting depth
+//! - A lazy iterator yielding decoded characters without building the whole string
+//! - The inverse encoder producing a shortest `k[...]` encoding
+
+use std::fmt;
+use std::rc::Rc;
+
+/// Limits enforced while decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    pub max_output: usize,
+    pub max_depth: usize,
+}
+
+impl DecodeLimits {
+    pub fn new(max_output: usize, max_depth: usize) -> Self {
+        DecodeLimits { max_output, max_depth }
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits {
+            max_output: 1 << 20,
+            max_depth: 64,
+        }
+    }
+}
+
+/// Errors reported while decoding. Structural errors carry the character
+/// offset of the offending input; limit errors carry the limit that was hit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A `[` that is never closed; the field is the offset of the `[`.
+    UnmatchedOpen(usize),
+    /// A `]` without a matching `[`; the field is the offset of the `]`.
+    UnmatchedClose(usize),
+    /// A `[` that is not preceded by a repeat count; the field is the offset of the `[`.
+    MissingCount(usize),
+    /// A repeat count that is not followed by `[`; the field is the offset of the count.
+    DanglingCount(usize),
+    /// A repeat count too large to represent; the field is the offset of the count.
+    CountOverflow(usize),
+    /// Brackets nested deeper than allowed; the field is `DecodeLimits::max_depth`.
+    DepthExceeded(usize),
+    /// The decoded output would be too long; the field is `DecodeLimits::max_output`.
+    OutputTooLarge(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnmatchedOpen(pos) => write!(f, "unmatched '[' at position {}", pos),
+            DecodeError::UnmatchedClose(pos) => write!(f, "unmatched ']' at position {}", pos),
+            DecodeError::MissingCount(pos) => write!(f, "missing repeat count before '[' at position {}", pos),
+            DecodeError::DanglingCount(pos) => write!(f, "repeat count at position {} is not followed by '['", pos),
+            DecodeError::CountOverflow(pos) => write!(f, "repeat count at position {} is too large", pos),
+            DecodeError::DepthExceeded(limit) => write!(f, "nesting deeper than {} levels", limit),
+            DecodeError::OutputTooLarge(limit) => write!(f, "decoded output exceeds {} characters", limit),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// One piece of a parsed encoding: literal text or a repeated group.
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(Rc<str>),
+    Repeat(usize, Rc<Vec<Segment>>),
+}
+
+/// Parse an encoded string into segments, validating its structure.
+///
+/// Empty literals and groups that decode to nothing are dropped, so every
+/// remaining segment produces at least one character.
+fn parse(s: &str, max_depth: usize) -> Result<Vec<Segment>, DecodeError> {
+    // Each frame holds the enclosing segments, the repeat count and the position of '['
+    let mut stack: Vec<(Vec<Segment>, usize, usize)> = Vec::new();
+    let mut current: Vec<Segment> = Vec::new();
+    let mut literal = String::new();
+    let mut count: Option<(usize, usize)> = None;
+    
+    fn flush(literal: &mut String, current: &mut Vec<Segment>) {
+        if !literal.is_empty() {
+            current.push(Segment::Literal(Rc::from(literal.as_str())));
+            literal.clear();
+        }
+    }
+    
+    for (pos, ch) in s.chars().enumerate() {
+        match ch {
+            '0'..='9' => {
+                let digit = (ch as u8 - b'0') as usize;
+                let (value, start) = count.unwrap_or((0, pos));
+                let value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit))
+                    .ok_or(DecodeError::CountOverflow(start))?;
+                count = Some((value, start));
+            }
+            '[' => {
+                let (repeat, _) = count.take().ok_or(DecodeError::MissingCount(pos))?;
+                if stack.len() >= max_depth {
+                    return Err(DecodeError::DepthExceeded(max_depth));
+                }
+                flush(&mut literal, &mut current);
+                stack.push((std::mem::take(&mut current), repeat, pos));
+            }
+            ']' => {
+                if let Some((_, start)) = count {
+                    return Err(DecodeError::DanglingCount(start));
+                }
+                flush(&mut literal, &mut current);
+                let (parent, repeat, _) = stack.pop().ok_or(DecodeError::UnmatchedClose(pos))?;
+                let body = std::mem::replace(&mut current, parent);
+                if repeat > 0 && !body.is_empty() {
+                    current.push(Segment::Repeat(repeat, Rc::new(body)));
+                }
+            }
+            _ => {
+                if let Some((_, start)) = count {
+                    return Err(DecodeError::DanglingCount(start));
+                }
+                literal.push(ch);
+            }
+        }
+    }
+    
+    if let Some((_, start)) = count {
+        return Err(DecodeError::DanglingCount(start));
+    }
+    if let Some(&(_, _, pos)) = stack.last() {
+        return Err(DecodeError::UnmatchedOpen(pos));
+    }
+    flush(&mut literal, &mut current);
+    
+    Ok(current)
+}
+
+/// Number of characters the segments decode to, or `None` on overflow.
+fn decoded_len(segments: &[Segment]) -> Option<usize> {
+    segments.iter().try_fold(0usize, |total, segment| {
+        let len = match segment {
+            Segment::Literal(text) => text.chars().count(),
+            Segment::Repeat(repeat, body) => decoded_len(body)?.checked_mul(*repeat)?,
+        };
+        total.checked_add(len)
+    })
+}
+
+fn write_segments(segments: &[Segment], out: &mut String) {
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => out.push_str(text),
+            Segment::Repeat(repeat, body) => {
+                for _ in 0..*repeat {
+                    write_segments(body, out);
+                }
+            }
+        }
+    }
+}
+
+/// Problem: Decode String (hardened)
+/// Decode `k[encoded]` strings, rejecting malformed input and outputs over the limits.
+/// The output size is computed before anything is materialized.
+pub fn decode_with_limits(s: &str, limits: &DecodeLimits) -> Result<String, DecodeError> {
+    let segments = parse(s, limits.max_depth)?;
+    let len = decoded_len(&segments)
+        .filter(|&len| len <= limits.max_output)
+        .ok_or(DecodeError::OutputTooLarge(limits.max_output))?;
+    
+    let mut result = String::with_capacity(len);
+    write_segments(&segments, &mut result);
+    Ok(result)
+}
+
+/// Decode lazily; only the nesting depth limit applies since nothing is materialized.
+pub fn decode_iter(s: &str, limits: &DecodeLimits) -> Result<DecodeIter, DecodeError> {
+    let segments = parse(s, limits.max_depth)?;
+    Ok(DecodeIter {
+        frames: vec![Frame {
+            body: Rc::new(segments),
+            index: 0,
+            remaining: 1,
+        }],
+        literal: None,
+    })
+}
+
+/// A repeated group being walked by `DecodeIter`.
+struct Frame {
+    body: Rc<Vec<Segment>>,
+    index: usize,
+    remaining: usize,
+}
+
+/// Iterator over decoded characters, driven by an explicit stack of groups.
+pub struct DecodeIter {
+    frames: Vec<Frame>,
+    literal: Option<(Rc<str>, usize)>,
+}
+
+impl Iterator for DecodeIter {
+    type Item = char;
+    
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if let Some((text, offset)) = &mut self.literal {
+                if let Some(ch) = text[*offset..].chars().next() {
+                    *offset += ch.len_utf8();
+                    return Some(ch);
+                }
+                self.literal = None;
+            }
+            
+            let frame = self.frames.last_mut()?;
+            if frame.index < frame.body.len() {
+                let segment = frame.body[frame.index].clone();
+                frame.index += 1;
+                match segment {
+                    Segment::Literal(text) => self.literal = Some((text, 0)),
+                    Segment::Repeat(repeat, body) => self.frames.push(Frame {
+                        body,
+                        index: 0,
+                        remaining: repeat,
+                    }),
+                }
+            } else {
+                frame.remaining -= 1;
+                if frame.remaining > 0 {
+                    frame.index = 0;
+                } else {
+                    self.frames.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Problem: Encode String with Shortest Length
+/// Produce a shortest `k[...]` encoding whose decoding is `s`.
+/// Returns `None` when `s` contains digits or brackets, which cannot be encoded unambiguously.
+pub fn encode_string(s: &str) -> Option<String> {
+    if s.chars().any(|c| c.is_ascii_digit() || c == '[' || c == ']') {
+        return None;
+    }
+    
+    let chars: Vec<char> = s.chars().collect();
+    let n = chars.len();
+    // dp[i][j] holds a shortest encoding of chars[i..=j] and its length in characters
+    let mut dp = vec![vec![(String::new(), 0usize); n]; n];
+    
+    for len in 1..=n {
+        for i in 0..=n - len {
+            let j = i + len - 1;
+            let mut best: (String, usize) = (chars[i..=j].iter().collect(), len);
+            
+            // "k[x]" costs at least four characters, so shorter substrings stay literal
+            if len >= 5 {
+                for k in i..j {
+                    let cost = dp[i][k].1 + dp[k + 1][j].1;
+                    if cost < best.1 {
+                        best = (format!("{}{}", dp[i][k].0, dp[k + 1][j].0), cost);
+                    }
+                }
+                
+                if let Some(period) = smallest_period(&chars[i..=j]) {
+                    let (pattern, pattern_len) = &dp[i][i + period - 1];
+                    let encoded = format!("{}[{}]", len / period, pattern);
+                    let cost = (len / period).to_string().len() + 2 + pattern_len;
+                    if cost < best.1 {
+                        best = (encoded, cost);
+                    }
+                }
+            }
+            
+            dp[i][j] = best;
+        }
+    }
+    
+    Some(dp.first().and_then(|row| row.last()).map(|(s, _)| s.clone()).unwrap_or_default())
+}
+
+/// Length of the shortest block that tiles `chars` at least twice.
+fn smallest_period(chars: &[char]) -> Option<usize> {
+    let n = chars.len();
+    (1..=n / 2).find(|&p| n.is_multiple_of(p) && (p..n).all(|i| chars[i] == chars[i - p]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stacks::decode_string;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_decode_with_limits() {
+        let limits = DecodeLimits::default();
+        assert_eq!(decode_with_limits("3[a]2[bc]", &limits).unwrap(), "aaabcbc");
+        assert_eq!(decode_with_limits("3[a2[c]]", &limits).unwrap(), "accaccacc");
+        assert_eq!(decode_with_limits("ab0[cd]ef", &limits).unwrap(), "abef");
+        assert_eq!(decode_with_limits("", &limits).unwrap(), "");
+    }
+    
+    #[test]
+    fn test_decode_errors() {
+        let limits = DecodeLimits::default();
+        assert_eq!(decode_with_limits("a]", &limits), Err(DecodeError::UnmatchedClose(1)));
+        assert_eq!(decode_with_limits("2[a", &limits), Err(DecodeError::UnmatchedOpen(1)));
+        assert_eq!(decode_with_limits("[a]", &limits), Err(DecodeError::MissingCount(0)));
+        assert_eq!(decode_with_limits("a3b", &limits), Err(DecodeError::DanglingCount(1)));
+        assert_eq!(
+            decode_with_limits("99999999999999999999999[a]", &limits),
+            Err(DecodeError::CountOverflow(0))
+        );
+    }
+    
+    #[test]
+    fn test_decode_limits_enforced() {
+        let limits = DecodeLimits::new(100, 2);
+        assert_eq!(decode_with_limits("999999[999999[a]]", &limits), Err(DecodeError::OutputTooLarge(100)));
+        assert_eq!(decode_with_limits("10[10[a]]", &limits).unwrap().len(), 100);
+        assert_eq!(decode_with_limits("1[1[1[a]]]", &limits), Err(DecodeError::DepthExceeded(2)));
+    }
+    
+    #[test]
+    fn test_decode_iter() {
+        let limits = DecodeLimits::default();
+        let decoded: String = decode_iter("2[ab3[c]]é", &limits).unwrap().collect();
+        assert_eq!(decoded, "abcccabccc\u{e9}");
+        
+        // The full output would be ~10^12 characters, but only a prefix is produced
+        let prefix: String = decode_iter("999999[999999[ab]]", &limits).unwrap().take(5).collect();
+        assert_eq!(prefix, "ababa");
+        
+        assert_eq!(decode_iter("5[0[a]]b", &limits).unwrap().collect::<String>(), "b");
+        assert!(decode_iter("2[a", &limits).is_err());
+    }
+    
+    #[test]
+    fn test_encode_string() {
+        assert_eq!(encode_string("aaa").unwrap(), "aaa");
+        assert_eq!(encode_string("aaaaa").unwrap(), "5[a]");
+        // "10[a]" and "a9[a]" are both shortest
+        assert_eq!(encode_string("aaaaaaaaaa").unwrap().len(), 5);
+        assert_eq!(encode_string("aabcaabcd").unwrap(), "2[aabc]d");
+        assert_eq!(encode_string("abbbabbbcabbbabbbc").unwrap(), "2[2[abbb]c]");
+        assert_eq!(encode_string("").unwrap(), "");
+        assert_eq!(encode_string("a1b"), None);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_encode_round_trips(s in "[a-c]{0,24}") {
+            let encoded = encode_string(&s).unwrap();
+            prop_assert!(encoded.len() <= s.len());
+            prop_assert_eq!(decode_with_limits(&encoded, &DecodeLimits::default()).unwrap(), s.clone());
+            prop_assert_eq!(decode_string(&encoded), s);
+        }
+    }
+}
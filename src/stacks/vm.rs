@@ -0,0 +1,615 @@
+//! Stack Virtual Machine
+//! 
+//! A small deterministic stack machine showing how interpreters use stacks:
+//! - A data stack for operands and a call stack for return addresses
+//! - A bytecode `Instruction` set with arithmetic, stack shuffling, jumps and calls
+//! - An assembler from a line-based text format with labels and comments
+//! - An interpreter with a step limit and an optional execution trace
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// A single bytecode instruction. Jump and call targets are instruction indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Push(i64),
+    Pop,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Neg,
+    Dup,
+    Swap,
+    /// Copy the second element to the top: `a b -> a b a`.
+    Over,
+    /// Rotate the top three elements: `a b c -> b c a`.
+    Rot,
+    Eq,
+    Lt,
+    Gt,
+    Jump(usize),
+    /// Pop the top element and jump if it is zero.
+    JumpIfZero(usize),
+    /// Pop the top element and jump if it is not zero.
+    JumpIfNotZero(usize),
+    Call(usize),
+    Ret,
+    /// Pop the top element and append it to the output.
+    Print,
+    Halt,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Push(value) => write!(f, "PUSH {}", value),
+            Instruction::Pop => write!(f, "POP"),
+            Instruction::Add => write!(f, "ADD"),
+            Instruction::Sub => write!(f, "SUB"),
+            Instruction::Mul => write!(f, "MUL"),
+            Instruction::Div => write!(f, "DIV"),
+            Instruction::Mod => write!(f, "MOD"),
+            Instruction::Neg => write!(f, "NEG"),
+            Instruction::Dup => write!(f, "DUP"),
+            Instruction::Swap => write!(f, "SWAP"),
+            Instruction::Over => write!(f, "OVER"),
+            Instruction::Rot => write!(f, "ROT"),
+            Instruction::Eq => write!(f, "EQ"),
+            Instruction::Lt => write!(f, "LT"),
+            Instruction::Gt => write!(f, "GT"),
+            Instruction::Jump(target) => write!(f, "JMP {}", target),
+            Instruction::JumpIfZero(target) => write!(f, "JZ {}", target),
+            Instruction::JumpIfNotZero(target) => write!(f, "JNZ {}", target),
+            Instruction::Call(target) => write!(f, "CALL {}", target),
+            Instruction::Ret => write!(f, "RET"),
+            Instruction::Print => write!(f, "PRINT"),
+            Instruction::Halt => write!(f, "HALT"),
+        }
+    }
+}
+
+/// Errors reported by the assembler. Lines are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssembleError {
+    UnknownInstruction { line: usize, mnemonic: String },
+    MissingOperand { line: usize },
+    UnexpectedOperand { line: usize },
+    InvalidOperand { line: usize, operand: String },
+    UnknownLabel { line: usize, label: String },
+    DuplicateLabel { line: usize, label: String },
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssembleError::UnknownInstruction { line, mnemonic } => {
+                write!(f, "line {}: unknown instruction '{}'", line, mnemonic)
+            }
+            AssembleError::MissingOperand { line } => write!(f, "line {}: missing operand", line),
+            AssembleError::UnexpectedOperand { line } => write!(f, "line {}: unexpected operand", line),
+            AssembleError::InvalidOperand { line, operand } => {
+                write!(f, "line {}: invalid operand '{}'", line, operand)
+            }
+            AssembleError::UnknownLabel { line, label } => write!(f, "line {}: unknown label '{}'", line, label),
+            AssembleError::DuplicateLabel { line, label } => {
+                write!(f, "line {}: label '{}' defined twice", line, label)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AssembleError {}
+
+/// Runtime errors. `pc` is the index of the instruction that failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmError {
+    StackUnderflow { pc: usize },
+    DivisionByZero { pc: usize },
+    ArithmeticOverflow { pc: usize },
+    InvalidTarget { pc: usize, target: usize },
+    CallStackOverflow { pc: usize },
+    ReturnWithoutCall { pc: usize },
+    StepLimitExceeded { limit: usize },
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::StackUnderflow { pc } => write!(f, "stack underflow at {}", pc),
+            VmError::DivisionByZero { pc } => write!(f, "division by zero at {}", pc),
+            VmError::ArithmeticOverflow { pc } => write!(f, "arithmetic overflow at {}", pc),
+            VmError::InvalidTarget { pc, target } => write!(f, "jump to invalid target {} at {}", target, pc),
+            VmError::CallStackOverflow { pc } => write!(f, "call stack overflow at {}", pc),
+            VmError::ReturnWithoutCall { pc } => write!(f, "return without call at {}", pc),
+            VmError::StepLimitExceeded { limit } => write!(f, "step limit of {} exceeded", limit),
+        }
+    }
+}
+
+impl std::error::Error for VmError {}
+
+/// Assemble a program from text.
+///
+/// One instruction per line; `;` starts a comment and `name:` defines a label
+/// that jump and call instructions may use instead of a numeric target.
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, AssembleError> {
+    // First pass: strip comments, record label positions
+    let mut labels = HashMap::new();
+    let mut lines = Vec::new();
+    
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        let mut text = raw.split(';').next().unwrap_or("").trim();
+        
+        while let Some(colon) = text.find(':') {
+            let label = text[..colon].trim();
+            if label.is_empty() || label.contains(char::is_whitespace) {
+                break;
+            }
+            if labels.insert(label.to_string(), lines.len()).is_some() {
+                return Err(AssembleError::DuplicateLabel {
+                    line,
+                    label: label.to_string(),
+                });
+            }
+            text = text[colon + 1..].trim();
+        }
+        
+        if !text.is_empty() {
+            lines.push((line, text));
+        }
+    }
+    
+    // Second pass: translate mnemonics now that every label is known
+    lines
+        .into_iter()
+        .map(|(line, text)| {
+            let mut parts = text.split_whitespace();
+            let mnemonic = parts.next().unwrap_or_default();
+            let operand = parts.next();
+            if parts.next().is_some() {
+                return Err(AssembleError::UnexpectedOperand { line });
+            }
+            
+            let target = || -> Result<usize, AssembleError> {
+                let operand = operand.ok_or(AssembleError::MissingOperand { line })?;
+                if let Ok(target) = operand.parse() {
+                    return Ok(target);
+                }
+                labels.get(operand).copied().ok_or_else(|| AssembleError::UnknownLabel {
+                    line,
+                    label: operand.to_string(),
+                })
+            };
+            
+            let instruction = match mnemonic.to_ascii_uppercase().as_str() {
+                "PUSH" => {
+                    let operand = operand.ok_or(AssembleError::MissingOperand { line })?;
+                    let value = operand.parse().map_err(|_| AssembleError::InvalidOperand {
+                        line,
+                        operand: operand.to_string(),
+                    })?;
+                    return Ok(Instruction::Push(value));
+                }
+                "JMP" => return Ok(Instruction::Jump(target()?)),
+                "JZ" => return Ok(Instruction::JumpIfZero(target()?)),
+                "JNZ" => return Ok(Instruction::JumpIfNotZero(target()?)),
+                "CALL" => return Ok(Instruction::Call(target()?)),
+                "POP" => Instruction::Pop,
+                "ADD" => Instruction::Add,
+                "SUB" => Instruction::Sub,
+                "MUL" => Instruction::Mul,
+                "DIV" => Instruction::Div,
+                "MOD" => Instruction::Mod,
+                "NEG" => Instruction::Neg,
+                "DUP" => Instruction::Dup,
+                "SWAP" => Instruction::Swap,
+                "OVER" => Instruction::Over,
+                "ROT" => Instruction::Rot,
+                "EQ" => Instruction::Eq,
+                "LT" => Instruction::Lt,
+                "GT" => Instruction::Gt,
+                "RET" => Instruction::Ret,
+                "PRINT" => Instruction::Print,
+                "HALT" => Instruction::Halt,
+                _ => {
+                    return Err(AssembleError::UnknownInstruction {
+                        line,
+                        mnemonic: mnemonic.to_string(),
+                    })
+                }
+            };
+            
+            if operand.is_some() {
+                return Err(AssembleError::UnexpectedOperand { line });
+            }
+            Ok(instruction)
+        })
+        .collect()
+}
+
+/// Translate Reverse Polish Notation tokens (as accepted by `eval_rpn`) into bytecode.
+pub fn compile_rpn(tokens: &[String]) -> Result<Vec<Instruction>, AssembleError> {
+    let mut program: Vec<Instruction> = tokens
+        .iter()
+        .enumerate()
+        .map(|(index, token)| match token.as_str() {
+            "+" => Ok(Instruction::Add),
+            "-" => Ok(Instruction::Sub),
+            "*" => Ok(Instruction::Mul),
+            "/" => Ok(Instruction::Div),
+            _ => token.parse().map(Instruction::Push).map_err(|_| AssembleError::InvalidOperand {
+                line: index + 1,
+                operand: token.clone(),
+            }),
+        })
+        .collect::<Result<_, _>>()?;
+    program.push(Instruction::Halt);
+    Ok(program)
+}
+
+/// One executed instruction, recorded when tracing is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub step: usize,
+    pub pc: usize,
+    pub instruction: Instruction,
+    /// Data stack after the instruction ran.
+    pub stack: Vec<i64>,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5} | {:04} {:<10} {:?}", self.step, self.pc, self.instruction.to_string(), self.stack)
+    }
+}
+
+/// Interpreter for a bytecode program.
+pub struct Vm {
+    program: Vec<Instruction>,
+    pc: usize,
+    stack: Vec<i64>,
+    call_stack: Vec<usize>,
+    output: Vec<i64>,
+    steps: usize,
+    halted: bool,
+    step_limit: usize,
+    max_call_depth: usize,
+    trace: Option<Vec<TraceEntry>>,
+}
+
+impl Vm {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Vm {
+            program,
+            pc: 0,
+            stack: Vec::new(),
+            call_stack: Vec::new(),
+            output: Vec::new(),
+            steps: 0,
+            halted: false,
+            step_limit: 1_000_000,
+            max_call_depth: 1024,
+            trace: None,
+        }
+    }
+    
+    /// Maximum number of instructions `run` executes before giving up.
+    pub fn step_limit(mut self, limit: usize) -> Self {
+        self.step_limit = limit;
+        self
+    }
+    
+    pub fn max_call_depth(mut self, depth: usize) -> Self {
+        self.max_call_depth = depth;
+        self
+    }
+    
+    /// Record a `TraceEntry` for every executed instruction.
+    pub fn trace(mut self, enabled: bool) -> Self {
+        self.trace = if enabled { Some(Vec::new()) } else { None };
+        self
+    }
+    
+    pub fn stack(&self) -> &[i64] {
+        &self.stack
+    }
+    
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+    
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+    
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+    
+    pub fn trace_entries(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+    
+    /// Run until the program halts, falls off the end, fails or hits the step limit.
+    pub fn run(&mut self) -> Result<(), VmError> {
+        while !self.halted {
+            if self.steps >= self.step_limit {
+                return Err(VmError::StepLimitExceeded { limit: self.step_limit });
+            }
+            self.step()?;
+        }
+        Ok(())
+    }
+    
+    /// Execute a single instruction.
+    pub fn step(&mut self) -> Result<(), VmError> {
+        if self.halted {
+            return Ok(());
+        }
+        let pc = self.pc;
+        let Some(&instruction) = self.program.get(pc) else {
+            self.halted = true;
+            return Ok(());
+        };
+        self.pc += 1;
+        
+        match instruction {
+            Instruction::Push(value) => self.stack.push(value),
+            Instruction::Pop => {
+                self.pop(pc)?;
+            }
+            Instruction::Add => self.binary(pc, |a, b| a.checked_add(b).ok_or(VmError::ArithmeticOverflow { pc }))?,
+            Instruction::Sub => self.binary(pc, |a, b| a.checked_sub(b).ok_or(VmError::ArithmeticOverflow { pc }))?,
+            Instruction::Mul => self.binary(pc, |a, b| a.checked_mul(b).ok_or(VmError::ArithmeticOverflow { pc }))?,
+            Instruction::Div => self.binary(pc, |a, b| {
+                if b == 0 {
+                    return Err(VmError::DivisionByZero { pc });
+                }
+                a.checked_div(b).ok_or(VmError::ArithmeticOverflow { pc })
+            })?,
+            Instruction::Mod => self.binary(pc, |a, b| {
+                if b == 0 {
+                    return Err(VmError::DivisionByZero { pc });
+                }
+                a.checked_rem(b).ok_or(VmError::ArithmeticOverflow { pc })
+            })?,
+            Instruction::Neg => {
+                let a = self.pop(pc)?;
+                self.stack.push(a.checked_neg().ok_or(VmError::ArithmeticOverflow { pc })?);
+            }
+            Instruction::Eq => self.binary(pc, |a, b| Ok((a == b) as i64))?,
+            Instruction::Lt => self.binary(pc, |a, b| Ok((a < b) as i64))?,
+            Instruction::Gt => self.binary(pc, |a, b| Ok((a > b) as i64))?,
+            Instruction::Dup => {
+                let top = *self.stack.last().ok_or(VmError::StackUnderflow { pc })?;
+                self.stack.push(top);
+            }
+            Instruction::Swap => {
+                let len = self.require(pc, 2)?;
+                self.stack.swap(len - 1, len - 2);
+            }
+            Instruction::Over => {
+                let len = self.require(pc, 2)?;
+                self.stack.push(self.stack[len - 2]);
+            }
+            Instruction::Rot => {
+                let len = self.require(pc, 3)?;
+                self.stack[len - 3..].rotate_left(1);
+            }
+            Instruction::Jump(target) => self.jump(pc, target)?,
+            Instruction::JumpIfZero(target) => {
+                if self.pop(pc)? == 0 {
+                    self.jump(pc, target)?;
+                }
+            }
+            Instruction::JumpIfNotZero(target) => {
+                if self.pop(pc)? != 0 {
+                    self.jump(pc, target)?;
+                }
+            }
+            Instruction::Call(target) => {
+                if self.call_stack.len() >= self.max_call_depth {
+                    return Err(VmError::CallStackOverflow { pc });
+                }
+                self.call_stack.push(self.pc);
+                self.jump(pc, target)?;
+            }
+            Instruction::Ret => {
+                self.pc = self.call_stack.pop().ok_or(VmError::ReturnWithoutCall { pc })?;
+            }
+            Instruction::Print => {
+                let value = self.pop(pc)?;
+                self.output.push(value);
+            }
+            Instruction::Halt => self.halted = true,
+        }
+        
+        self.steps += 1;
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                step: self.steps,
+                pc,
+                instruction,
+                stack: self.stack.clone(),
+            });
+        }
+        Ok(())
+    }
+    
+    fn pop(&mut self, pc: usize) -> Result<i64, VmError> {
+        self.stack.pop().ok_or(VmError::StackUnderflow { pc })
+    }
+    
+    /// Check that at least `n` elements are on the stack and return the stack length.
+    fn require(&self, pc: usize, n: usize) -> Result<usize, VmError> {
+        if self.stack.len() < n {
+            Err(VmError::StackUnderflow { pc })
+        } else {
+            Ok(self.stack.len())
+        }
+    }
+    
+    fn binary<F>(&mut self, pc: usize, op: F) -> Result<(), VmError>
+    where
+        F: FnOnce(i64, i64) -> Result<i64, VmError>,
+    {
+        self.require(pc, 2)?;
+        let b = self.stack.pop().unwrap();
+        let a = self.stack.pop().unwrap();
+        self.stack.push(op(a, b)?);
+        Ok(())
+    }
+    
+    fn jump(&mut self, pc: usize, target: usize) -> Result<(), VmError> {
+        // Jumping to one past the last instruction is allowed and ends the program
+        if target > self.program.len() {
+            return Err(VmError::InvalidTarget { pc, target });
+        }
+        self.pc = target;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stacks::eval_rpn;
+    
+    const FACTORIAL: &str = "
+        PUSH 1          ; accumulator
+        PUSH 5          ; n
+    loop:
+        DUP
+        JZ done
+        OVER
+        OVER
+        MUL             ; acc n acc*n
+        SWAP
+        PUSH 1
+        SUB             ; acc acc*n n-1
+        ROT
+        POP             ; acc*n n-1
+        JMP loop
+    done:
+        POP
+        PRINT
+        HALT
+    ";
+    
+    const FIBONACCI: &str = "
+        PUSH 10
+        CALL fib
+        PRINT
+        HALT
+    fib:                ; n -> fib(n)
+        DUP
+        PUSH 2
+        LT
+        JNZ base
+        DUP
+        PUSH 1
+        SUB
+        CALL fib        ; n fib(n-1)
+        SWAP
+        PUSH 2
+        SUB
+        CALL fib        ; fib(n-1) fib(n-2)
+        ADD
+    base:
+        RET
+    ";
+    
+    fn run(source: &str) -> Result<Vm, VmError> {
+        let mut vm = Vm::new(assemble(source).unwrap());
+        vm.run()?;
+        Ok(vm)
+    }
+    
+    #[test]
+    fn test_arithmetic() {
+        let vm = run("PUSH 7\nPUSH 3\nSUB\nPUSH 4\nMUL\nPUSH 5\nMOD\nNEG").unwrap();
+        assert_eq!(vm.stack(), &[-1]);
+        assert!(vm.is_halted());
+        let vm = run("PUSH 2\nPUSH 3\nLT\nPUSH 3\nPUSH 3\nEQ\nPUSH 1\nPUSH 2\nGT").unwrap();
+        assert_eq!(vm.stack(), &[1, 1, 0]);
+    }
+    
+    #[test]
+    fn test_stack_shuffling() {
+        let vm = run("PUSH 1\nPUSH 2\nPUSH 3\nROT\nOVER\nSWAP\nDUP").unwrap();
+        assert_eq!(vm.stack(), &[2, 3, 3, 1, 1]);
+    }
+    
+    #[test]
+    fn test_loops_and_calls() {
+        assert_eq!(run(FACTORIAL).unwrap().output(), &[120]);
+        let vm = run(FIBONACCI).unwrap();
+        assert_eq!(vm.output(), &[55]);
+        assert!(vm.stack().is_empty());
+    }
+    
+    #[test]
+    fn test_runtime_errors() {
+        assert_eq!(run("PUSH 1\nADD").err(), Some(VmError::StackUnderflow { pc: 1 }));
+        assert_eq!(run("PUSH 1\nPUSH 0\nDIV").err(), Some(VmError::DivisionByZero { pc: 2 }));
+        assert_eq!(
+            run("PUSH 9223372036854775807\nPUSH 1\nADD").err(),
+            Some(VmError::ArithmeticOverflow { pc: 2 })
+        );
+        assert_eq!(run("RET").err(), Some(VmError::ReturnWithoutCall { pc: 0 }));
+        assert_eq!(run("JMP 42").err(), Some(VmError::InvalidTarget { pc: 0, target: 42 }));
+        
+        let mut vm = Vm::new(assemble("f: CALL f").unwrap()).max_call_depth(8);
+        assert_eq!(vm.run(), Err(VmError::CallStackOverflow { pc: 0 }));
+    }
+    
+    #[test]
+    fn test_step_limit() {
+        let mut vm = Vm::new(assemble("spin: JMP spin").unwrap()).step_limit(100);
+        assert_eq!(vm.run(), Err(VmError::StepLimitExceeded { limit: 100 }));
+        assert_eq!(vm.steps(), 100);
+    }
+    
+    #[test]
+    fn test_trace() {
+        let mut vm = Vm::new(assemble("PUSH 2\nPUSH 3\nADD\nPRINT").unwrap()).trace(true);
+        vm.run().unwrap();
+        let trace = vm.trace_entries();
+        assert_eq!(trace.len(), 4);
+        assert_eq!(trace[2].instruction, Instruction::Add);
+        assert_eq!(trace[2].stack, vec![5]);
+        assert_eq!(trace[2].to_string(), "    3 | 0002 ADD        [5]");
+        assert!(Vm::new(vec![Instruction::Halt]).trace_entries().is_empty());
+    }
+    
+    #[test]
+    fn test_assembler() {
+        let program = assemble(FIBONACCI).unwrap();
+        assert_eq!(program[1], Instruction::Call(4));
+        assert_eq!(program[7], Instruction::JumpIfNotZero(17));
+        
+        // Disassembled output assembles back to the same program
+        let listing: Vec<String> = program.iter().map(|i| i.to_string()).collect();
+        assert_eq!(assemble(&listing.join("\n")).unwrap(), program);
+        
+        assert_eq!(
+            assemble("PUSH 1\nFOO"),
+            Err(AssembleError::UnknownInstruction { line: 2, mnemonic: "FOO".to_string() })
+        );
+        assert_eq!(assemble("JMP nowhere"), Err(AssembleError::UnknownLabel { line: 1, label: "nowhere".to_string() }));
+        assert_eq!(assemble("PUSH"), Err(AssembleError::MissingOperand { line: 1 }));
+        assert_eq!(assemble("ADD 1"), Err(AssembleError::UnexpectedOperand { line: 1 }));
+        assert_eq!(assemble("a:\na:"), Err(AssembleError::DuplicateLabel { line: 2, label: "a".to_string() }));
+    }
+    
+    #[test]
+    fn test_compile_rpn_matches_eval_rpn() {
+        let tokens: Vec<String> = ["4", "13", "5", "/", "+", "3", "-", "2", "*"].iter().map(|s| s.to_string()).collect();
+        let mut vm = Vm::new(compile_rpn(&tokens).unwrap());
+        vm.run().unwrap();
+        assert_eq!(vm.stack(), &[eval_rpn(&tokens) as i64]);
+    }
+}
@@ -1,6 +1,8 @@
-//! String Problems Module (Coming Soon)
+//! String Problems Module
 //! 
-//! This module will contain string manipulation problems:
+//! This module contains string data structures and problems:
+//! - Trie with prefix search, deletion, top-k autocomplete and `.` wildcards
+//! - Radix tree (compressed trie) with memory-usage reporting
 //! - Pattern Matching (KMP, Boyer-Moore)
 //! - String Manipulation
 //! - Regular Expressions
@@ -8,21 +10,51 @@
 //! - Anagram problems
 //! - String algorithms
 
+pub mod radix_tree;
+pub mod trie;
+
 use crate::{measure_time, assert_result};
+use radix_tree::RadixTree;
+use trie::Trie;
 
-/// Placeholder function for string problems
 pub fn run_examples() {
-    println!("📝 String Problems Module - Coming Soon!");
-    println!("========================================");
-    println!("This module will include:");
+    println!("Running String Problem Examples...");
+    
+    // Trie
+    let mut trie: Trie = measure_time("Trie Build", || ["apple", "app", "apply", "banana", "band"].into_iter().collect());
+    println!("Trie: contains(\"app\") = {}, starts_with(\"ban\") = {}, words with prefix \"app\" = {:?}",
+        trie.contains("app"), trie.starts_with("ban"), trie.words_with_prefix("app"));
+    assert_result(trie.words_with_prefix("app"), vec!["app".to_string(), "apple".into(), "apply".into()], "Trie Prefix Search");
+    assert_result((trie.remove("app"), trie.contains("app"), trie.contains("apple")), (Some(1), false, true), "Trie Delete");
+    assert_result((trie.matches("b.n.n."), trie.matches("b.nd"), trie.matches("..")), (true, true, false), "Trie Wildcard Search");
+    
+    // Autocomplete by frequency
+    let mut searches = Trie::new();
+    for (query, count) in [("i love you", 5), ("island", 3), ("ironman", 2), ("i love leetcode", 2), ("icon", 3)] {
+        searches.insert_with_frequency(query, count);
+    }
+    let top = measure_time("Autocomplete", || searches.autocomplete("i", 3));
+    println!("Autocomplete \"i\", top 3: {:?}", top);
+    assert_result(top.iter().map(|(query, _)| query.as_str()).collect::<Vec<_>>(), vec!["i love you", "icon", "island"], "Autocomplete Top-K");
+    
+    // Radix tree
+    let words = ["internationalization", "internationalize", "international", "internet", "interval"];
+    let radix: RadixTree<usize> = words.iter().map(|word| (*word, word.len())).collect();
+    let plain: Trie = words.into_iter().collect();
+    let (compressed, uncompressed) = (radix.memory_usage(), plain.memory_usage());
+    println!("Radix Tree: {} nodes / {} bytes vs Trie: {} nodes / {} bytes", compressed.nodes, compressed.bytes, uncompressed.nodes, uncompressed.bytes);
+    println!("Radix Tree keys with prefix \"intern\": {:?}", radix.with_prefix("intern").into_iter().map(|(key, _)| key).collect::<Vec<_>>());
+    assert_result((compressed.nodes, uncompressed.nodes), (9, 27), "Radix Tree Compression");
+    assert_result(radix.get("internet"), Some(&8), "Radix Tree Lookup");
+    
+    println!();
+    println!("Coming soon:");
     println!("• Pattern Matching (KMP, Boyer-Moore)");
     println!("• String Manipulation");
     println!("• Regular Expressions");
     println!("• Palindrome problems");
     println!("• Anagram problems");
     println!("• String algorithms");
-    println!();
-    println!("Stay tuned for comprehensive string implementations!");
 }
 
 #[cfg(test)]
@@ -0,0 +1,307 @@
+//! Radix Tree (Compressed Trie)
+//! 
+//! A map from strings to values where each edge carries a whole substring
+//! instead of one character: chains of nodes with a single child and no value
+//! are merged into one. Node count is bounded by twice the number of keys,
+//! however long the keys are.
+//! - insert (splitting an edge where a new key diverges), get, remove
+//!   (merging a node back into its only child)
+//! - ordered prefix queries
+//! - `memory_usage`, comparable with the uncompressed `Trie`
+
+use super::trie::MemoryUsage;
+use std::mem::size_of;
+
+struct Node<V> {
+    /// The edge label leading here; empty only for the root.
+    label: String,
+    value: Option<V>,
+    /// Sorted by the first character of their labels, which are all distinct.
+    children: Vec<Node<V>>,
+}
+
+impl<V> Node<V> {
+    fn first_char(&self) -> char {
+        self.label.chars().next().expect("only the root has an empty label")
+    }
+    
+    /// Index of the child whose label starts with the first character of `key`.
+    fn child_index(&self, key: &str) -> Result<usize, usize> {
+        let c = key.chars().next().expect("key is not empty");
+        self.children.binary_search_by_key(&c, Node::first_char)
+    }
+    
+    /// Every entry in this subtree, in order, with keys starting with `prefix`.
+    fn collect<'a>(&'a self, prefix: &mut String, out: &mut Vec<(String, &'a V)>) {
+        let len = prefix.len();
+        prefix.push_str(&self.label);
+        if let Some(value) = &self.value {
+            out.push((prefix.clone(), value));
+        }
+        for child in &self.children {
+            child.collect(prefix, out);
+        }
+        prefix.truncate(len);
+    }
+}
+
+/// Byte length of the longest common prefix of `a` and `b`, on a char boundary.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, x), y)| x != y)
+        .map_or(a.len().min(b.len()), |((i, _), _)| i)
+}
+
+/// Problem: Radix Tree
+pub struct RadixTree<V> {
+    root: Node<V>,
+    len: usize,
+}
+
+impl<V> RadixTree<V> {
+    pub fn new() -> Self {
+        RadixTree {
+            root: Node { label: String::new(), value: None, children: Vec::new() },
+            len: 0,
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Insert or replace, returning the previous value for `key`.
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        fn insert<V>(node: &mut Node<V>, key: &str, value: V) -> Option<V> {
+            if key.is_empty() {
+                return node.value.replace(value);
+            }
+            let i = match node.child_index(key) {
+                Ok(i) => i,
+                Err(i) => {
+                    let leaf = Node { label: key.to_string(), value: Some(value), children: Vec::new() };
+                    node.children.insert(i, leaf);
+                    return None;
+                }
+            };
+            let child = &mut node.children[i];
+            let common = common_prefix_len(&child.label, key);
+            if common < child.label.len() {
+                // The key leaves this edge part-way: split it, pushing the rest of the edge down
+                let lower = Node {
+                    label: child.label.split_off(common),
+                    value: child.value.take(),
+                    children: std::mem::take(&mut child.children),
+                };
+                child.children.push(lower);
+            }
+            insert(child, &key[common..], value)
+        }
+        let old = insert(&mut self.root, key, value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+    
+    /// The node reached by following exactly `key` from the root.
+    fn find(&self, key: &str) -> Option<&Node<V>> {
+        let mut node = &self.root;
+        let mut rest = key;
+        while !rest.is_empty() {
+            node = &node.children[node.child_index(rest).ok()?];
+            rest = rest.strip_prefix(node.label.as_str())?;
+        }
+        Some(node)
+    }
+    
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.find(key)?.value.as_ref()
+    }
+    
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        while !rest.is_empty() {
+            let i = node.child_index(rest).ok()?;
+            node = &mut node.children[i];
+            rest = rest.strip_prefix(node.label.as_str())?;
+        }
+        node.value.as_mut()
+    }
+    
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+    
+    /// Remove `key`, merging away any node that no longer needs to exist.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        fn remove<V>(node: &mut Node<V>, key: &str) -> Option<V> {
+            if key.is_empty() {
+                return node.value.take();
+            }
+            let i = node.child_index(key).ok()?;
+            let child = &mut node.children[i];
+            let rest = key.strip_prefix(child.label.as_str())?;
+            let removed = remove(child, rest)?;
+            if child.value.is_none() {
+                match child.children.len() {
+                    0 => {
+                        node.children.remove(i);
+                    }
+                    1 => {
+                        let only = child.children.pop().expect("one child");
+                        child.label.push_str(&only.label);
+                        child.value = only.value;
+                        child.children = only.children;
+                    }
+                    _ => {}
+                }
+            }
+            Some(removed)
+        }
+        let removed = remove(&mut self.root, key)?;
+        self.len -= 1;
+        Some(removed)
+    }
+    
+    /// Entries whose keys start with `prefix`, in key order.
+    pub fn with_prefix(&self, prefix: &str) -> Vec<(String, &V)> {
+        let mut out = Vec::new();
+        let mut path = String::new();
+        if prefix.is_empty() {
+            self.root.collect(&mut path, &mut out);
+            return out;
+        }
+        let mut node = &self.root;
+        let mut rest = prefix;
+        loop {
+            let Ok(i) = node.child_index(rest) else { return out };
+            let child = &node.children[i];
+            if child.label.starts_with(rest) {
+                // The prefix ends on this edge: everything below matches
+                child.collect(&mut path, &mut out);
+                return out;
+            }
+            let Some(tail) = rest.strip_prefix(child.label.as_str()) else { return out };
+            path.push_str(&child.label);
+            rest = tail;
+            node = child;
+        }
+    }
+    
+    /// Every entry in key order.
+    pub fn entries(&self) -> Vec<(String, &V)> {
+        self.with_prefix("")
+    }
+    
+    /// Approximate bytes used: the tree itself plus every node's label and
+    /// child array, excluding anything the values own on the heap.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        fn walk<V>(node: &Node<V>, usage: &mut MemoryUsage) {
+            usage.nodes += 1;
+            usage.bytes += node.label.capacity() + node.children.capacity() * size_of::<Node<V>>();
+            for child in &node.children {
+                walk(child, usage);
+            }
+        }
+        let mut usage = MemoryUsage { nodes: 0, bytes: size_of::<Self>() };
+        walk(&self.root, &mut usage);
+        usage
+    }
+}
+
+impl<V> Default for RadixTree<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, V> FromIterator<(&'a str, V)> for RadixTree<V> {
+    fn from_iter<I: IntoIterator<Item = (&'a str, V)>>(iter: I) -> Self {
+        let mut tree = RadixTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strings::trie::Trie;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+    
+    #[test]
+    fn test_split_and_merge() {
+        let mut tree: RadixTree<u32> = [("romane", 1), ("romanus", 2), ("romulus", 3)].into_iter().collect();
+        // root -> "rom" -> { "an" -> { "e", "us" }, "ulus" }
+        assert_eq!(tree.memory_usage().nodes, 6);
+        assert_eq!(tree.get("romanus"), Some(&2));
+        assert_eq!(tree.get("roman"), None);
+        assert_eq!(tree.insert("roman", 4), None);
+        assert_eq!(tree.memory_usage().nodes, 6);
+        assert_eq!(tree.remove("romane"), Some(1));
+        // "roman" keeps its node, now with the single child "us"
+        assert_eq!(tree.memory_usage().nodes, 5);
+        assert_eq!(tree.remove("roman"), Some(4));
+        // "an" merges with "us" into "anus"
+        assert_eq!(tree.memory_usage().nodes, 4);
+        assert_eq!(tree.entries(), [("romanus".to_string(), &2), ("romulus".to_string(), &3)]);
+        *tree.get_mut("romulus").unwrap() += 10;
+        assert_eq!(tree.get("romulus"), Some(&13));
+        assert_eq!(tree.remove("rom"), None);
+        assert_eq!(tree.len(), 2);
+    }
+    
+    #[test]
+    fn test_prefix_queries() {
+        let tree: RadixTree<()> = ["test", "team", "toast", "tea", "té"].into_iter().map(|key| (key, ())).collect();
+        let keys = |prefix| tree.with_prefix(prefix).into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+        assert_eq!(keys("te"), ["tea", "team", "test"]);
+        assert_eq!(keys("tea"), ["tea", "team"]);
+        assert_eq!(keys("teamwork"), Vec::<String>::new());
+        assert_eq!(keys("t"), ["tea", "team", "test", "toast", "té"]);
+        assert_eq!(keys("té"), ["té"]);
+        assert_eq!(keys("x"), Vec::<String>::new());
+    }
+    
+    #[test]
+    fn test_compression_saves_nodes() {
+        let words = ["internationalization", "internationalize", "international", "internet", "interval"];
+        let trie: Trie = words.into_iter().collect();
+        let radix: RadixTree<()> = words.into_iter().map(|word| (word, ())).collect();
+        assert!(radix.memory_usage().nodes * 2 < trie.memory_usage().nodes);
+        assert!(radix.memory_usage().bytes < trie.memory_usage().bytes);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_btreemap(ops in proptest::collection::vec((any::<bool>(), "[abé]{0,5}", any::<u8>()), 0..150)) {
+            let mut tree = RadixTree::new();
+            let mut reference = BTreeMap::new();
+            for (insert, key, value) in &ops {
+                if *insert {
+                    prop_assert_eq!(tree.insert(key, *value), reference.insert(key.clone(), *value));
+                } else {
+                    prop_assert_eq!(tree.remove(key), reference.remove(key));
+                }
+                prop_assert_eq!(tree.get(key), reference.get(key));
+                prop_assert_eq!(tree.len(), reference.len());
+            }
+            let expected: Vec<(String, &u8)> = reference.iter().map(|(key, value)| (key.clone(), value)).collect();
+            prop_assert_eq!(tree.entries(), expected);
+            let expected: Vec<(String, &u8)> = reference.iter().filter(|(key, _)| key.starts_with("ab")).map(|(key, value)| (key.clone(), value)).collect();
+            prop_assert_eq!(tree.with_prefix("ab"), expected);
+            // Compression: every node but the root holds a value or branches
+            prop_assert!(tree.memory_usage().nodes <= 2 * tree.len() + 1);
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Trie (Prefix Tree)
+//! 
+//! One node per character, children kept sorted so words come out in
+//! lexicographic order. Each word carries a frequency: inserting it again
+//! bumps the count, which is what autocomplete ranks by.
+//! - insert, exact and prefix search, and delete (pruning dead branches)
+//! - `autocomplete`: the k most frequent words under a prefix
+//! - wildcard search, where `.` matches any single character
+//! - `memory_usage` to compare against the compressed `RadixTree`
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+
+/// Approximate heap footprint of a prefix tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub nodes: usize,
+    pub bytes: usize,
+}
+
+#[derive(Default)]
+struct TrieNode {
+    /// Sorted by character.
+    children: Vec<(char, TrieNode)>,
+    /// How many times the word ending here was inserted; 0 if no word ends here.
+    frequency: usize,
+}
+
+impl TrieNode {
+    fn child(&self, c: char) -> Option<&TrieNode> {
+        let i = self.children.binary_search_by_key(&c, |(key, _)| *key).ok()?;
+        Some(&self.children[i].1)
+    }
+    
+    /// Every word in this subtree, in order, as `prefix` followed by its path from here.
+    fn collect(&self, prefix: &mut String, out: &mut Vec<(String, usize)>) {
+        if self.frequency > 0 {
+            out.push((prefix.clone(), self.frequency));
+        }
+        for (c, child) in &self.children {
+            prefix.push(*c);
+            child.collect(prefix, out);
+            prefix.pop();
+        }
+    }
+}
+
+/// Problem: Implement Trie (Prefix Tree)
+#[derive(Default)]
+pub struct Trie {
+    root: TrieNode,
+    len: usize,
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Trie::default()
+    }
+    
+    /// Number of distinct words.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Insert `word` once, returning its new frequency.
+    pub fn insert(&mut self, word: &str) -> usize {
+        self.insert_with_frequency(word, 1)
+    }
+    
+    /// Add `frequency` occurrences of `word`, returning its new frequency.
+    /// Adding 0 changes nothing, so no branch is created for an absent word.
+    /// The count saturates at `usize::MAX`.
+    pub fn insert_with_frequency(&mut self, word: &str, frequency: usize) -> usize {
+        if frequency == 0 {
+            return self.frequency(word);
+        }
+        let mut node = &mut self.root;
+        for c in word.chars() {
+            let i = match node.children.binary_search_by_key(&c, |(key, _)| *key) {
+                Ok(i) => i,
+                Err(i) => {
+                    node.children.insert(i, (c, TrieNode::default()));
+                    i
+                }
+            };
+            node = &mut node.children[i].1;
+        }
+        if node.frequency == 0 {
+            self.len += 1;
+        }
+        node.frequency = node.frequency.saturating_add(frequency);
+        node.frequency
+    }
+    
+    fn find(&self, prefix: &str) -> Option<&TrieNode> {
+        prefix.chars().try_fold(&self.root, |node, c| node.child(c))
+    }
+    
+    /// How many times `word` was inserted; 0 if it is not in the trie.
+    pub fn frequency(&self, word: &str) -> usize {
+        self.find(word).map_or(0, |node| node.frequency)
+    }
+    
+    pub fn contains(&self, word: &str) -> bool {
+        self.frequency(word) > 0
+    }
+    
+    /// Whether any word starts with `prefix`.
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.find(prefix).is_some()
+    }
+    
+    /// Every word starting with `prefix`, in lexicographic order.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut out = Vec::new();
+        if let Some(node) = self.find(prefix) {
+            node.collect(&mut prefix.to_string(), &mut out);
+        }
+        out.into_iter().map(|(word, _)| word).collect()
+    }
+    
+    /// Delete `word`, returning the frequency it had, and drop any branch
+    /// left leading to no word.
+    pub fn remove(&mut self, word: &str) -> Option<usize> {
+        /// Returns the removed frequency and whether `node` is now dead.
+        fn remove(node: &mut TrieNode, mut chars: std::str::Chars<'_>) -> Option<(usize, bool)> {
+            let removed = match chars.next() {
+                None => Some(std::mem::take(&mut node.frequency)).filter(|&frequency| frequency > 0)?,
+                Some(c) => {
+                    let i = node.children.binary_search_by_key(&c, |(key, _)| *key).ok()?;
+                    let (removed, dead) = remove(&mut node.children[i].1, chars)?;
+                    if dead {
+                        node.children.remove(i);
+                    }
+                    removed
+                }
+            };
+            Some((removed, node.frequency == 0 && node.children.is_empty()))
+        }
+        let (removed, _) = remove(&mut self.root, word.chars())?;
+        self.len -= 1;
+        Some(removed)
+    }
+    
+    /// Problem: Search Autocomplete System
+    /// The `k` most frequent words starting with `prefix`, most frequent first
+    /// and alphabetical among equals. Keeps a heap of the best `k` seen so far
+    /// rather than sorting every match.
+    pub fn autocomplete(&self, prefix: &str, k: usize) -> Vec<(String, usize)> {
+        let mut matches = Vec::new();
+        if let Some(node) = self.find(prefix) {
+            node.collect(&mut prefix.to_string(), &mut matches);
+        }
+        // The heap's top is the worst kept entry: lowest frequency, then last alphabetically
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        for (word, frequency) in matches {
+            best.push((Reverse(frequency), word));
+            if best.len() > k {
+                best.pop();
+            }
+        }
+        best.into_sorted_vec().into_iter().map(|(Reverse(frequency), word)| (word, frequency)).collect()
+    }
+    
+    /// Problem: Design Add and Search Words Data Structure
+    /// Whether some word matches `pattern`, where `.` stands for any one character.
+    pub fn matches(&self, pattern: &str) -> bool {
+        fn matches(node: &TrieNode, pattern: &[char]) -> bool {
+            match pattern.split_first() {
+                None => node.frequency > 0,
+                Some(('.', rest)) => node.children.iter().any(|(_, child)| matches(child, rest)),
+                Some((c, rest)) => node.child(*c).is_some_and(|child| matches(child, rest)),
+            }
+        }
+        matches(&self.root, &pattern.chars().collect::<Vec<_>>())
+    }
+    
+    /// Approximate bytes used: the trie itself plus every node's child array.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        fn walk(node: &TrieNode, usage: &mut MemoryUsage) {
+            usage.nodes += 1;
+            usage.bytes += node.children.capacity() * size_of::<(char, TrieNode)>();
+            for (_, child) in &node.children {
+                walk(child, usage);
+            }
+        }
+        let mut usage = MemoryUsage { nodes: 0, bytes: size_of::<Self>() };
+        walk(&self.root, &mut usage);
+        usage
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Trie {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut trie = Trie::new();
+        for word in iter {
+            trie.insert(word);
+        }
+        trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+    
+    #[test]
+    fn test_insert_and_search() {
+        let mut trie: Trie = ["apple", "app", "apply", "banana"].into_iter().collect();
+        assert!(trie.contains("app") && trie.contains("apple"));
+        assert!(!trie.contains("ap") && !trie.contains("apples"));
+        assert!(trie.starts_with("ap") && trie.starts_with("") && !trie.starts_with("c"));
+        assert_eq!(trie.words_with_prefix("app"), ["app", "apple", "apply"]);
+        assert_eq!(trie.insert("app"), 2);
+        assert_eq!((trie.len(), trie.frequency("app"), trie.frequency("ap")), (4, 2, 0));
+    }
+    
+    #[test]
+    fn test_insert_zero_frequency() {
+        let mut trie: Trie = ["app"].into_iter().collect();
+        let nodes = trie.memory_usage().nodes;
+        assert_eq!(trie.insert_with_frequency("apple", 0), 0);
+        assert_eq!(trie.insert_with_frequency("app", 0), 1);
+        assert!(!trie.starts_with("appl") && !trie.contains("apple"));
+        assert_eq!((trie.len(), trie.memory_usage().nodes), (1, nodes));
+    }
+    
+    #[test]
+    fn test_frequency_saturates() {
+        let mut trie = Trie::new();
+        assert_eq!(trie.insert_with_frequency("max", usize::MAX), usize::MAX);
+        assert_eq!(trie.insert_with_frequency("max", usize::MAX), usize::MAX);
+        assert_eq!(trie.insert("max"), usize::MAX);
+        assert_eq!((trie.len(), trie.frequency("max")), (1, usize::MAX));
+    }
+    
+    #[test]
+    fn test_remove_prunes() {
+        let mut trie: Trie = ["car", "cart", "cat"].into_iter().collect();
+        let full = trie.memory_usage().nodes;
+        assert_eq!(trie.remove("cart"), Some(1));
+        assert_eq!(trie.memory_usage().nodes, full - 1);
+        assert_eq!(trie.remove("cart"), None);
+        assert_eq!(trie.remove("ca"), None);
+        assert!(trie.contains("car") && trie.starts_with("ca"));
+        trie.remove("car");
+        trie.remove("cat");
+        assert!(trie.is_empty());
+        assert_eq!(trie.memory_usage().nodes, 1);
+    }
+    
+    #[test]
+    fn test_autocomplete() {
+        let mut trie = Trie::new();
+        for (word, frequency) in [("i love you", 5), ("island", 3), ("ironman", 2), ("i love leetcode", 2), ("icon", 3)] {
+            trie.insert_with_frequency(word, frequency);
+        }
+        let top = trie.autocomplete("i", 3);
+        assert_eq!(top, [("i love you".to_string(), 5), ("icon".to_string(), 3), ("island".to_string(), 3)]);
+        assert_eq!(trie.autocomplete("i ", 5).len(), 2);
+        assert_eq!(trie.autocomplete("x", 3), []);
+        assert_eq!(trie.autocomplete("i", 0), []);
+    }
+    
+    #[test]
+    fn test_wildcards() {
+        let trie: Trie = ["bad", "dad", "mad", "héllo"].into_iter().collect();
+        assert!(!trie.matches("pad"));
+        assert!(trie.matches("bad") && trie.matches(".ad") && trie.matches("b.."));
+        assert!(!trie.matches("b.") && !trie.matches("...."));
+        assert!(trie.matches("h.llo"));
+        assert!(!Trie::new().matches(""));
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_btreemap(ops in proptest::collection::vec((any::<bool>(), "[ab]{0,4}"), 0..100)) {
+            let mut trie = Trie::new();
+            let mut reference: BTreeMap<String, usize> = BTreeMap::new();
+            for (insert, word) in &ops {
+                if *insert {
+                    *reference.entry(word.clone()).or_default() += 1;
+                    prop_assert_eq!(trie.insert(word), reference[word]);
+                } else {
+                    prop_assert_eq!(trie.remove(word), reference.remove(word));
+                }
+                prop_assert_eq!(trie.len(), reference.len());
+            }
+            prop_assert_eq!(trie.words_with_prefix(""), reference.keys().cloned().collect::<Vec<_>>());
+            let a_words: Vec<String> = reference.keys().filter(|word| word.starts_with('a')).cloned().collect();
+            prop_assert_eq!(trie.words_with_prefix("a"), a_words);
+            // Nothing but the root survives once every word is gone
+            for word in reference.keys() {
+                trie.remove(word);
+            }
+            prop_assert_eq!(trie.memory_usage().nodes, 1);
+        }
+    }
+}
@@ -0,0 +1,624 @@
+//! AVL Tree
+//! 
+//! A height-balanced binary search tree used as an ordered map: the heights of
+//! every node's two subtrees differ by at most one, so the tree stays
+//! O(log n) deep and every operation below is O(log n).
+//! - insert / get / remove, rebalancing with single and double rotations
+//! - in-order iteration
+//! - split at a key and join of two trees whose keys don't overlap
+//! - `check_invariants` (debug builds only) verifies heights, balance factors, sizes and key order
+
+use super::ordered_map::OrderedMap;
+use super::visualize::{Sketch, Visualize};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// Height of the subtree rooted here; a leaf has height 1.
+    height: u32,
+    /// Number of entries in the subtree rooted here.
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            height: 1,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+    
+    /// Recompute `height` and `size` from the children.
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+    
+    /// Left height minus right height.
+    fn balance_factor(&self) -> i64 {
+        i64::from(height(&self.left)) - i64::from(height(&self.right))
+    }
+}
+
+fn height<K, V>(link: &Link<K, V>) -> u32 {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut pivot = node.left.take().expect("rotate_right needs a left child");
+    node.left = pivot.right.take();
+    node.update();
+    pivot.right = Some(node);
+    pivot.update();
+    pivot
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut pivot = node.right.take().expect("rotate_left needs a right child");
+    node.right = pivot.left.take();
+    node.update();
+    pivot.left = Some(node);
+    pivot.update();
+    pivot
+}
+
+/// Restore the AVL property at `node`, whose subtrees are balanced and differ in height by at most two.
+fn rebalance<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    node.update();
+    match node.balance_factor() {
+        2.. => {
+            if node.left.as_ref().is_some_and(|left| left.balance_factor() < 0) {
+                node.left = node.left.take().map(rotate_left);
+            }
+            rotate_right(node)
+        }
+        ..=-2 => {
+            if node.right.as_ref().is_some_and(|right| right.balance_factor() > 0) {
+                node.right = node.right.take().map(rotate_right);
+            }
+            rotate_left(node)
+        }
+        _ => node,
+    }
+}
+
+fn insert<K: Ord, V>(link: Link<K, V>, key: K, value: V) -> (Box<Node<K, V>>, Option<V>) {
+    let Some(mut node) = link else {
+        return (Node::new(key, value), None);
+    };
+    let old = match key.cmp(&node.key) {
+        Ordering::Less => {
+            let (left, old) = insert(node.left.take(), key, value);
+            node.left = Some(left);
+            old
+        }
+        Ordering::Greater => {
+            let (right, old) = insert(node.right.take(), key, value);
+            node.right = Some(right);
+            old
+        }
+        Ordering::Equal => {
+            let old = std::mem::replace(&mut node.value, value);
+            return (node, Some(old));
+        }
+    };
+    (rebalance(node), old)
+}
+
+/// Detach the minimum node of a subtree, returning it and the rebalanced rest.
+fn take_min<K, V>(mut node: Box<Node<K, V>>) -> (Box<Node<K, V>>, Link<K, V>) {
+    match node.left.take() {
+        None => {
+            let rest = node.right.take();
+            (node, rest)
+        }
+        Some(left) => {
+            let (min, rest) = take_min(left);
+            node.left = rest;
+            (min, Some(rebalance(node)))
+        }
+    }
+}
+
+fn remove<K: Ord, V>(link: Link<K, V>, key: &K) -> (Link<K, V>, Option<V>) {
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    let removed = match key.cmp(&node.key) {
+        Ordering::Less => {
+            let (left, removed) = remove(node.left.take(), key);
+            node.left = left;
+            removed
+        }
+        Ordering::Greater => {
+            let (right, removed) = remove(node.right.take(), key);
+            node.right = right;
+            removed
+        }
+        Ordering::Equal => {
+            let replacement = match (node.left.take(), node.right.take()) {
+                (None, None) => None,
+                (Some(child), None) | (None, Some(child)) => Some(child),
+                (Some(left), Some(right)) => {
+                    let (mut successor, rest) = take_min(right);
+                    successor.left = Some(left);
+                    successor.right = rest;
+                    Some(rebalance(successor))
+                }
+            };
+            return (replacement, Some(node.value));
+        }
+    };
+    (Some(rebalance(node)), removed)
+}
+
+/// Join `left`, `mid` and `right`, where every key in `left` is below `mid`'s
+/// and every key in `right` is above it. Walks down the spine of the taller
+/// tree to a subtree of the shorter one's height, so it costs
+/// O(|height(left) - height(right)| + 1).
+fn join<K, V>(left: Link<K, V>, mut mid: Box<Node<K, V>>, right: Link<K, V>) -> Box<Node<K, V>> {
+    let (left_height, right_height) = (height(&left), height(&right));
+    if left_height > right_height + 1 {
+        let mut left = left.expect("taller tree is not empty");
+        left.right = Some(join(left.right.take(), mid, right));
+        rebalance(left)
+    } else if right_height > left_height + 1 {
+        let mut right = right.expect("taller tree is not empty");
+        right.left = Some(join(left, mid, right.left.take()));
+        rebalance(right)
+    } else {
+        mid.left = left;
+        mid.right = right;
+        mid.update();
+        mid
+    }
+}
+
+/// Split a subtree into the entries below `key`, the entry at `key` and the entries above it.
+fn split<K: Ord, V>(link: Link<K, V>, key: &K) -> (Link<K, V>, Option<V>, Link<K, V>) {
+    let Some(mut node) = link else {
+        return (None, None, None);
+    };
+    let (left, right) = (node.left.take(), node.right.take());
+    match key.cmp(&node.key) {
+        Ordering::Less => {
+            let (below, found, above) = split(left, key);
+            (below, found, Some(join(above, node, right)))
+        }
+        Ordering::Greater => {
+            let (below, found, above) = split(right, key);
+            (Some(join(left, node, below)), found, above)
+        }
+        Ordering::Equal => (left, Some(node.value), right),
+    }
+}
+
+/// Problem: AVL Tree
+pub struct AvlTree<K, V> {
+    root: Link<K, V>,
+}
+
+impl<K: Ord, V> AvlTree<K, V> {
+    pub fn new() -> Self {
+        AvlTree { root: None }
+    }
+    
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+    
+    /// Height of the tree; 0 when empty.
+    pub fn height(&self) -> u32 {
+        height(&self.root)
+    }
+    
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(&node.value),
+            };
+        }
+        None
+    }
+    
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+    
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+    
+    /// Insert or replace, returning the previous value for `key`.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (root, old) = insert(self.root.take(), key, value);
+        self.root = Some(root);
+        old
+    }
+    
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (root, removed) = remove(self.root.take(), key);
+        self.root = root;
+        removed
+    }
+    
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+    
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+    
+    /// Split into the entries below `key`, the value at `key` (if any) and the entries above it.
+    pub fn split(mut self, key: &K) -> (Self, Option<V>, Self) {
+        let (below, found, above) = split(self.root.take(), key);
+        (AvlTree { root: below }, found, AvlTree { root: above })
+    }
+    
+    /// Move the entries with keys `>= key` into a new tree, like `BTreeMap::split_off`.
+    pub fn split_off(&mut self, key: K) -> Self {
+        let (below, found, above) = split(self.root.take(), &key);
+        self.root = below;
+        let mut above = AvlTree { root: above };
+        if let Some(value) = found {
+            above.insert(key, value);
+        }
+        above
+    }
+    
+    /// Concatenate two trees. Panics unless every key in `left` is below every key in `right`.
+    pub fn join(mut left: Self, mut right: Self) -> Self {
+        if let (Some((left_max, _)), Some((right_min, _))) = (left.max(), right.min()) {
+            assert!(left_max < right_min, "joined trees must not overlap");
+        }
+        let Some(right_root) = right.root.take() else {
+            return left;
+        };
+        let (mid, rest) = take_min(right_root);
+        AvlTree {
+            root: Some(join(left.root.take(), mid, rest)),
+        }
+    }
+    
+    /// Entries in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+    
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+    
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+    
+    /// Panic if any node breaks the AVL invariants: stored heights and sizes
+    /// must match the subtrees, balance factors must be in -1..=1, and keys
+    /// must be strictly increasing in order. A no-op in release builds.
+    pub fn check_invariants(&self) {
+        fn walk<K: Ord, V>(link: &Link<K, V>, low: Option<&K>, high: Option<&K>) -> (u32, usize) {
+            let Some(node) = link else { return (0, 0) };
+            assert!(low.is_none_or(|low| *low < node.key), "keys out of order");
+            assert!(high.is_none_or(|high| node.key < *high), "keys out of order");
+            let (left_height, left_size) = walk(&node.left, low, Some(&node.key));
+            let (right_height, right_size) = walk(&node.right, Some(&node.key), high);
+            assert!(left_height.abs_diff(right_height) <= 1, "balance factor out of range");
+            assert_eq!(node.height, 1 + left_height.max(right_height), "stale height");
+            assert_eq!(node.size, 1 + left_size + right_size, "stale size");
+            (node.height, node.size)
+        }
+        if cfg!(debug_assertions) {
+            walk(&self.root, None, None);
+        }
+    }
+}
+
+impl<K: Ord, V> Default for AvlTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for AvlTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = AvlTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for AvlTree<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for AvlTree<K, V> {
+    type Iter<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+    
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        AvlTree::insert(self, key, value)
+    }
+    
+    fn get(&self, key: &K) -> Option<&V> {
+        AvlTree::get(self, key)
+    }
+    
+    fn remove(&mut self, key: &K) -> Option<V> {
+        AvlTree::remove(self, key)
+    }
+    
+    fn len(&self) -> usize {
+        AvlTree::len(self)
+    }
+    
+    fn min(&self) -> Option<(&K, &V)> {
+        AvlTree::min(self)
+    }
+    
+    fn max(&self) -> Option<(&K, &V)> {
+        AvlTree::max(self)
+    }
+    
+    fn iter(&self) -> Iter<'_, K, V> {
+        AvlTree::iter(self)
+    }
+}
+
+impl<K: Debug, V> Visualize for AvlTree<K, V> {
+    fn sketch(&self) -> Option<Sketch> {
+        fn sketch<K: Debug, V>(link: &Link<K, V>) -> Option<Sketch> {
+            let node = link.as_ref()?;
+            Some(Sketch::binary(format!("{:?} (h={})", node.key, node.height), sketch(&node.left), sketch(&node.right)))
+        }
+        sketch(&self.root)
+    }
+}
+
+/// In-order iterator over an `AvlTree`.
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left_spine(&mut self, mut node: Option<&'a Node<K, V>>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+    
+    /// Deterministic xorshift stream for the long randomized runs.
+    fn xorshift(seed: u64) -> impl Iterator<Item = u64> {
+        let mut state = seed | 1;
+        std::iter::repeat_with(move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+    }
+    
+    #[test]
+    fn test_rotations_on_sorted_input() {
+        // Ascending and descending inserts exercise single rotations,
+        // zig-zag inserts exercise double rotations
+        for keys in [(0..1000).collect::<Vec<_>>(), (0..1000).rev().collect(), vec![10, 30, 20, 5, 1, 3, 40, 35]] {
+            let mut tree = AvlTree::new();
+            for &key in &keys {
+                tree.insert(key, key * 2);
+                tree.check_invariants();
+            }
+            let mut sorted = keys.clone();
+            sorted.sort();
+            assert!(tree.keys().eq(sorted.iter()));
+        }
+        let tree: AvlTree<i32, ()> = (0..1023).map(|k| (k, ())).collect();
+        assert_eq!(tree.height(), 10);
+    }
+    
+    #[test]
+    fn test_insert_get_remove() {
+        let mut tree: AvlTree<&str, i32> = [("m", 1), ("c", 2), ("x", 3), ("a", 4), ("e", 5)].into_iter().collect();
+        assert_eq!(tree.insert("c", 20), Some(2));
+        assert_eq!(tree.get(&"c"), Some(&20));
+        *tree.get_mut(&"a").unwrap() += 1;
+        assert_eq!(tree.get(&"a"), Some(&5));
+        assert_eq!(tree.get(&"z"), None);
+        assert_eq!(tree.min(), Some((&"a", &5)));
+        assert_eq!(tree.max(), Some((&"x", &3)));
+        
+        // Removing the root (two children) promotes its successor
+        assert_eq!(tree.remove(&"m"), Some(1));
+        tree.check_invariants();
+        assert_eq!(tree.remove(&"m"), None);
+        assert_eq!(tree.remove(&"a"), Some(5));
+        tree.check_invariants();
+        assert_eq!(tree.keys().copied().collect::<Vec<_>>(), vec!["c", "e", "x"]);
+        assert_eq!(tree.len(), 3);
+    }
+    
+    #[test]
+    fn test_remove_rebalances() {
+        let mut tree: AvlTree<u32, ()> = (0..512).map(|k| (k, ())).collect();
+        for key in (0..512).filter(|k| k % 4 != 0) {
+            tree.remove(&key);
+            tree.check_invariants();
+        }
+        assert_eq!(tree.len(), 128);
+        assert!(tree.height() <= 8);
+    }
+    
+    #[test]
+    fn test_split_and_join() {
+        let tree: AvlTree<u32, u32> = (0..100).map(|k| (k * 2, k)).collect();
+        let (below, found, above) = tree.split(&50);
+        below.check_invariants();
+        above.check_invariants();
+        assert_eq!(found, Some(25));
+        assert!(below.keys().copied().eq((0..25).map(|k| k * 2)));
+        assert!(above.keys().copied().eq((26..100).map(|k| k * 2)));
+        
+        // Missing split key: nothing is found and nothing is lost
+        let (low, missing, high) = above.split(&101);
+        assert_eq!((missing, low.len(), high.len()), (None, 25, 49));
+        
+        // Joining trees of very different heights keeps the result balanced
+        let small: AvlTree<u32, u32> = [(1000, 0)].into_iter().collect();
+        let joined = AvlTree::join(AvlTree::join(below, low), small);
+        joined.check_invariants();
+        assert_eq!(joined.len(), 51);
+        assert_eq!(joined.max(), Some((&1000, &0)));
+        let joined = AvlTree::join(AvlTree::new(), AvlTree::join(joined, AvlTree::new()));
+        assert_eq!(joined.len(), 51);
+    }
+    
+    #[test]
+    fn test_split_off() {
+        let mut tree: AvlTree<u32, u32> = (0..10).map(|k| (k, k)).collect();
+        let upper = tree.split_off(4);
+        assert!(tree.keys().copied().eq(0..4));
+        assert!(upper.keys().copied().eq(4..10));
+        upper.check_invariants();
+    }
+    
+    #[test]
+    #[should_panic(expected = "must not overlap")]
+    fn test_join_rejects_overlap() {
+        let left: AvlTree<u32, ()> = [(5, ())].into_iter().collect();
+        let right: AvlTree<u32, ()> = [(3, ())].into_iter().collect();
+        AvlTree::join(left, right);
+    }
+    
+    /// A million mixed operations against `BTreeMap`, with a full invariant check every 50,000.
+    #[test]
+    fn test_million_random_ops() {
+        let mut tree = AvlTree::new();
+        let mut model = BTreeMap::new();
+        for (step, r) in xorshift(0xa71).take(1_000_000).enumerate() {
+            let key = (r >> 8) % 50_000;
+            match r % 8 {
+                0..=3 => assert_eq!(tree.insert(key, step), model.insert(key, step)),
+                4..=6 => assert_eq!(tree.remove(&key), model.remove(&key)),
+                _ => assert_eq!(tree.get(&key), model.get(&key)),
+            }
+            if step % 50_000 == 0 {
+                tree.check_invariants();
+                assert_eq!(tree.len(), model.len());
+            }
+        }
+        tree.check_invariants();
+        assert!(tree.iter().eq(model.iter()));
+        // Height stays within the AVL bound of about 1.44 log2(n)
+        assert!(f64::from(tree.height()) <= 1.45 * (tree.len() as f64 + 2.0).log2());
+    }
+    
+    #[derive(Debug, Clone)]
+    enum Op {
+        Insert(u8, u16),
+        Remove(u8),
+        SplitJoin(u8),
+    }
+    
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            4 => (any::<u8>(), any::<u16>()).prop_map(|(k, v)| Op::Insert(k, v)),
+            3 => any::<u8>().prop_map(Op::Remove),
+            1 => any::<u8>().prop_map(Op::SplitJoin),
+        ]
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_btreemap(ops in proptest::collection::vec(op(), 0..300)) {
+            let mut tree = AvlTree::new();
+            let mut model = BTreeMap::new();
+            for op in ops {
+                match op {
+                    Op::Insert(k, v) => prop_assert_eq!(tree.insert(k, v), model.insert(k, v)),
+                    Op::Remove(k) => prop_assert_eq!(tree.remove(&k), model.remove(&k)),
+                    Op::SplitJoin(k) => {
+                        // Split, check the pieces, then put the tree back together
+                        let (below, found, above) = std::mem::take(&mut tree).split(&k);
+                        below.check_invariants();
+                        above.check_invariants();
+                        prop_assert_eq!(found, model.get(&k).copied());
+                        prop_assert!(below.iter().eq(model.range(..k)));
+                        let mut rejoined = AvlTree::join(below, above);
+                        if let Some(value) = found {
+                            rejoined.insert(k, value);
+                        }
+                        tree = rejoined;
+                    }
+                }
+                tree.check_invariants();
+            }
+            prop_assert_eq!(tree.len(), model.len());
+            prop_assert!(tree.iter().eq(model.iter()));
+        }
+    }
+}
@@ -0,0 +1,603 @@
+//! Binary Search Tree
+//! 
+//! An unbalanced binary search tree used as an ordered map. Every node stores
+//! the size of its subtree, so order statistics are answered in one walk down.
+//! All operations walk the tree iteratively, so a degenerate (list-shaped) tree
+//! from sorted input is slow but never overflows the stack.
+//! - insert / get / remove (a node with two children takes its successor's entry)
+//! - min / max, floor / ceiling, rank / select
+//! - in-order and range iterators
+//! - `is_valid_bst` for plain `TreeNode` trees
+
+use super::ordered_map::OrderedMap;
+use super::traversal::Inorder;
+use super::visualize::{Sketch, Visualize};
+use super::TreeNode;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::{Bound, RangeBounds};
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// Number of entries in the subtree rooted here.
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+/// Problem: Binary Search Tree as an Ordered Map
+pub struct Bst<K, V> {
+    root: Link<K, V>,
+}
+
+impl<K: Ord, V> Bst<K, V> {
+    pub fn new() -> Self {
+        Bst { root: None }
+    }
+    
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+    
+    fn find(&self, key: &K) -> Option<&Node<K, V>> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(node),
+            };
+        }
+        None
+    }
+    
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|node| &node.value)
+    }
+    
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+    
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+    
+    /// Insert or replace, returning the previous value for `key`.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(std::mem::replace(existing, value));
+        }
+        // The key is new, so every node on the way down gains one descendant
+        let mut link = &mut self.root;
+        while let Some(node) = link {
+            node.size += 1;
+            link = if key < node.key { &mut node.left } else { &mut node.right };
+        }
+        *link = Some(Node::new(key, value));
+        None
+    }
+    
+    /// Remove `key`, returning its value. A node with two children is replaced
+    /// by its in-order successor, the minimum of its right subtree.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let mut link = &mut self.root;
+        loop {
+            let ordering = key.cmp(&link.as_ref().expect("key is present").key);
+            if ordering == Ordering::Equal {
+                break;
+            }
+            let node = link.as_mut().unwrap();
+            node.size -= 1;
+            link = if ordering == Ordering::Less { &mut node.left } else { &mut node.right };
+        }
+        
+        let mut node = link.take().expect("key is present");
+        *link = match (node.left.take(), node.right.take()) {
+            (None, None) => None,
+            (Some(child), None) | (None, Some(child)) => Some(child),
+            (Some(left), Some(right)) => {
+                let (mut successor, right) = take_min(right);
+                successor.left = Some(left);
+                successor.right = right;
+                successor.size = node.size - 1;
+                Some(successor)
+            }
+        };
+        Some(node.value)
+    }
+    
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+    
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+    
+    /// The entry with the greatest key `<= key`.
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        let mut best = None;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    best = Some((&node.key, &node.value));
+                    current = node.right.as_deref();
+                }
+            }
+        }
+        best
+    }
+    
+    /// The entry with the smallest key `>= key`.
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        let mut best = None;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Greater => current = node.right.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Less => {
+                    best = Some((&node.key, &node.value));
+                    current = node.left.as_deref();
+                }
+            }
+        }
+        best
+    }
+    
+    /// Number of keys strictly less than `key`.
+    pub fn rank(&self, key: &K) -> usize {
+        let mut rank = 0;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return rank + size(&node.left),
+                Ordering::Greater => {
+                    rank += size(&node.left) + 1;
+                    current = node.right.as_deref();
+                }
+            }
+        }
+        rank
+    }
+    
+    /// The entry with exactly `index` smaller keys, i.e. the `index`-th smallest (0-based).
+    pub fn select(&self, mut index: usize) -> Option<(&K, &V)> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            let left = size(&node.left);
+            match index.cmp(&left) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    index -= left + 1;
+                    current = node.right.as_deref();
+                }
+            }
+        }
+        None
+    }
+    
+    /// Entries in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+    
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+    
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+    
+    /// Entries with keys in `range`, in ascending order. Only the nodes on the
+    /// path to the start of the range and the entries yielded are visited.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R> {
+        let mut stack = Vec::new();
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            let above_start = match range.start_bound() {
+                Bound::Included(start) => node.key >= *start,
+                Bound::Excluded(start) => node.key > *start,
+                Bound::Unbounded => true,
+            };
+            if above_start {
+                stack.push(node);
+                current = node.left.as_deref();
+            } else {
+                current = node.right.as_deref();
+            }
+        }
+        Range {
+            iter: Iter { stack },
+            range,
+        }
+    }
+}
+
+/// Detach the minimum node of a subtree, returning it and what remains of the subtree.
+fn take_min<K, V>(mut root: Box<Node<K, V>>) -> (Box<Node<K, V>>, Link<K, V>) {
+    if root.left.is_none() {
+        let rest = root.right.take();
+        return (root, rest);
+    }
+    let mut parent = &mut root;
+    loop {
+        parent.size -= 1;
+        if parent.left.as_ref().is_some_and(|left| left.left.is_none()) {
+            break;
+        }
+        parent = parent.left.as_mut().unwrap();
+    }
+    let mut min = parent.left.take().unwrap();
+    parent.left = min.right.take();
+    (min, Some(root))
+}
+
+impl<K: Ord, V> Default for Bst<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for Bst<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut bst = Bst::new();
+        bst.extend(iter);
+        bst
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for Bst<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> Drop for Bst<K, V> {
+    /// Free nodes with an explicit stack; the default recursive drop could
+    /// overflow on a degenerate tree.
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<K, V>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for Bst<K, V> {
+    type Iter<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+    
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        Bst::insert(self, key, value)
+    }
+    
+    fn get(&self, key: &K) -> Option<&V> {
+        Bst::get(self, key)
+    }
+    
+    fn remove(&mut self, key: &K) -> Option<V> {
+        Bst::remove(self, key)
+    }
+    
+    fn len(&self) -> usize {
+        Bst::len(self)
+    }
+    
+    fn min(&self) -> Option<(&K, &V)> {
+        Bst::min(self)
+    }
+    
+    fn max(&self) -> Option<(&K, &V)> {
+        Bst::max(self)
+    }
+    
+    fn iter(&self) -> Iter<'_, K, V> {
+        Bst::iter(self)
+    }
+}
+
+impl<K: Debug, V> Visualize for Bst<K, V> {
+    /// Built post-order with an explicit stack, so a degenerate tree can be drawn too.
+    fn sketch(&self) -> Option<Sketch> {
+        // `false` means the node's children still have to be sketched
+        let mut pending = vec![(self.root.as_deref(), false)];
+        // Finished subtrees, the most recent (a right child) on top
+        let mut done: Vec<Option<Sketch>> = Vec::new();
+        while let Some((link, children_done)) = pending.pop() {
+            let Some(node) = link else {
+                done.push(None);
+                continue;
+            };
+            if children_done {
+                let right = done.pop().expect("right child was sketched");
+                let left = done.pop().expect("left child was sketched");
+                done.push(Some(Sketch::binary(format!("{:?}", node.key), left, right)));
+            } else {
+                pending.push((link, true));
+                pending.push((node.right.as_deref(), false));
+                pending.push((node.left.as_deref(), false));
+            }
+        }
+        done.pop().flatten()
+    }
+}
+
+/// In-order iterator over a `Bst`.
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left_spine(&mut self, mut node: Option<&'a Node<K, V>>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+/// Iterator over the entries of a `Bst` whose keys fall in a range.
+pub struct Range<'a, K, V, R> {
+    iter: Iter<'a, K, V>,
+    range: R,
+}
+
+impl<'a, K: Ord, V, R: RangeBounds<K>> Iterator for Range<'a, K, V, R> {
+    type Item = (&'a K, &'a V);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.next()?;
+        let below_end = match self.range.end_bound() {
+            Bound::Included(end) => key <= end,
+            Bound::Excluded(end) => key < end,
+            Bound::Unbounded => true,
+        };
+        if below_end {
+            Some((key, value))
+        } else {
+            self.iter.stack.clear();
+            None
+        }
+    }
+}
+
+/// Problem: Validate Binary Search Tree
+/// Whether an in-order walk of the tree is strictly increasing.
+pub fn is_valid_bst(root: &Option<Box<TreeNode>>) -> bool {
+    let mut previous = None;
+    for val in Inorder::new(root) {
+        if previous.is_some_and(|prev| prev >= val) {
+            return false;
+        }
+        previous = Some(val);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trees::create_tree;
+    use crate::trees::visualize::{dot, mermaid};
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+    
+    /// Check subtree sizes and key order throughout the tree.
+    fn check_invariants<K: Ord, V>(bst: &Bst<K, V>) {
+        fn walk<K: Ord, V>(node: &Link<K, V>, low: Option<&K>, high: Option<&K>) -> usize {
+            let Some(node) = node else { return 0 };
+            assert!(low.is_none_or(|low| *low < node.key), "key order violated");
+            assert!(high.is_none_or(|high| node.key < *high), "key order violated");
+            let size = walk(&node.left, low, Some(&node.key)) + walk(&node.right, Some(&node.key), high) + 1;
+            assert_eq!(node.size, size, "subtree size is stale");
+            size
+        }
+        walk(&bst.root, None, None);
+    }
+    
+    fn sample() -> Bst<i32, &'static str> {
+        [(50, "e"), (30, "c"), (70, "g"), (20, "b"), (40, "d"), (60, "f"), (80, "h"), (10, "a")]
+            .into_iter()
+            .collect()
+    }
+    
+    #[test]
+    fn test_insert_get_replace() {
+        let mut bst = sample();
+        assert_eq!(bst.len(), 8);
+        assert_eq!(bst.get(&40), Some(&"d"));
+        assert_eq!(bst.get(&45), None);
+        assert_eq!(bst.insert(40, "D"), Some("d"));
+        assert_eq!(bst.len(), 8);
+        *bst.get_mut(&10).unwrap() = "A";
+        assert_eq!(bst.values().copied().collect::<Vec<_>>(), vec!["A", "b", "c", "D", "e", "f", "g", "h"]);
+        check_invariants(&bst);
+    }
+    
+    #[test]
+    fn test_remove_each_shape() {
+        let mut bst = sample();
+        // Leaf, node with one child, node with two children, and the root
+        assert_eq!(bst.remove(&10), Some("a"));
+        assert_eq!(bst.remove(&20), Some("b"));
+        assert_eq!(bst.remove(&70), Some("g"));
+        assert_eq!(bst.remove(&50), Some("e"));
+        assert_eq!(bst.remove(&50), None);
+        check_invariants(&bst);
+        assert_eq!(bst.keys().copied().collect::<Vec<_>>(), vec![30, 40, 60, 80]);
+        // The successor of 50 took its place at the root
+        assert_eq!(bst.root.as_ref().map(|root| root.key), Some(60));
+    }
+    
+    #[test]
+    fn test_order_queries() {
+        let bst = sample();
+        assert_eq!(bst.min(), Some((&10, &"a")));
+        assert_eq!(bst.max(), Some((&80, &"h")));
+        assert_eq!(bst.floor(&45), Some((&40, &"d")));
+        assert_eq!(bst.floor(&40), Some((&40, &"d")));
+        assert_eq!(bst.floor(&5), None);
+        assert_eq!(bst.ceiling(&45), Some((&50, &"e")));
+        assert_eq!(bst.ceiling(&81), None);
+        assert_eq!(bst.rank(&10), 0);
+        assert_eq!(bst.rank(&45), 4);
+        assert_eq!(bst.rank(&100), 8);
+        assert_eq!(bst.select(0), Some((&10, &"a")));
+        assert_eq!(bst.select(4), Some((&50, &"e")));
+        assert_eq!(bst.select(8), None);
+        
+        fn keys<R: RangeBounds<i32>>(range: Range<'_, i32, &str, R>) -> Vec<i32> {
+            range.map(|(key, _)| *key).collect()
+        }
+        assert_eq!(keys(bst.range(25..60)), vec![30, 40, 50]);
+        assert_eq!(keys(bst.range(30..=60)), vec![30, 40, 50, 60]);
+        assert_eq!(keys(bst.range((Bound::Excluded(30), Bound::Unbounded))), vec![40, 50, 60, 70, 80]);
+        assert_eq!(keys(bst.range(..)), bst.keys().copied().collect::<Vec<_>>());
+        assert!(keys(bst.range(81..)).is_empty());
+    }
+    
+    #[test]
+    fn test_degenerate_tree() {
+        // Sorted input makes a list-shaped tree; nothing may recurse on its depth
+        let mut bst: Bst<u32, u32> = (0..10_000).map(|i| (i, i)).collect();
+        assert_eq!(bst.select(9_999), Some((&9_999, &9_999)));
+        assert_eq!(bst.rank(&5_000), 5_000);
+        assert_eq!(bst.remove(&0), Some(0));
+        assert_eq!(bst.len(), 9_999);
+    }
+    
+    #[test]
+    fn test_visualize_degenerate_tree() {
+        let bst: Bst<u32, ()> = (0..10_000).map(|i| (i, ())).collect();
+        let sketch = bst.sketch().unwrap();
+        assert_eq!((sketch.label.as_str(), sketch.children.len()), ("0", 2));
+        assert!(sketch.children[0].is_none());
+        assert_eq!(dot(&bst).matches(" -> ").count(), 2 * 9_999);
+        assert_eq!(mermaid(&bst).matches("-->|right|").count(), 9_999);
+    }
+    
+    #[test]
+    fn test_is_valid_bst() {
+        assert!(is_valid_bst(&create_tree(&[Some(2), Some(1), Some(3)])));
+        assert!(is_valid_bst(&None));
+        assert!(!is_valid_bst(&create_tree(&[Some(5), Some(1), Some(4), None, None, Some(3), Some(6)])));
+        // 3 sits in 5's right subtree two levels down, so only a global check catches it
+        assert!(!is_valid_bst(&create_tree(&[Some(5), Some(4), Some(6), None, None, Some(3), Some(7)])));
+        // Duplicates are not allowed
+        assert!(!is_valid_bst(&create_tree(&[Some(2), Some(2), Some(2)])));
+        assert!(is_valid_bst(&create_tree(&[Some(i32::MAX), Some(i32::MIN)])));
+    }
+    
+    #[derive(Debug, Clone)]
+    enum Op {
+        Insert(i16, u32),
+        Remove(i16),
+    }
+    
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            3 => (-50i16..50, any::<u32>()).prop_map(|(k, v)| Op::Insert(k, v)),
+            2 => (-50i16..50).prop_map(Op::Remove),
+        ]
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_btreemap(ops in proptest::collection::vec(op(), 0..200), probes in proptest::collection::vec(-60i16..60, 1..10)) {
+            let mut bst = Bst::new();
+            let mut model = BTreeMap::new();
+            for op in ops {
+                match op {
+                    Op::Insert(k, v) => prop_assert_eq!(bst.insert(k, v), model.insert(k, v)),
+                    Op::Remove(k) => prop_assert_eq!(bst.remove(&k), model.remove(&k)),
+                }
+            }
+            check_invariants(&bst);
+            prop_assert_eq!(bst.len(), model.len());
+            prop_assert!(bst.iter().eq(model.iter()));
+            prop_assert_eq!(bst.min(), model.iter().next());
+            prop_assert_eq!(bst.max(), model.iter().next_back());
+            
+            for &probe in &probes {
+                prop_assert_eq!(bst.get(&probe), model.get(&probe));
+                prop_assert_eq!(bst.floor(&probe), model.range(..=probe).next_back());
+                prop_assert_eq!(bst.ceiling(&probe), model.range(probe..).next());
+                prop_assert_eq!(bst.rank(&probe), model.range(..probe).count());
+                prop_assert_eq!(bst.select(probe.unsigned_abs() as usize), model.iter().nth(probe.unsigned_abs() as usize));
+                prop_assert!(bst.range(probe..probe.saturating_add(15)).eq(model.range(probe..probe.saturating_add(15))));
+                if probe < 40 {
+                    let bounds = (Bound::Excluded(probe), Bound::Included(40));
+                    prop_assert!(bst.range(bounds).eq(model.range(bounds)));
+                }
+            }
+        }
+    }
+}
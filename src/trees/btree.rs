@@ -0,0 +1,618 @@
+//! B-Tree
+//! 
+//! A B-tree of order `ORDER`: every node holds at most `ORDER - 1` keys and
+//! `ORDER` children, every node but the root holds at least
+//! `ceil(ORDER / 2) - 1` keys, and all leaves sit at the same depth.
+//! - insert, splitting full nodes on the way back up
+//! - remove, refilling underfull nodes by borrowing from a sibling or merging with one
+//! - search, ascending range scans and bulk loading from sorted input
+//! - `dump` prints the tree level by level, for teaching
+//! - agreement with `std::collections::BTreeMap` at several orders, timed in
+//!   `benches/ordered_maps.rs`
+
+use super::ordered_map::{run_workloads, OrderedMap};
+use super::visualize::{Sketch, Visualize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Bound, RangeBounds, RangeFull};
+
+struct Node<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    /// Empty for a leaf; otherwise one more child than there are keys.
+    children: Vec<Node<K, V>>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new() -> Self {
+        Node {
+            keys: Vec::new(),
+            values: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+    
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+    
+    /// The node's keys as `[k1 k2 ...]`.
+    fn label(&self) -> String
+    where
+        K: fmt::Debug,
+    {
+        let keys: Vec<String> = self.keys.iter().map(|key| format!("{:?}", key)).collect();
+        format!("[{}]", keys.join(" "))
+    }
+}
+
+/// A new right sibling produced by splitting a node, with the median entry that separates them.
+type Split<K, V> = (K, V, Node<K, V>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkLoadError {
+    /// The key at `index` is not greater than the key before it.
+    Unsorted { index: usize },
+}
+
+impl fmt::Display for BulkLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BulkLoadError::Unsorted { index } => {
+                write!(f, "key at index {} is not greater than the previous key", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BulkLoadError {}
+
+/// Problem: B-Tree
+pub struct BTree<K, V, const ORDER: usize> {
+    root: Option<Box<Node<K, V>>>,
+    len: usize,
+}
+
+impl<K: Ord, V, const ORDER: usize> BTree<K, V, ORDER> {
+    const MIN_KEYS: usize = ORDER.div_ceil(2) - 1;
+    
+    pub fn new() -> Self {
+        const { assert!(ORDER >= 3, "a B-tree needs ORDER >= 3") };
+        BTree { root: None, len: 0 }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Number of levels; 0 when empty.
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            height += 1;
+            current = node.children.first();
+        }
+        height
+    }
+    
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut node = self.root.as_deref()?;
+        loop {
+            match node.keys.binary_search(key) {
+                Ok(i) => return Some(&node.values[i]),
+                Err(i) => node = node.children.get(i)?,
+            }
+        }
+    }
+    
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+    
+    /// Insert or replace, returning the previous value for `key`.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let root = self.root.get_or_insert_with(|| Box::new(Node::new()));
+        let (old, split) = Self::insert_into(root, key, value);
+        if let Some((median_key, median_value, right)) = split {
+            // The root split: grow the tree by one level
+            let left = *self.root.take().expect("root exists");
+            self.root = Some(Box::new(Node {
+                keys: vec![median_key],
+                values: vec![median_value],
+                children: vec![left, right],
+            }));
+        }
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+    
+    fn insert_into(node: &mut Node<K, V>, key: K, value: V) -> (Option<V>, Option<Split<K, V>>) {
+        let i = match node.keys.binary_search(&key) {
+            Ok(i) => return (Some(std::mem::replace(&mut node.values[i], value)), None),
+            Err(i) => i,
+        };
+        if node.is_leaf() {
+            node.keys.insert(i, key);
+            node.values.insert(i, value);
+        } else {
+            match Self::insert_into(&mut node.children[i], key, value) {
+                (None, Some((median_key, median_value, right))) => {
+                    node.keys.insert(i, median_key);
+                    node.values.insert(i, median_value);
+                    node.children.insert(i + 1, right);
+                }
+                done => return done,
+            }
+        }
+        if node.keys.len() < ORDER {
+            return (None, None);
+        }
+        
+        // Overfull by one: move the upper half into a new sibling and pass the median up
+        let mid = ORDER / 2;
+        let right = Node {
+            keys: node.keys.split_off(mid + 1),
+            values: node.values.split_off(mid + 1),
+            children: if node.is_leaf() { Vec::new() } else { node.children.split_off(mid + 1) },
+        };
+        let median_key = node.keys.pop().expect("node is overfull");
+        let median_value = node.values.pop().expect("node is overfull");
+        (None, Some((median_key, median_value, right)))
+    }
+    
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let root = self.root.as_mut()?;
+        let removed = Self::remove_from(root, key)?;
+        if root.keys.is_empty() {
+            // The root lost its last key: shrink the tree by one level
+            self.root = root.children.pop().map(Box::new);
+        }
+        self.len -= 1;
+        Some(removed)
+    }
+    
+    fn remove_from(node: &mut Node<K, V>, key: &K) -> Option<V> {
+        match node.keys.binary_search(key) {
+            Ok(i) if node.is_leaf() => {
+                node.keys.remove(i);
+                Some(node.values.remove(i))
+            }
+            Ok(i) => {
+                // Swap in the predecessor, the largest entry of the left subtree
+                let (key, value) = Self::remove_max(&mut node.children[i]);
+                node.keys[i] = key;
+                let removed = std::mem::replace(&mut node.values[i], value);
+                Self::refill(node, i);
+                Some(removed)
+            }
+            Err(_) if node.is_leaf() => None,
+            Err(i) => {
+                let removed = Self::remove_from(&mut node.children[i], key)?;
+                Self::refill(node, i);
+                Some(removed)
+            }
+        }
+    }
+    
+    fn remove_max(node: &mut Node<K, V>) -> (K, V) {
+        if node.is_leaf() {
+            let key = node.keys.pop().expect("B-tree nodes are never empty");
+            let value = node.values.pop().expect("B-tree nodes are never empty");
+            return (key, value);
+        }
+        let last = node.children.len() - 1;
+        let max = Self::remove_max(&mut node.children[last]);
+        Self::refill(node, last);
+        max
+    }
+    
+    /// Bring `node.children[i]` back up to the minimum number of keys if it fell short,
+    /// by borrowing through the parent from a sibling that can spare a key, or else
+    /// by merging it with a sibling and the separator between them.
+    fn refill(node: &mut Node<K, V>, i: usize) {
+        if node.children[i].keys.len() >= Self::MIN_KEYS {
+            return;
+        }
+        if i > 0 && node.children[i - 1].keys.len() > Self::MIN_KEYS {
+            let (before, after) = node.children.split_at_mut(i);
+            let (left, child) = (&mut before[i - 1], &mut after[0]);
+            let key = std::mem::replace(&mut node.keys[i - 1], left.keys.pop().unwrap());
+            let value = std::mem::replace(&mut node.values[i - 1], left.values.pop().unwrap());
+            child.keys.insert(0, key);
+            child.values.insert(0, value);
+            if let Some(grandchild) = left.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+        } else if i + 1 < node.children.len() && node.children[i + 1].keys.len() > Self::MIN_KEYS {
+            let (before, after) = node.children.split_at_mut(i + 1);
+            let (child, right) = (&mut before[i], &mut after[0]);
+            let key = std::mem::replace(&mut node.keys[i], right.keys.remove(0));
+            let value = std::mem::replace(&mut node.values[i], right.values.remove(0));
+            child.keys.push(key);
+            child.values.push(value);
+            if !right.children.is_empty() {
+                child.children.push(right.children.remove(0));
+            }
+        } else {
+            // Merge with the left sibling if there is one, otherwise with the right
+            let at = if i > 0 { i - 1 } else { i };
+            let right = node.children.remove(at + 1);
+            let left = &mut node.children[at];
+            left.keys.push(node.keys.remove(at));
+            left.values.push(node.values.remove(at));
+            let Node { keys, values, children } = right;
+            left.keys.extend(keys);
+            left.values.extend(values);
+            left.children.extend(children);
+        }
+    }
+    
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(child) = node.children.first() {
+            node = child;
+        }
+        Some((node.keys.first()?, node.values.first()?))
+    }
+    
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(child) = node.children.last() {
+            node = child;
+        }
+        Some((node.keys.last()?, node.values.last()?))
+    }
+    
+    /// Entries with keys in `range`, in ascending order.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R> {
+        let mut stack = Vec::new();
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            // Index of the first key at or after the start of the range
+            let i = match range.start_bound() {
+                Bound::Included(start) => node.keys.partition_point(|key| key < start),
+                Bound::Excluded(start) => node.keys.partition_point(|key| key <= start),
+                Bound::Unbounded => 0,
+            };
+            stack.push((node, i));
+            current = node.children.get(i);
+        }
+        Range { stack, range }
+    }
+    
+    /// Entries in ascending key order.
+    pub fn iter(&self) -> Range<'_, K, V, RangeFull> {
+        self.range(..)
+    }
+    
+    /// Build a tree from entries in strictly increasing key order in O(n),
+    /// packing each level as evenly as the order allows.
+    pub fn from_sorted(entries: impl IntoIterator<Item = (K, V)>) -> Result<Self, BulkLoadError> {
+        let mut entries: Vec<(K, V)> = entries.into_iter().collect();
+        if let Some(index) = (1..entries.len()).find(|&i| entries[i - 1].0 >= entries[i].0) {
+            return Err(BulkLoadError::Unsorted { index });
+        }
+        let len = entries.len();
+        if len == 0 {
+            return Ok(Self::new());
+        }
+        
+        // Split this level's entries into nodes with one separator between neighbors;
+        // the separators become the entries of the level above.
+        let mut children: Vec<Node<K, V>> = Vec::new();
+        loop {
+            let nodes = (entries.len() + 1).div_ceil(ORDER);
+            let keys_in_nodes = entries.len() - (nodes - 1);
+            let mut entries_iter = entries.into_iter();
+            let mut children_iter = children.into_iter();
+            let mut level = Vec::with_capacity(nodes);
+            let mut separators = Vec::with_capacity(nodes - 1);
+            for n in 0..nodes {
+                let count = keys_in_nodes / nodes + usize::from(n < keys_in_nodes % nodes);
+                let mut node = Node::new();
+                for (key, value) in entries_iter.by_ref().take(count) {
+                    node.keys.push(key);
+                    node.values.push(value);
+                }
+                node.children.extend(children_iter.by_ref().take(count + 1));
+                level.push(node);
+                separators.extend(entries_iter.next());
+            }
+            if level.len() == 1 {
+                return Ok(BTree { root: level.pop().map(Box::new), len });
+            }
+            entries = separators;
+            children = level;
+        }
+    }
+    
+    /// The tree drawn level by level, one line per level, e.g.
+    /// `[20]` over `[10] [30 40]`.
+    pub fn dump(&self) -> String
+    where
+        K: fmt::Debug,
+    {
+        let mut lines = Vec::new();
+        let mut level: Vec<&Node<K, V>> = self.root.as_deref().into_iter().collect();
+        while !level.is_empty() {
+            let rendered: Vec<String> = level.iter().map(|node| node.label()).collect();
+            lines.push(rendered.join(" "));
+            level = level.iter().flat_map(|node| node.children.iter()).collect();
+        }
+        lines.join("\n")
+    }
+    
+    /// Panic if the tree breaks a B-tree invariant: key counts within bounds,
+    /// one more child than keys in internal nodes, all leaves at one depth, and
+    /// keys strictly increasing in order. A no-op in release builds.
+    pub fn check_invariants(&self) {
+        /// Returns the depth of the subtree's leaves and its number of entries.
+        fn walk<K: Ord, V>(node: &Node<K, V>, is_root: bool, order: usize, low: Option<&K>, high: Option<&K>) -> (usize, usize) {
+            let min_keys = if is_root { 1 } else { order.div_ceil(2) - 1 };
+            assert!(node.keys.len() >= min_keys && node.keys.len() < order, "key count out of bounds");
+            assert_eq!(node.keys.len(), node.values.len(), "keys and values out of step");
+            assert!(node.keys.windows(2).all(|pair| pair[0] < pair[1]), "keys out of order");
+            assert!(low.is_none_or(|low| *low < node.keys[0]), "keys out of order");
+            assert!(high.is_none_or(|high| node.keys[node.keys.len() - 1] < *high), "keys out of order");
+            if node.is_leaf() {
+                return (1, node.keys.len());
+            }
+            assert_eq!(node.children.len(), node.keys.len() + 1, "wrong number of children");
+            let mut depth = None;
+            let mut count = node.keys.len();
+            for (i, child) in node.children.iter().enumerate() {
+                let low = if i == 0 { low } else { Some(&node.keys[i - 1]) };
+                let high = node.keys.get(i).or(high);
+                let (child_depth, child_count) = walk(child, false, order, low, high);
+                assert!(depth.is_none_or(|depth| depth == child_depth), "leaves at different depths");
+                depth = Some(child_depth);
+                count += child_count;
+            }
+            (depth.unwrap() + 1, count)
+        }
+        if cfg!(debug_assertions) {
+            let count = self.root.as_ref().map_or(0, |root| walk(root, true, ORDER, None, None).1);
+            assert_eq!(count, self.len, "stale length");
+        }
+    }
+}
+
+impl<K: Ord, V, const ORDER: usize> Default for BTree<K, V, ORDER> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V, const ORDER: usize> FromIterator<(K, V)> for BTree<K, V, ORDER> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+impl<K: Ord, V, const ORDER: usize> OrderedMap<K, V> for BTree<K, V, ORDER> {
+    type Iter<'a>
+        = Range<'a, K, V, RangeFull>
+    where
+        K: 'a,
+        V: 'a;
+    
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTree::insert(self, key, value)
+    }
+    
+    fn get(&self, key: &K) -> Option<&V> {
+        BTree::get(self, key)
+    }
+    
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BTree::remove(self, key)
+    }
+    
+    fn len(&self) -> usize {
+        BTree::len(self)
+    }
+    
+    fn min(&self) -> Option<(&K, &V)> {
+        BTree::min(self)
+    }
+    
+    fn max(&self) -> Option<(&K, &V)> {
+        BTree::max(self)
+    }
+    
+    fn iter(&self) -> Range<'_, K, V, RangeFull> {
+        BTree::iter(self)
+    }
+}
+
+impl<K: fmt::Debug, V, const ORDER: usize> Visualize for BTree<K, V, ORDER> {
+    fn sketch(&self) -> Option<Sketch> {
+        fn sketch<K: fmt::Debug, V>(node: &Node<K, V>) -> Sketch {
+            Sketch { label: node.label(), children: node.children.iter().map(|child| Some(sketch(child))).collect() }
+        }
+        self.root.as_deref().map(sketch)
+    }
+}
+
+/// Ascending iterator over the entries of a `BTree` whose keys fall in a range.
+pub struct Range<'a, K, V, R> {
+    /// Path from the root: each node with the index of its next key to yield.
+    /// For an internal node, the child at that index is being visited first.
+    stack: Vec<(&'a Node<K, V>, usize)>,
+    range: R,
+}
+
+impl<'a, K: Ord, V, R: RangeBounds<K>> Iterator for Range<'a, K, V, R> {
+    type Item = (&'a K, &'a V);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, i) = *self.stack.last()?;
+            if i == node.keys.len() {
+                self.stack.pop();
+                continue;
+            }
+            self.stack.last_mut().unwrap().1 += 1;
+            // Everything right of this key comes next: the leftmost path of the following child
+            let mut child = node.children.get(i + 1);
+            while let Some(current) = child {
+                self.stack.push((current, 0));
+                child = current.children.first();
+            }
+            
+            let key = &node.keys[i];
+            let in_range = match self.range.end_bound() {
+                Bound::Included(end) => key <= end,
+                Bound::Excluded(end) => key < end,
+                Bound::Unbounded => true,
+            };
+            if !in_range {
+                self.stack.clear();
+                return None;
+            }
+            return Some((key, &node.values[i]));
+        }
+    }
+}
+
+/// Problem: B-Tree Agreement
+/// Run the shared ordered-map workloads on `std::collections::BTreeMap` and on
+/// `BTree` at several orders, then bulk load; all must agree. Timings live in
+/// `benches/ordered_maps.rs`.
+pub fn btree_agrees(n: usize) -> bool {
+    let results = [
+        run_workloads(n, BTreeMap::new),
+        run_workloads(n, BTree::<u64, u64, 3>::new),
+        run_workloads(n, BTree::<u64, u64, 6>::new),
+        run_workloads(n, BTree::<u64, u64, 16>::new),
+        run_workloads(n, BTree::<u64, u64, 64>::new),
+    ];
+    let bulk = BTree::<u64, u64, 16>::from_sorted((0..n as u64).map(|key| (key, key)));
+    let bulk_ok = bulk.is_ok_and(|tree| tree.len() == n && tree.iter().map(|(key, _)| *key).eq(0..n as u64));
+    bulk_ok && results.iter().all(|&checksum| checksum == results[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_insert_splits() {
+        let mut tree: BTree<u32, (), 3> = BTree::new();
+        for key in [10, 20, 30] {
+            tree.insert(key, ());
+            tree.check_invariants();
+        }
+        assert_eq!(tree.dump(), "[20]\n[10] [30]");
+        for key in [40, 50, 5, 15, 25] {
+            tree.insert(key, ());
+            tree.check_invariants();
+        }
+        assert_eq!(tree.dump(), "[20]\n[10] [40]\n[5] [15] [25 30] [50]");
+        assert_eq!(tree.height(), 3);
+        assert_eq!(tree.insert(25, ()), Some(()));
+        assert_eq!(tree.len(), 8);
+    }
+    
+    #[test]
+    fn test_remove_borrows_and_merges() {
+        let mut tree: BTree<u32, u32, 3> = (1..=15).map(|key| (key, key * 10)).collect();
+        for key in [8, 1, 15, 4, 12, 2, 3, 9] {
+            assert_eq!(tree.remove(&key), Some(key * 10));
+            assert_eq!(tree.remove(&key), None);
+            tree.check_invariants();
+        }
+        assert!(tree.keys_eq([5, 6, 7, 10, 11, 13, 14]));
+        for key in [5, 6, 7, 10, 11, 13, 14] {
+            tree.remove(&key);
+            tree.check_invariants();
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.height(), 0);
+        assert_eq!(tree.dump(), "");
+    }
+    
+    #[test]
+    fn test_range() {
+        let tree: BTree<i32, i32, 4> = (0..100).map(|key| (key * 2, key)).collect();
+        let keys = |range: (Bound<i32>, Bound<i32>)| tree.range(range).map(|(key, _)| *key).collect::<Vec<_>>();
+        assert_eq!(keys((Bound::Included(10), Bound::Excluded(18))), [10, 12, 14, 16]);
+        assert_eq!(keys((Bound::Excluded(9), Bound::Included(17))), [10, 12, 14, 16]);
+        assert_eq!(keys((Bound::Excluded(10), Bound::Included(10))), Vec::<i32>::new());
+        assert_eq!(keys((Bound::Included(195), Bound::Unbounded)), [196, 198]);
+        assert_eq!(tree.range(..).count(), 100);
+        assert_eq!(tree.min(), Some((&0, &0)));
+        assert_eq!(tree.max(), Some((&198, &99)));
+    }
+    
+    #[test]
+    fn test_from_sorted() {
+        fn check<const ORDER: usize>() {
+            for n in 0..200u32 {
+                let tree = BTree::<u32, u32, ORDER>::from_sorted((0..n).map(|key| (key, key + 1))).unwrap();
+                tree.check_invariants();
+                assert_eq!(tree.len(), n as usize);
+                assert!(tree.iter().map(|(key, value)| (*key, *value)).eq((0..n).map(|key| (key, key + 1))));
+            }
+        }
+        check::<3>();
+        check::<4>();
+        check::<5>();
+        check::<8>();
+        
+        let tree = BTree::<u32, (), 3>::from_sorted((1..=7).map(|key| (key, ()))).unwrap();
+        assert_eq!(tree.dump(), "[3 6]\n[1 2] [4 5] [7]");
+        let unsorted = BTree::<u32, (), 3>::from_sorted([(1, ()), (3, ()), (3, ())]);
+        assert_eq!(unsorted.err(), Some(BulkLoadError::Unsorted { index: 2 }));
+    }
+    
+    #[test]
+    fn test_benchmark_agrees() {
+        assert!(btree_agrees(2_000));
+    }
+    
+    impl<const ORDER: usize> BTree<u32, u32, ORDER> {
+        fn keys_eq<const N: usize>(&self, keys: [u32; N]) -> bool {
+            self.iter().map(|(key, _)| *key).eq(keys)
+        }
+    }
+    
+    fn check_against_btreemap<const ORDER: usize>(ops: &[(bool, i16, u32)]) -> Result<(), TestCaseError> {
+        let mut tree: BTree<i16, u32, ORDER> = BTree::new();
+        let mut reference = BTreeMap::new();
+        for &(insert, key, value) in ops {
+            if insert {
+                prop_assert_eq!(tree.insert(key, value), reference.insert(key, value));
+            } else {
+                prop_assert_eq!(tree.remove(&key), reference.remove(&key));
+            }
+            tree.check_invariants();
+            prop_assert_eq!(tree.get(&key), reference.get(&key));
+        }
+        prop_assert!(tree.iter().eq(reference.iter()));
+        prop_assert!(tree.range(-10..20).eq(reference.range(-10..20)));
+        Ok(())
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_btreemap(ops in proptest::collection::vec((any::<bool>(), -64i16..64, any::<u32>()), 0..300)) {
+            check_against_btreemap::<3>(&ops)?;
+            check_against_btreemap::<4>(&ops)?;
+            check_against_btreemap::<5>(&ops)?;
+            check_against_btreemap::<8>(&ops)?;
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Level-Order Tree Codec
+//! 
+//! Read and write binary trees in the LeetCode level-order format, e.g.
+//! `[1,null,2,3]`: nodes listed breadth-first, `null` marking a missing child,
+//! trailing `null`s omitted.
+//! - `serialize` renders a tree as that string
+//! - `deserialize` parses it back, reporting malformed input instead of panicking
+
+use super::{create_tree, tree_to_level_order, TreeNode};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTreeError {
+    /// The input is not wrapped in `[` and `]`.
+    MissingBrackets,
+    /// The token at `index` is neither an `i32` nor `null`.
+    InvalidToken { index: usize, token: String },
+    /// The value at `index` has no parent left to attach to.
+    OrphanValue { index: usize },
+}
+
+impl fmt::Display for ParseTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseTreeError::MissingBrackets => write!(f, "expected a list wrapped in '[' and ']'"),
+            ParseTreeError::InvalidToken { index, token } => {
+                write!(f, "entry {} ({:?}) is not an integer or null", index, token)
+            }
+            ParseTreeError::OrphanValue { index } => write!(f, "entry {} has no parent node", index),
+        }
+    }
+}
+
+impl std::error::Error for ParseTreeError {}
+
+/// Problem: Serialize Binary Tree
+pub fn serialize(root: &Option<Box<TreeNode>>) -> String {
+    let entries: Vec<String> = tree_to_level_order(root)
+        .into_iter()
+        .map(|entry| entry.map_or_else(|| "null".to_string(), |val| val.to_string()))
+        .collect();
+    format!("[{}]", entries.join(","))
+}
+
+/// Problem: Deserialize Binary Tree
+pub fn deserialize(data: &str) -> Result<Option<Box<TreeNode>>, ParseTreeError> {
+    let inner = data
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or(ParseTreeError::MissingBrackets)?;
+    if inner.trim().is_empty() {
+        return Ok(None);
+    }
+    
+    let mut values = Vec::new();
+    for (index, token) in inner.split(',').map(str::trim).enumerate() {
+        let value = match token {
+            "null" => None,
+            _ => Some(token.parse::<i32>().map_err(|_| ParseTreeError::InvalidToken {
+                index,
+                token: token.to_string(),
+            })?),
+        };
+        values.push(value);
+    }
+    
+    // Entry 0 is the root; every present node then owns the next two slots
+    let mut slots = 1;
+    for (index, value) in values.iter().enumerate() {
+        if index >= slots {
+            if value.is_some() {
+                return Err(ParseTreeError::OrphanValue { index });
+            }
+        } else if value.is_some() {
+            slots += 2;
+        }
+    }
+    Ok(create_tree(&values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_round_trip() {
+        for data in ["[]", "[1]", "[1,null,2,3]", "[5,4,8,11,null,13,4,7,2,null,null,5,1]", "[-3,null,-2147483648]"] {
+            let tree = deserialize(data).unwrap();
+            assert_eq!(serialize(&tree), data);
+        }
+    }
+    
+    #[test]
+    fn test_lenient_whitespace_and_trailing_nulls() {
+        let tree = deserialize(" [ 1 , 2 , null , null , null ] ").unwrap();
+        assert_eq!(serialize(&tree), "[1,2]");
+        assert_eq!(deserialize("[null]"), Ok(None));
+    }
+    
+    #[test]
+    fn test_errors() {
+        assert_eq!(deserialize("1,2"), Err(ParseTreeError::MissingBrackets));
+        assert_eq!(
+            deserialize("[1,x]"),
+            Err(ParseTreeError::InvalidToken { index: 1, token: "x".to_string() })
+        );
+        assert_eq!(deserialize("[1,,2]").unwrap_err().to_string(), "entry 1 (\"\") is not an integer or null");
+        // The root's two children are null, so entry 3 has nowhere to go
+        assert_eq!(deserialize("[1,null,null,4]"), Err(ParseTreeError::OrphanValue { index: 3 }));
+        assert_eq!(deserialize("[null,1]"), Err(ParseTreeError::OrphanValue { index: 1 }));
+    }
+}
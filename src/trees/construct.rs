@@ -0,0 +1,270 @@
+//! Tree Construction
+//! 
+//! Rebuild binary trees from traversal dumps, and build height-balanced BSTs
+//! from sorted input. Inconsistent input is reported as a `BuildTreeError`
+//! rather than a panic.
+//! - preorder + inorder and postorder + inorder (values must be distinct)
+//! - preorder of a BST alone
+//! - sorted array or sorted linked list to a height-balanced BST
+
+use super::TreeNode;
+use crate::linked_lists::ListNode;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildTreeError {
+    /// The two traversals have different lengths.
+    LengthMismatch { traversal: usize, inorder: usize },
+    /// `value` occurs more than once, so its position is ambiguous.
+    DuplicateValue { value: i32 },
+    /// `value` appears in the traversal but not in the inorder sequence.
+    MissingFromInorder { value: i32 },
+    /// `value` must root a subtree whose inorder span does not contain it.
+    Inconsistent { value: i32 },
+    /// The value at `index` cannot follow the ones before it in a BST preorder.
+    NotBstPreorder { index: usize },
+    /// The value at `index` is not greater than the one before it.
+    Unsorted { index: usize },
+}
+
+impl fmt::Display for BuildTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildTreeError::LengthMismatch { traversal, inorder } => {
+                write!(f, "traversal has {} values but inorder has {}", traversal, inorder)
+            }
+            BuildTreeError::DuplicateValue { value } => write!(f, "value {} occurs more than once", value),
+            BuildTreeError::MissingFromInorder { value } => write!(f, "value {} is missing from the inorder sequence", value),
+            BuildTreeError::Inconsistent { value } => {
+                write!(f, "value {} is out of place in the inorder sequence", value)
+            }
+            BuildTreeError::NotBstPreorder { index } => {
+                write!(f, "value at index {} breaks the BST ordering of the preorder sequence", index)
+            }
+            BuildTreeError::Unsorted { index } => {
+                write!(f, "value at index {} is not greater than the previous value", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildTreeError {}
+
+/// Map each inorder value to its position, rejecting repeats and a length mismatch.
+fn index_inorder(traversal: &[i32], inorder: &[i32]) -> Result<HashMap<i32, usize>, BuildTreeError> {
+    if traversal.len() != inorder.len() {
+        return Err(BuildTreeError::LengthMismatch { traversal: traversal.len(), inorder: inorder.len() });
+    }
+    let mut positions = HashMap::with_capacity(inorder.len());
+    for (i, &value) in inorder.iter().enumerate() {
+        if positions.insert(value, i).is_some() {
+            return Err(BuildTreeError::DuplicateValue { value });
+        }
+    }
+    Ok(positions)
+}
+
+/// Build the subtree spanning `inorder[low..high]`, taking its root from `roots`.
+/// Preorder yields roots before left subtrees; reversed postorder yields them
+/// before right subtrees, which `right_first` accounts for.
+fn build_span(
+    roots: &mut impl Iterator<Item = i32>,
+    positions: &HashMap<i32, usize>,
+    low: usize,
+    high: usize,
+    right_first: bool,
+) -> Result<Option<Box<TreeNode>>, BuildTreeError> {
+    if low == high {
+        return Ok(None);
+    }
+    // Lengths match and every span is non-empty here, so a root is always left
+    let value = roots.next().expect("traversal and inorder have equal lengths");
+    let &mid = positions.get(&value).ok_or(BuildTreeError::MissingFromInorder { value })?;
+    if !(low..high).contains(&mid) {
+        return Err(BuildTreeError::Inconsistent { value });
+    }
+    let mut node = Box::new(TreeNode::new(value));
+    if right_first {
+        node.right = build_span(roots, positions, mid + 1, high, right_first)?;
+        node.left = build_span(roots, positions, low, mid, right_first)?;
+    } else {
+        node.left = build_span(roots, positions, low, mid, right_first)?;
+        node.right = build_span(roots, positions, mid + 1, high, right_first)?;
+    }
+    Ok(Some(node))
+}
+
+/// Problem: Construct Binary Tree from Preorder and Inorder Traversal
+pub fn build_tree_from_preorder_inorder(preorder: &[i32], inorder: &[i32]) -> Result<Option<Box<TreeNode>>, BuildTreeError> {
+    let positions = index_inorder(preorder, inorder)?;
+    build_span(&mut preorder.iter().copied(), &positions, 0, inorder.len(), false)
+}
+
+/// Problem: Construct Binary Tree from Inorder and Postorder Traversal
+pub fn build_tree_from_postorder_inorder(postorder: &[i32], inorder: &[i32]) -> Result<Option<Box<TreeNode>>, BuildTreeError> {
+    let positions = index_inorder(postorder, inorder)?;
+    build_span(&mut postorder.iter().rev().copied(), &positions, 0, inorder.len(), true)
+}
+
+/// Problem: Construct Binary Search Tree from Preorder Traversal
+/// Each value either fits under the node just placed or climbs back up to an
+/// ancestor's right side, so tracking the open bounds builds the tree in O(n).
+pub fn bst_from_preorder(preorder: &[i32]) -> Result<Option<Box<TreeNode>>, BuildTreeError> {
+    /// Build the subtree of values strictly between `low` and `high` from the front of `rest`.
+    fn build(rest: &mut &[i32], low: i64, high: i64) -> Option<Box<TreeNode>> {
+        let (&value, tail) = rest.split_first()?;
+        if (value as i64) <= low || (value as i64) >= high {
+            return None;
+        }
+        *rest = tail;
+        let mut node = Box::new(TreeNode::new(value));
+        node.left = build(rest, low, value as i64);
+        node.right = build(rest, value as i64, high);
+        Some(node)
+    }
+    let mut rest = preorder;
+    let root = build(&mut rest, i64::MIN, i64::MAX);
+    if !rest.is_empty() {
+        return Err(BuildTreeError::NotBstPreorder { index: preorder.len() - rest.len() });
+    }
+    Ok(root)
+}
+
+fn check_sorted(values: &[i32]) -> Result<(), BuildTreeError> {
+    match (1..values.len()).find(|&i| values[i - 1] >= values[i]) {
+        Some(index) => Err(BuildTreeError::Unsorted { index }),
+        None => Ok(()),
+    }
+}
+
+/// Problem: Convert Sorted Array to Binary Search Tree
+/// The middle value becomes the root, so the two halves differ in size by at most one.
+pub fn sorted_array_to_bst(values: &[i32]) -> Result<Option<Box<TreeNode>>, BuildTreeError> {
+    fn build(values: &[i32]) -> Option<Box<TreeNode>> {
+        if values.is_empty() {
+            return None;
+        }
+        let mid = values.len() / 2;
+        let mut node = Box::new(TreeNode::new(values[mid]));
+        node.left = build(&values[..mid]);
+        node.right = build(&values[mid + 1..]);
+        Some(node)
+    }
+    check_sorted(values)?;
+    Ok(build(values))
+}
+
+/// Problem: Convert Sorted List to Binary Search Tree
+/// After one pass to validate and count, builds the tree in inorder, consuming
+/// list values as it goes, so the list is never indexed.
+pub fn sorted_list_to_bst(head: &Option<Box<ListNode>>) -> Result<Option<Box<TreeNode>>, BuildTreeError> {
+    /// Build a balanced tree of the next `len` values.
+    fn build(values: &mut impl Iterator<Item = i32>, len: usize) -> Option<Box<TreeNode>> {
+        if len == 0 {
+            return None;
+        }
+        let left = build(values, len / 2);
+        let mut node = Box::new(TreeNode::new(values.next().expect("list has len values")));
+        node.left = left;
+        node.right = build(values, len - len / 2 - 1);
+        Some(node)
+    }
+    let values = || std::iter::successors(head.as_deref(), |node| node.next.as_deref()).map(|node| node.val);
+    let mut len = 0;
+    let mut previous = None;
+    for (index, value) in values().enumerate() {
+        if previous.is_some_and(|previous| previous >= value) {
+            return Err(BuildTreeError::Unsorted { index });
+        }
+        previous = Some(value);
+        len += 1;
+    }
+    Ok(build(&mut values(), len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linked_lists::create_list;
+    use crate::trees::bst::is_valid_bst;
+    use crate::trees::traversal::{Inorder, Postorder, Preorder};
+    use crate::trees::{create_tree, is_balanced, tree_to_level_order};
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_from_traversals() {
+        let expected = create_tree(&[Some(3), Some(9), Some(20), None, None, Some(15), Some(7)]);
+        assert_eq!(build_tree_from_preorder_inorder(&[3, 9, 20, 15, 7], &[9, 3, 15, 20, 7]), Ok(expected));
+        let expected = create_tree(&[Some(3), Some(9), Some(20), None, None, Some(15), Some(7)]);
+        assert_eq!(build_tree_from_postorder_inorder(&[9, 15, 7, 20, 3], &[9, 3, 15, 20, 7]), Ok(expected));
+        assert_eq!(build_tree_from_preorder_inorder(&[], &[]), Ok(None));
+        assert_eq!(build_tree_from_postorder_inorder(&[], &[]), Ok(None));
+    }
+    
+    #[test]
+    fn test_inconsistent_traversals() {
+        assert_eq!(
+            build_tree_from_preorder_inorder(&[1, 2], &[1]),
+            Err(BuildTreeError::LengthMismatch { traversal: 2, inorder: 1 })
+        );
+        assert_eq!(build_tree_from_preorder_inorder(&[1, 1], &[1, 1]), Err(BuildTreeError::DuplicateValue { value: 1 }));
+        assert_eq!(build_tree_from_preorder_inorder(&[1, 5], &[1, 2]), Err(BuildTreeError::MissingFromInorder { value: 5 }));
+        // 1 comes after the root 3 in inorder, so it cannot root 3's left subtree
+        assert_eq!(build_tree_from_preorder_inorder(&[3, 1, 2], &[2, 3, 1]), Err(BuildTreeError::Inconsistent { value: 1 }));
+        assert_eq!(build_tree_from_postorder_inorder(&[1, 2, 3], &[2, 3, 1]), Err(BuildTreeError::Inconsistent { value: 2 }));
+        assert_eq!(build_tree_from_postorder_inorder(&[2, 2, 3], &[2, 3, 1]), Err(BuildTreeError::Inconsistent { value: 2 }));
+        let message = BuildTreeError::MissingFromInorder { value: 5 }.to_string();
+        assert_eq!(message, "value 5 is missing from the inorder sequence");
+    }
+    
+    #[test]
+    fn test_bst_from_preorder() {
+        let expected = create_tree(&[Some(8), Some(5), Some(10), Some(1), Some(7), None, Some(12)]);
+        assert_eq!(bst_from_preorder(&[8, 5, 1, 7, 10, 12]), Ok(expected));
+        assert_eq!(bst_from_preorder(&[]), Ok(None));
+        assert_eq!(bst_from_preorder(&[i32::MIN, i32::MAX]).map(|tree| tree_to_level_order(&tree)), Ok(vec![Some(i32::MIN), None, Some(i32::MAX)]));
+        // 6 would have to sit left of 8 but after 10 has closed that side
+        assert_eq!(bst_from_preorder(&[8, 5, 10, 6]), Err(BuildTreeError::NotBstPreorder { index: 3 }));
+        assert_eq!(bst_from_preorder(&[2, 2]), Err(BuildTreeError::NotBstPreorder { index: 1 }));
+    }
+    
+    #[test]
+    fn test_sorted_to_balanced_bst() {
+        for n in 0..64 {
+            let values: Vec<i32> = (0..n).map(|v| v * 3 - 20).collect();
+            let from_array = sorted_array_to_bst(&values).unwrap();
+            let from_list = sorted_list_to_bst(&create_list(&values)).unwrap();
+            for tree in [&from_array, &from_list] {
+                assert!(is_valid_bst(tree) && is_balanced(tree));
+                assert!(Inorder::new(tree).eq(values.iter().copied()));
+            }
+        }
+        assert_eq!(sorted_array_to_bst(&[1, 3, 3]), Err(BuildTreeError::Unsorted { index: 2 }));
+        assert_eq!(sorted_list_to_bst(&create_list(&[4, 1])), Err(BuildTreeError::Unsorted { index: 1 }));
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_traversals_round_trip(shape in proptest::collection::vec(any::<bool>(), 0..80)) {
+            // Distinct values in a random shape
+            let values: Vec<Option<i32>> = shape.iter().enumerate().map(|(i, &present)| present.then_some(i as i32)).collect();
+            let tree = create_tree(&values);
+            let preorder: Vec<i32> = Preorder::new(&tree).collect();
+            let inorder: Vec<i32> = Inorder::new(&tree).collect();
+            let postorder: Vec<i32> = Postorder::new(&tree).collect();
+            prop_assert_eq!(&build_tree_from_preorder_inorder(&preorder, &inorder), &Ok(tree));
+            let tree = create_tree(&values);
+            prop_assert_eq!(build_tree_from_postorder_inorder(&postorder, &inorder), Ok(tree));
+        }
+        
+        #[test]
+        fn prop_bst_preorder_round_trip(mut keys in proptest::collection::vec(any::<i32>(), 0..80)) {
+            keys.sort_unstable();
+            keys.dedup();
+            let tree = sorted_array_to_bst(&keys).unwrap();
+            let preorder: Vec<i32> = Preorder::new(&tree).collect();
+            prop_assert_eq!(bst_from_preorder(&preorder), Ok(tree));
+        }
+    }
+}
@@ -0,0 +1,332 @@
+//! Fenwick Trees (Binary Indexed Trees)
+//! 
+//! Prefix sums with point updates in O(log n), in a flat array where entry
+//! `i` (1-based) covers the `i & -i` values ending at `i`.
+//! - `FenwickTree<T>`: point add, prefix and range sums, and `lower_bound`
+//!   (first index whose prefix sum reaches a target)
+//! - `RangeFenwickTree`: range add and range sum, using two trees
+//! - `FenwickTree2D<T>`: point add and rectangle sums over a grid
+
+use super::segment_tree::to_range;
+use std::ops::{AddAssign, Range, RangeBounds, Sub};
+
+/// Values a Fenwick tree can sum: `Default` is zero, and `-` undoes `+=`.
+pub trait FenwickValue: Copy + Default + AddAssign + Sub<Output = Self> {}
+
+impl<T: Copy + Default + AddAssign + Sub<Output = T>> FenwickValue for T {}
+
+/// Lowest set bit of `i`: how many values entry `i` covers.
+fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+/// Problem: Fenwick Tree (Binary Indexed Tree)
+pub struct FenwickTree<T> {
+    /// 1-based: `tree[0]` is unused.
+    tree: Vec<T>,
+}
+
+impl<T: FenwickValue> FenwickTree<T> {
+    /// `len` zeros.
+    pub fn new(len: usize) -> Self {
+        FenwickTree { tree: vec![T::default(); len + 1] }
+    }
+    
+    /// Build over `values` in O(n) by pushing each entry's total into its parent.
+    pub fn from_slice(values: &[T]) -> Self {
+        let mut tree = vec![T::default(); values.len() + 1];
+        tree[1..].copy_from_slice(values);
+        for i in 1..tree.len() {
+            let parent = i + lowbit(i);
+            if parent < tree.len() {
+                let value = tree[i];
+                tree[parent] += value;
+            }
+        }
+        FenwickTree { tree }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.tree.len() - 1
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Add `delta` to the value at `index`.
+    pub fn add(&mut self, index: usize, delta: T) {
+        assert!(index < self.len(), "index {} out of bounds for length {}", index, self.len());
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += lowbit(i);
+        }
+    }
+    
+    /// Sum of the first `end` values.
+    pub fn prefix_sum(&self, end: usize) -> T {
+        assert!(end <= self.len(), "prefix end {} out of bounds for length {}", end, self.len());
+        let mut sum = T::default();
+        let mut i = end;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= lowbit(i);
+        }
+        sum
+    }
+    
+    pub fn range_sum(&self, range: impl RangeBounds<usize>) -> T {
+        let Range { start, end } = to_range(range, self.len());
+        self.prefix_sum(end) - self.prefix_sum(start)
+    }
+    
+    /// Smallest `index` such that `prefix_sum(index + 1) >= target`, or `None`
+    /// if even the whole sum falls short. Binary-lifts down the implicit tree
+    /// in O(log n); only meaningful when no value is negative.
+    pub fn lower_bound(&self, mut target: T) -> Option<usize>
+    where
+        T: PartialOrd,
+    {
+        // Largest `position` whose prefix sum is still below the target
+        let mut position = 0;
+        let mut step = self.tree.len().next_power_of_two() / 2;
+        while step > 0 {
+            let next = position + step;
+            if next < self.tree.len() && self.tree[next] < target {
+                position = next;
+                target = target - self.tree[next];
+            }
+            step /= 2;
+        }
+        (position < self.len()).then_some(position)
+    }
+}
+
+/// Problem: Range Update and Range Query (Fenwick Tree)
+/// With `b1` holding range-add deltas and `b2` a correction term, the prefix
+/// sum of the first `end` values is `prefix(b1, end) * end - prefix(b2, end)`.
+pub struct RangeFenwickTree {
+    b1: FenwickTree<i64>,
+    b2: FenwickTree<i64>,
+}
+
+impl RangeFenwickTree {
+    pub fn new(len: usize) -> Self {
+        // One spare slot so a range ending at `len` has somewhere to cancel its delta
+        RangeFenwickTree {
+            b1: FenwickTree::new(len + 1),
+            b2: FenwickTree::new(len + 1),
+        }
+    }
+    
+    pub fn from_slice(values: &[i64]) -> Self {
+        let mut tree = Self::new(values.len());
+        for (i, &value) in values.iter().enumerate() {
+            tree.add(i..=i, value);
+        }
+        tree
+    }
+    
+    pub fn len(&self) -> usize {
+        self.b1.len() - 1
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Add `delta` to every value in `range`.
+    pub fn add(&mut self, range: impl RangeBounds<usize>, delta: i64) {
+        let Range { start, end } = to_range(range, self.len());
+        if start == end {
+            return;
+        }
+        self.b1.add(start, delta);
+        self.b1.add(end, -delta);
+        self.b2.add(start, delta * start as i64);
+        self.b2.add(end, -delta * end as i64);
+    }
+    
+    fn prefix_sum(&self, end: usize) -> i64 {
+        self.b1.prefix_sum(end) * end as i64 - self.b2.prefix_sum(end)
+    }
+    
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> i64 {
+        let Range { start, end } = to_range(range, self.len());
+        self.prefix_sum(end) - self.prefix_sum(start)
+    }
+}
+
+/// Problem: Range Sum Query 2D - Mutable
+pub struct FenwickTree2D<T> {
+    /// 1-based in both dimensions.
+    tree: Vec<Vec<T>>,
+}
+
+impl<T: FenwickValue> FenwickTree2D<T> {
+    /// A `rows` by `cols` grid of zeros.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        FenwickTree2D { tree: vec![vec![T::default(); cols + 1]; rows + 1] }
+    }
+    
+    pub fn from_grid(grid: &[Vec<T>]) -> Self {
+        let cols = grid.first().map_or(0, Vec::len);
+        let mut tree = Self::new(grid.len(), cols);
+        for (row, values) in grid.iter().enumerate() {
+            assert_eq!(values.len(), cols, "grid rows must all have the same length");
+            for (col, &value) in values.iter().enumerate() {
+                tree.add(row, col, value);
+            }
+        }
+        tree
+    }
+    
+    pub fn rows(&self) -> usize {
+        self.tree.len() - 1
+    }
+    
+    pub fn cols(&self) -> usize {
+        self.tree[0].len() - 1
+    }
+    
+    pub fn add(&mut self, row: usize, col: usize, delta: T) {
+        assert!(row < self.rows() && col < self.cols(), "cell ({}, {}) out of bounds", row, col);
+        let mut i = row + 1;
+        while i < self.tree.len() {
+            let mut j = col + 1;
+            while j < self.tree[i].len() {
+                self.tree[i][j] += delta;
+                j += lowbit(j);
+            }
+            i += lowbit(i);
+        }
+    }
+    
+    /// Sum of the cells in the first `rows` rows and first `cols` columns.
+    pub fn prefix_sum(&self, rows: usize, cols: usize) -> T {
+        let mut sum = T::default();
+        let mut i = rows;
+        while i > 0 {
+            let mut j = cols;
+            while j > 0 {
+                sum += self.tree[i][j];
+                j -= lowbit(j);
+            }
+            i -= lowbit(i);
+        }
+        sum
+    }
+    
+    /// Sum of the rectangle `rows` by `cols`, by inclusion-exclusion over four prefixes.
+    pub fn sum(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> T {
+        let rows = to_range(rows, self.rows());
+        let cols = to_range(cols, self.cols());
+        let mut sum = self.prefix_sum(rows.end, cols.end);
+        sum += self.prefix_sum(rows.start, cols.start);
+        sum - self.prefix_sum(rows.start, cols.end) - self.prefix_sum(rows.end, cols.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_prefix_sums() {
+        let mut tree = FenwickTree::from_slice(&[3, 1, 4, 1, 5, 9, 2, 6]);
+        assert_eq!((tree.prefix_sum(0), tree.prefix_sum(3), tree.prefix_sum(8)), (0, 8, 31));
+        assert_eq!(tree.range_sum(2..5), 10);
+        tree.add(4, -5);
+        assert_eq!((tree.range_sum(2..=4), tree.range_sum(..)), (5, 26));
+        assert_eq!(FenwickTree::<i32>::new(0).range_sum(..), 0);
+    }
+    
+    #[test]
+    fn test_lower_bound() {
+        let tree = FenwickTree::from_slice(&[2u32, 0, 3, 1, 0, 4]);
+        // Prefix sums: 2, 2, 5, 6, 6, 10
+        assert_eq!(tree.lower_bound(0), Some(0));
+        assert_eq!(tree.lower_bound(2), Some(0));
+        assert_eq!(tree.lower_bound(3), Some(2));
+        assert_eq!(tree.lower_bound(6), Some(3));
+        assert_eq!(tree.lower_bound(7), Some(5));
+        assert_eq!(tree.lower_bound(11), None);
+        assert_eq!(FenwickTree::<u32>::new(0).lower_bound(0), None);
+    }
+    
+    #[test]
+    fn test_range_updates() {
+        let mut tree = RangeFenwickTree::from_slice(&[1, 2, 3, 4, 5]);
+        tree.add(1..4, 10);
+        tree.add(..=4, -1);
+        // [0, 11, 12, 13, 4]
+        assert_eq!((tree.sum(..), tree.sum(1..3), tree.sum(4..)), (40, 23, 4));
+        tree.add(2..2, 100);
+        assert_eq!(tree.sum(..), 40);
+    }
+    
+    #[test]
+    fn test_2d() {
+        let grid = vec![vec![3, 0, 1, 4, 2], vec![5, 6, 3, 2, 1], vec![1, 2, 0, 1, 5], vec![4, 1, 0, 1, 7], vec![1, 0, 3, 0, 5]];
+        let mut tree = FenwickTree2D::from_grid(&grid);
+        assert_eq!(tree.sum(2..=4, 1..=3), 8);
+        tree.add(3, 2, 2);
+        assert_eq!(tree.sum(2..=4, 1..=3), 10);
+        assert_eq!(tree.sum(.., ..), 60);
+        assert_eq!(tree.sum(1..1, ..), 0);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_brute_force(
+            mut values in proptest::collection::vec(0..100i64, 1..60),
+            ops in proptest::collection::vec((0..60usize, 0..60usize, -50..50i64), 0..100),
+        ) {
+            let n = values.len();
+            let mut point = FenwickTree::from_slice(&values);
+            let mut ranged = RangeFenwickTree::from_slice(&values);
+            for (a, b, delta) in ops {
+                let (low, high) = ((a % n).min(b % n), (a % n).max(b % n) + 1);
+                // Point add keeps values non-negative so lower_bound stays meaningful
+                let delta = delta.max(-values[low]);
+                values[low] += delta;
+                point.add(low, delta);
+                ranged.add(low..=low, delta);
+                prop_assert_eq!(point.range_sum(low..high), values[low..high].iter().sum::<i64>());
+                prop_assert_eq!(ranged.sum(low..high), values[low..high].iter().sum::<i64>());
+                let target = values[..high].iter().sum::<i64>();
+                let expected = (0..n).find(|&i| values[..=i].iter().sum::<i64>() >= target);
+                prop_assert_eq!(point.lower_bound(target), expected);
+            }
+        }
+        
+        #[test]
+        fn prop_range_add_matches_brute_force(ops in proptest::collection::vec((0..40usize, 0..41usize, -50..50i64), 0..100)) {
+            let mut values = vec![0i64; 40];
+            let mut tree = RangeFenwickTree::new(40);
+            for (a, b, delta) in ops {
+                let (low, high) = (a.min(b), a.max(b));
+                values[low..high].iter_mut().for_each(|value| *value += delta);
+                tree.add(low..high, delta);
+                prop_assert_eq!(tree.sum(..), values.iter().sum::<i64>());
+                prop_assert_eq!(tree.sum(low..), values[low..].iter().sum::<i64>());
+            }
+        }
+        
+        #[test]
+        fn prop_2d_matches_brute_force(cells in proptest::collection::vec((0..6usize, 0..7usize, -9..9i32), 0..40), r in (0..7usize, 0..7usize), c in (0..8usize, 0..8usize)) {
+            let mut grid = vec![vec![0; 7]; 6];
+            let mut tree = FenwickTree2D::new(6, 7);
+            for (row, col, delta) in cells {
+                grid[row][col] += delta;
+                tree.add(row, col, delta);
+            }
+            let rows = r.0.min(r.1).min(6)..r.0.max(r.1).min(6);
+            let cols = c.0.min(c.1).min(7)..c.0.max(c.1).min(7);
+            let expected: i32 = grid[rows.clone()].iter().map(|row| row[cols.clone()].iter().sum::<i32>()).sum();
+            prop_assert_eq!(tree.sum(rows, cols), expected);
+        }
+    }
+}
@@ -1,13 +1,52 @@
-//! Tree Problems Module (Coming Soon)
+//! Tree Problems Module
 //! 
-//! This module will contain common tree problems and their solutions:
-//! - Binary Tree Traversal (Inorder, Preorder, Postorder)
-//! - Binary Search Tree operations
-//! - AVL Tree implementation
-//! - B-Tree operations
-//! - Tree problems (Path Sum, Diameter, etc.)
+//! This module contains common tree problems and their solutions:
+//! - Building trees from level-order arrays and serializing them back
+//! - Rebuilding trees from traversal sequences, and balanced BSTs from sorted input
+//! - Binary Tree Traversal (Preorder, Inorder, Postorder, Level Order):
+//!   recursive, iterative and Morris
+//! - Binary Search Tree as an ordered map (floor/ceiling, rank/select, ranges) and BST validation
+//! - AVL Tree with split/join and invariant checking
+//! - Left-leaning red-black tree and treap, all behind a common `OrderedMap` trait
+//! - Segment trees: monoid range queries, lazy range updates, and a persistent
+//!   variant for k-th smallest in a subarray
+//! - Fenwick trees: prefix sums, range updates, 2D grids and `lower_bound`
+//! - B-Tree with bulk loading and a level-by-level dump, benchmarked across orders
+//!   in `benches/ordered_maps.rs`
+//! - Classic problems: depth, diameter, balance, symmetry, inversion,
+//!   path sums, lowest common ancestor and level/vertical views
+//! - Visualization of any of these trees as ASCII art, Graphviz DOT or Mermaid
+
+pub mod avl;
+pub mod bst;
+pub mod btree;
+pub mod codec;
+pub mod construct;
+pub mod fenwick;
+pub mod ordered_map;
+pub mod persistent_segment_tree;
+pub mod red_black;
+pub mod segment_tree;
+pub mod traversal;
+pub mod treap;
+pub mod visualize;
 
 use crate::{measure_time, assert_result};
+use crate::linked_lists::create_list;
+use avl::AvlTree;
+use bst::{is_valid_bst, Bst};
+use btree::{btree_agrees, BTree};
+use codec::{deserialize, serialize};
+use construct::{bst_from_preorder, build_tree_from_postorder_inorder, build_tree_from_preorder_inorder, sorted_array_to_bst, sorted_list_to_bst};
+use fenwick::{FenwickTree, FenwickTree2D, RangeFenwickTree};
+use ordered_map::{ordered_maps_agree, OrderedMap};
+use persistent_segment_tree::KthSmallest;
+use red_black::LlrbTree;
+use segment_tree::{LazySegmentTree, Max, Min, SegmentTree, Sum};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use traversal::{inorder_recursive, levels, Inorder, LevelOrder, MorrisInorder, Postorder, Preorder};
+use treap::Treap;
+use visualize::{ascii, dot, mermaid};
 
 /// Definition for a binary tree node
 #[derive(Debug, PartialEq, Eq)]
@@ -28,18 +67,480 @@ impl TreeNode {
     }
 }
 
-/// Placeholder function for tree problems
+/// Helper function to create a tree from LeetCode-style level-order values,
+/// where `None` marks a missing child. Values left over once every node's
+/// children have been assigned are ignored.
+pub fn create_tree(values: &[Option<i32>]) -> Option<Box<TreeNode>> {
+    let mut root = Box::new(TreeNode::new(values.first().copied().flatten()?));
+    let mut queue: VecDeque<&mut TreeNode> = VecDeque::new();
+    queue.push_back(&mut root);
+    let mut rest = values[1..].iter();
+    
+    while let Some(node) = queue.pop_front() {
+        let Some(&left) = rest.next() else { break };
+        let right = rest.next().copied().flatten();
+        node.left = left.map(|val| Box::new(TreeNode::new(val)));
+        node.right = right.map(|val| Box::new(TreeNode::new(val)));
+        queue.extend(node.left.as_deref_mut());
+        queue.extend(node.right.as_deref_mut());
+    }
+    
+    Some(root)
+}
+
+/// Helper function to convert a tree back to level-order values, without trailing `None`s
+pub fn tree_to_level_order(root: &Option<Box<TreeNode>>) -> Vec<Option<i32>> {
+    let mut result = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(root.as_deref());
+    
+    while let Some(entry) = queue.pop_front() {
+        result.push(entry.map(|node| node.val));
+        if let Some(node) = entry {
+            queue.push_back(node.left.as_deref());
+            queue.push_back(node.right.as_deref());
+        }
+    }
+    
+    while result.last() == Some(&None) {
+        result.pop();
+    }
+    result
+}
+
+/// Problem: Maximum Depth of Binary Tree
+/// Count the nodes on the longest root-to-leaf path.
+pub fn max_depth(root: &Option<Box<TreeNode>>) -> i32 {
+    match root {
+        None => 0,
+        Some(node) => 1 + max_depth(&node.left).max(max_depth(&node.right)),
+    }
+}
+
+/// Problem: Minimum Depth of Binary Tree
+/// Count the nodes on the shortest root-to-leaf path.
+/// A node with one child is not a leaf, so its empty side does not count.
+pub fn min_depth(root: &Option<Box<TreeNode>>) -> i32 {
+    let Some(node) = root else { return 0 };
+    match (&node.left, &node.right) {
+        (None, None) => 1,
+        (Some(_), None) => 1 + min_depth(&node.left),
+        (None, Some(_)) => 1 + min_depth(&node.right),
+        (Some(_), Some(_)) => 1 + min_depth(&node.left).min(min_depth(&node.right)),
+    }
+}
+
+/// Problem: Diameter of Binary Tree
+/// Find the number of edges on the longest path between any two nodes.
+pub fn diameter_of_binary_tree(root: &Option<Box<TreeNode>>) -> i32 {
+    /// Returns the depth of the subtree, updating `best` with the longest path through it.
+    fn depth(node: &Option<Box<TreeNode>>, best: &mut i32) -> i32 {
+        let Some(node) = node else { return 0 };
+        let left = depth(&node.left, best);
+        let right = depth(&node.right, best);
+        *best = (*best).max(left + right);
+        1 + left.max(right)
+    }
+    let mut best = 0;
+    depth(root, &mut best);
+    best
+}
+
+/// Problem: Balanced Binary Tree
+/// Check that the depths of every node's two subtrees differ by at most one.
+pub fn is_balanced(root: &Option<Box<TreeNode>>) -> bool {
+    /// Depth of a balanced subtree, or `None` as soon as any subtree is unbalanced.
+    fn depth(node: &Option<Box<TreeNode>>) -> Option<i32> {
+        let Some(node) = node else { return Some(0) };
+        let left = depth(&node.left)?;
+        let right = depth(&node.right)?;
+        ((left - right).abs() <= 1).then_some(1 + left.max(right))
+    }
+    depth(root).is_some()
+}
+
+/// Problem: Symmetric Tree
+/// Check whether a tree is a mirror image of itself.
+pub fn is_symmetric(root: &Option<Box<TreeNode>>) -> bool {
+    fn mirrors(a: &Option<Box<TreeNode>>, b: &Option<Box<TreeNode>>) -> bool {
+        match (a, b) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.val == b.val && mirrors(&a.left, &b.right) && mirrors(&a.right, &b.left),
+            _ => false,
+        }
+    }
+    root.as_ref().is_none_or(|node| mirrors(&node.left, &node.right))
+}
+
+/// Problem: Invert Binary Tree
+/// Swap the left and right children of every node.
+pub fn invert_tree(root: Option<Box<TreeNode>>) -> Option<Box<TreeNode>> {
+    root.map(|mut node| {
+        let left = node.left.take();
+        node.left = invert_tree(node.right.take());
+        node.right = invert_tree(left);
+        node
+    })
+}
+
+/// Problem: Path Sum
+/// Check whether some root-to-leaf path adds up to `target_sum`.
+pub fn has_path_sum(root: &Option<Box<TreeNode>>, target_sum: i32) -> bool {
+    fn walk(node: &Option<Box<TreeNode>>, remaining: i64) -> bool {
+        let Some(node) = node else { return false };
+        let remaining = remaining - node.val as i64;
+        if node.left.is_none() && node.right.is_none() {
+            return remaining == 0;
+        }
+        walk(&node.left, remaining) || walk(&node.right, remaining)
+    }
+    walk(root, target_sum as i64)
+}
+
+/// Problem: Path Sum II
+/// Find every root-to-leaf path that adds up to `target_sum`, left to right.
+pub fn path_sum_paths(root: &Option<Box<TreeNode>>, target_sum: i32) -> Vec<Vec<i32>> {
+    fn walk(node: &Option<Box<TreeNode>>, remaining: i64, path: &mut Vec<i32>, result: &mut Vec<Vec<i32>>) {
+        let Some(node) = node else { return };
+        path.push(node.val);
+        let remaining = remaining - node.val as i64;
+        if node.left.is_none() && node.right.is_none() {
+            if remaining == 0 {
+                result.push(path.clone());
+            }
+        } else {
+            walk(&node.left, remaining, path, result);
+            walk(&node.right, remaining, path, result);
+        }
+        path.pop();
+    }
+    let mut result = Vec::new();
+    walk(root, target_sum as i64, &mut Vec::new(), &mut result);
+    result
+}
+
+/// Problem: Path Sum III
+/// Count the downward paths (starting and ending anywhere) that add up to `target_sum`.
+/// Prefix sums along the current root path make this O(n): a path ending here sums to
+/// the target exactly when some earlier prefix equals `current - target_sum`.
+pub fn path_sum_count(root: &Option<Box<TreeNode>>, target_sum: i32) -> usize {
+    fn walk(node: &Option<Box<TreeNode>>, sum: i64, target: i64, prefixes: &mut HashMap<i64, usize>) -> usize {
+        let Some(node) = node else { return 0 };
+        let sum = sum + node.val as i64;
+        let mut count = prefixes.get(&(sum - target)).copied().unwrap_or(0);
+        *prefixes.entry(sum).or_insert(0) += 1;
+        count += walk(&node.left, sum, target, prefixes);
+        count += walk(&node.right, sum, target, prefixes);
+        *prefixes.get_mut(&sum).expect("inserted above") -= 1;
+        count
+    }
+    let mut prefixes = HashMap::from([(0, 1)]);
+    walk(root, 0, target_sum as i64, &mut prefixes)
+}
+
+/// Problem: Binary Tree Maximum Path Sum
+/// Find the largest sum of any non-empty path between two nodes.
+/// Returns `None` for an empty tree. The sum is an `i64`, since a path of
+/// `i32` values can add up to more than `i32::MAX`.
+pub fn max_path_sum(root: &Option<Box<TreeNode>>) -> Option<i64> {
+    /// Returns the best sum of a path going down from this node (dropping negative
+    /// branches), updating `best` with the best path that turns at this node.
+    fn gain(node: &Option<Box<TreeNode>>, best: &mut i64) -> i64 {
+        let Some(node) = node else { return 0 };
+        let left = gain(&node.left, best).max(0);
+        let right = gain(&node.right, best).max(0);
+        let val = node.val as i64;
+        *best = (*best).max(val + left + right);
+        val + left.max(right)
+    }
+    root.as_ref()?;
+    let mut best = i64::MIN;
+    gain(root, &mut best);
+    Some(best)
+}
+
+/// Problem: Lowest Common Ancestor of a Binary Tree
+/// Find the deepest node that has both `p` and `q` as descendants (a node counts
+/// as its own descendant). Returns `None` unless both values are in the tree.
+pub fn lowest_common_ancestor(root: &Option<Box<TreeNode>>, p: i32, q: i32) -> Option<i32> {
+    /// Returns how many of `p` and `q` the subtree contains, recording the
+    /// first (deepest) node whose subtree contains both.
+    fn walk(node: &Option<Box<TreeNode>>, p: i32, q: i32, found: &mut Option<i32>) -> usize {
+        let Some(node) = node else { return 0 };
+        let here = usize::from(node.val == p) + usize::from(node.val == q);
+        let count = here + walk(&node.left, p, q, found) + walk(&node.right, p, q, found);
+        if count >= 2 && found.is_none() {
+            *found = Some(node.val);
+        }
+        count
+    }
+    let mut found = None;
+    walk(root, p, q, &mut found);
+    found
+}
+
+/// Problem: Lowest Common Ancestor of a Binary Search Tree
+/// In a BST the ancestor is the first node on the search path where `p` and `q`
+/// go different ways, so no full traversal is needed. Returns `None` unless both
+/// values are in the tree.
+pub fn lowest_common_ancestor_bst(root: &Option<Box<TreeNode>>, p: i32, q: i32) -> Option<i32> {
+    fn contains(mut node: Option<&TreeNode>, val: i32) -> bool {
+        while let Some(current) = node {
+            node = match val.cmp(&current.val) {
+                std::cmp::Ordering::Less => current.left.as_deref(),
+                std::cmp::Ordering::Greater => current.right.as_deref(),
+                std::cmp::Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+    let (low, high) = (p.min(q), p.max(q));
+    let mut node = root.as_deref()?;
+    loop {
+        if high < node.val {
+            node = node.left.as_deref()?;
+        } else if low > node.val {
+            node = node.right.as_deref()?;
+        } else {
+            return (contains(Some(node), low) && contains(Some(node), high)).then_some(node.val);
+        }
+    }
+}
+
+/// Problem: Binary Tree Right Side View
+/// List the last node of each level, as seen from the right.
+pub fn right_side_view(root: &Option<Box<TreeNode>>) -> Vec<i32> {
+    levels(root).iter().filter_map(|level| level.last().copied()).collect()
+}
+
+/// Problem: Binary Tree Zigzag Level Order Traversal
+/// Level order, alternating left-to-right and right-to-left, starting left-to-right.
+pub fn zigzag_level_order(root: &Option<Box<TreeNode>>) -> Vec<Vec<i32>> {
+    let mut result = levels(root);
+    for level in result.iter_mut().skip(1).step_by(2) {
+        level.reverse();
+    }
+    result
+}
+
+/// Problem: Vertical Order Traversal of a Binary Tree
+/// Group nodes by column (the root is column 0, left children one column lower),
+/// columns left to right. Within a column nodes go top to bottom, and nodes that
+/// share both row and column go in ascending value order.
+pub fn vertical_order(root: &Option<Box<TreeNode>>) -> Vec<Vec<i32>> {
+    let mut columns: BTreeMap<i32, Vec<(usize, i32)>> = BTreeMap::new();
+    let mut queue = VecDeque::new();
+    queue.extend(root.as_deref().map(|node| (node, 0, 0)));
+    while let Some((node, row, column)) = queue.pop_front() {
+        columns.entry(column).or_default().push((row, node.val));
+        queue.extend(node.left.as_deref().map(|left| (left, row + 1, column - 1)));
+        queue.extend(node.right.as_deref().map(|right| (right, row + 1, column + 1)));
+    }
+    columns
+        .into_values()
+        .map(|mut column| {
+            column.sort_unstable();
+            column.into_iter().map(|(_, val)| val).collect()
+        })
+        .collect()
+}
+
 pub fn run_examples() {
-    println!("🌳 Tree Problems Module - Coming Soon!");
-    println!("======================================");
-    println!("This module will include:");
-    println!("• Binary Tree Traversal");
-    println!("• Binary Search Tree operations");
-    println!("• AVL Tree implementation");
-    println!("• B-Tree operations");
-    println!("• Tree problems (Path Sum, Diameter, etc.)");
-    println!();
-    println!("Stay tuned for comprehensive tree implementations!");
+    println!("Running Tree Problem Examples...");
+    
+    // Build from level order and serialize back
+    let values = [Some(5), Some(3), Some(8), None, Some(4), Some(6), None, None, None, None, Some(7)];
+    let root = measure_time("Create Tree", || create_tree(&values));
+    let serialized = serialize(&root);
+    println!("Serialize Tree: {}", serialized);
+    assert_result(serialized.as_str(), "[5,3,8,null,4,6,null,null,null,null,7]", "Serialize Tree");
+    let parsed = deserialize(&serialized);
+    assert_result(parsed.as_ref().map(tree_to_level_order), Ok(tree_to_level_order(&root)), "Deserialize Tree");
+    assert_result(deserialize("[1,null,null,4]").is_err(), true, "Deserialize Rejects Orphan");
+    
+    // Traversals
+    let preorder: Vec<i32> = Preorder::new(&root).collect();
+    let inorder: Vec<i32> = measure_time("Inorder Traversal (iterative)", || Inorder::new(&root).collect());
+    let postorder: Vec<i32> = Postorder::new(&root).collect();
+    let level_order: Vec<i32> = LevelOrder::new(&root).collect();
+    println!("Preorder: {:?}", preorder);
+    println!("Inorder: {:?}", inorder);
+    println!("Postorder: {:?}", postorder);
+    println!("Level Order: {:?} (levels {:?})", level_order, levels(&root));
+    assert_result(preorder, vec![5, 3, 4, 8, 6, 7], "Preorder Traversal");
+    assert_result(inorder.clone(), vec![3, 4, 5, 6, 7, 8], "Inorder Traversal");
+    assert_result(postorder, vec![4, 3, 7, 6, 8, 5], "Postorder Traversal");
+    assert_result(level_order, vec![5, 3, 8, 4, 6, 7], "Level Order Traversal");
+    assert_result(inorder_recursive(&root).collect::<Vec<_>>(), inorder.clone(), "Inorder Traversal (recursive)");
+    let morris: Vec<i32> = measure_time("Inorder Traversal (Morris)", || MorrisInorder::new(root).collect());
+    assert_result(morris, inorder, "Inorder Traversal (Morris)");
+    
+    // Reconstruction from traversals
+    let rebuilt = measure_time("Build Tree (preorder + inorder)", || build_tree_from_preorder_inorder(&[3, 9, 20, 15, 7], &[9, 3, 15, 20, 7]));
+    println!("Preorder [3,9,20,15,7] + Inorder [9,3,15,20,7] -> {:?}", rebuilt.as_ref().map(serialize));
+    assert_result(rebuilt.as_ref().map(serialize), Ok("[3,9,20,null,null,15,7]".to_string()), "Build Tree (preorder + inorder)");
+    let rebuilt = build_tree_from_postorder_inorder(&[9, 15, 7, 20, 3], &[9, 3, 15, 20, 7]);
+    assert_result(rebuilt.as_ref().map(serialize), Ok("[3,9,20,null,null,15,7]".to_string()), "Build Tree (postorder + inorder)");
+    let broken = build_tree_from_preorder_inorder(&[3, 1, 2], &[2, 3, 1]);
+    println!("Preorder [3,1,2] + Inorder [2,3,1] -> {}", broken.as_ref().map_or_else(|e| e.to_string(), serialize));
+    assert_result(broken.is_err(), true, "Build Tree Rejects Inconsistent Input");
+    let from_preorder = bst_from_preorder(&[8, 5, 1, 7, 10, 12]);
+    println!("BST from Preorder [8,5,1,7,10,12] -> {:?}", from_preorder.as_ref().map(serialize));
+    assert_result(from_preorder.as_ref().map(serialize), Ok("[8,5,10,1,7,null,12]".to_string()), "BST from Preorder");
+    let sorted: Vec<i32> = (1..=7).collect();
+    let from_array = sorted_array_to_bst(&sorted);
+    let from_list = sorted_list_to_bst(&create_list(&sorted));
+    println!("Sorted [1..=7] to BST: array -> {:?}, list -> {:?}", from_array.as_ref().map(serialize), from_list.as_ref().map(serialize));
+    assert_result(from_array.as_ref().map(serialize), Ok("[4,2,6,1,3,5,7]".to_string()), "Sorted Array to BST");
+    assert_result(from_list.as_ref().map(serialize), Ok("[4,2,6,1,3,5,7]".to_string()), "Sorted List to BST");
+    
+    // Binary Search Tree
+    let bst: Bst<i32, &str> = measure_time("BST Build", || {
+        [(50, "e"), (30, "c"), (70, "g"), (20, "b"), (40, "d"), (60, "f"), (80, "h")].into_iter().collect()
+    });
+    let window: Vec<i32> = bst.range(35..=65).map(|(key, _)| *key).collect();
+    println!("BST: floor(45) = {:?}, ceiling(45) = {:?}, rank(45) = {}, select(2) = {:?}, range 35..=65 = {:?}",
+        bst.floor(&45), bst.ceiling(&45), bst.rank(&45), bst.select(2), window);
+    assert_result((bst.floor(&45).map(|e| *e.0), bst.ceiling(&45).map(|e| *e.0)), (Some(40), Some(50)), "BST Floor/Ceiling");
+    assert_result((bst.rank(&45), bst.select(2).map(|e| *e.0)), (3, Some(40)), "BST Rank/Select");
+    assert_result(window, vec![40, 50, 60], "BST Range");
+    let invalid = create_tree(&[Some(5), Some(4), Some(6), None, None, Some(3), Some(7)]);
+    println!("Validate BST: [5,4,6,null,null,3,7] -> {}", is_valid_bst(&invalid));
+    assert_result(is_valid_bst(&invalid), false, "Validate BST");
+    
+    // AVL Tree
+    let avl: AvlTree<u32, u32> = (0..1_000).map(|k| (k, k)).collect();
+    let worst: Bst<u32, u32> = (0..1_000).map(|k| (k, k)).collect();
+    println!("AVL Tree: {} sorted keys -> height {} (an unbalanced BST is as deep as it is long: {} keys)", avl.len(), avl.height(), worst.len());
+    assert_result(avl.height(), 10, "AVL Height");
+    let (below, found, above) = avl.split(&600);
+    println!("AVL Split at 600: {} below, found {:?}, {} above", below.len(), found, above.len());
+    assert_result((below.len(), found, above.len()), (600, Some(600), 399), "AVL Split");
+    let rejoined = AvlTree::join(below, above);
+    rejoined.check_invariants();
+    assert_result(rejoined.len(), 999, "AVL Join");
+    
+    // Red-black tree and treap behind the shared OrderedMap trait
+    fn fill<M: OrderedMap<u32, u32>>(mut map: M, n: u32) -> M {
+        for key in 0..n {
+            map.insert(key, key);
+        }
+        map
+    }
+    let llrb = fill(LlrbTree::new(), 1_000);
+    let treap = fill(Treap::with_seed(2024), 1_000);
+    println!("Sorted inserts x1000: red-black height {}, treap height {}", llrb.height(), treap.height());
+    assert_result((llrb.len(), llrb.min(), treap.max()), (1_000, Some((&0, &0)), Some((&999, &999))), "Red-Black Tree and Treap");
+    assert_result(ordered_maps_agree(1_000), true, "Ordered Map Agreement");
+    
+    // Segment trees
+    let values = [5, -2, 7, 0, 3, -8, 4, 6];
+    let mut sums = measure_time("Segment Tree Build", || SegmentTree::new(&values, Sum));
+    let (mins, maxes) = (SegmentTree::new(&values, Min), SegmentTree::new(&values, Max));
+    println!("Segment Tree over {:?}: sum[2..6] = {}, min[2..6] = {}, max[2..6] = {}", values, sums.query(2..6), mins.query(2..6), maxes.query(2..6));
+    assert_result((sums.query(2..6), mins.query(2..6), maxes.query(2..6)), (2, -8, 7), "Segment Tree Range Query");
+    sums.set(5, 10);
+    assert_result(sums.query(2..6), 20, "Segment Tree Point Update");
+    let mut lazy = LazySegmentTree::new(&values.map(i64::from));
+    lazy.assign(0..4, 1);
+    lazy.add(2..8, 10);
+    println!("Lazy Segment Tree after assign [0..4] = 1, add [2..8] += 10: sum {}, min {:?}, max {:?}", lazy.sum(..), lazy.min(..), lazy.max(..));
+    assert_result((lazy.sum(..), lazy.min(..), lazy.max(..)), (69, Some(1), Some(16)), "Lazy Segment Tree");
+    let kth = measure_time("Persistent Segment Tree Build", || KthSmallest::new(&[1, 5, 2, 6, 3, 7, 4]));
+    println!("K-th Smallest: [1,5,2,6,3,7,4][1..5], k=3 -> {:?}", kth.query(1..5, 3));
+    assert_result(kth.query(1..5, 3), Some(5), "K-th Smallest in Subarray");
+    
+    // Fenwick trees
+    let mut fenwick = measure_time("Fenwick Tree Build", || FenwickTree::from_slice(&[3, 1, 4, 1, 5, 9, 2, 6]));
+    fenwick.add(4, -5);
+    println!("Fenwick Tree over [3,1,4,1,5,9,2,6] with -5 at 4: sum[2..5] = {}, first prefix >= 10 ends at {:?}", fenwick.range_sum(2..5), fenwick.lower_bound(10));
+    assert_result((fenwick.range_sum(2..5), fenwick.lower_bound(10)), (5, Some(5)), "Fenwick Tree");
+    let mut ranged = RangeFenwickTree::new(10);
+    ranged.add(2..7, 3);
+    ranged.add(5.., 1);
+    assert_result((ranged.sum(..), ranged.sum(4..6)), (20, 7), "Fenwick Tree Range Update");
+    let grid = FenwickTree2D::from_grid(&[vec![3, 0, 1, 4, 2], vec![5, 6, 3, 2, 1], vec![1, 2, 0, 1, 5], vec![4, 1, 0, 1, 7], vec![1, 0, 3, 0, 5]]);
+    println!("2D Fenwick Tree: sum of rows 2..=4, cols 1..=3 = {}", grid.sum(2..=4, 1..=3));
+    assert_result(grid.sum(2..=4, 1..=3), 8, "2D Fenwick Tree");
+    
+    // B-Tree
+    let mut btree: BTree<u32, (), 3> = BTree::new();
+    for key in [10, 20, 30, 40, 50, 5, 15, 25] {
+        btree.insert(key, ());
+    }
+    println!("B-Tree of order 3 after inserting 10 20 30 40 50 5 15 25:\n{}", btree.dump());
+    assert_result(btree.dump(), "[20]\n[10] [40]\n[5] [15] [25 30] [50]".to_string(), "B-Tree Insert");
+    btree.remove(&20);
+    btree.remove(&5);
+    println!("After removing 20 and 5:\n{}", btree.dump());
+    btree.check_invariants();
+    let loaded = BTree::<u32, u32, 32>::from_sorted((0..1_000).map(|k| (k, k)));
+    assert_result(loaded.map(|tree| (tree.len(), tree.height())), Ok((1_000, 2)), "B-Tree Bulk Load");
+    assert_result(btree_agrees(1_000), true, "B-Tree Agreement");
+    
+    // Classic problems
+    let tree = create_tree(&[Some(3), Some(9), Some(20), None, None, Some(15), Some(7)]);
+    println!("Depth: [3,9,20,null,null,15,7] -> max {}, min {}", max_depth(&tree), min_depth(&tree));
+    assert_result((max_depth(&tree), min_depth(&tree)), (3, 2), "Max/Min Depth");
+    let zigzag = zigzag_level_order(&tree);
+    println!("Zigzag Level Order: [3,9,20,null,null,15,7] -> {:?}", zigzag);
+    assert_result(zigzag, vec![vec![3], vec![20, 9], vec![15, 7]], "Zigzag Level Order");
+    
+    let tree = create_tree(&[Some(1), Some(2), Some(3), Some(4), Some(5)]);
+    let diameter = measure_time("Diameter", || diameter_of_binary_tree(&tree));
+    println!("Diameter: [1,2,3,4,5] -> {}", diameter);
+    assert_result(diameter, 3, "Diameter of Binary Tree");
+    let skewed = create_tree(&[Some(1), None, Some(2), None, Some(3)]);
+    println!("Balanced: [1,2,3,4,5] -> {}, [1,null,2,null,3] -> {}", is_balanced(&tree), is_balanced(&skewed));
+    assert_result((is_balanced(&tree), is_balanced(&skewed)), (true, false), "Balanced Binary Tree");
+    
+    let mirror = create_tree(&[Some(1), Some(2), Some(2), Some(3), Some(4), Some(4), Some(3)]);
+    println!("Symmetric: [1,2,2,3,4,4,3] -> {}", is_symmetric(&mirror));
+    assert_result(is_symmetric(&mirror), true, "Symmetric Tree");
+    let inverted = invert_tree(create_tree(&[Some(4), Some(2), Some(7), Some(1), Some(3), Some(6), Some(9)]));
+    println!("Invert Tree: [4,2,7,1,3,6,9] -> {}", serialize(&inverted));
+    assert_result(tree_to_level_order(&inverted), [4, 7, 2, 9, 6, 3, 1].map(Some).to_vec(), "Invert Binary Tree");
+    
+    let tree = create_tree(&[Some(5), Some(4), Some(8), Some(11), None, Some(13), Some(4), Some(7), Some(2), None, None, Some(5), Some(1)]);
+    let paths = measure_time("Path Sum II", || path_sum_paths(&tree, 22));
+    println!("Path Sum 22: has path {}, paths {:?}", has_path_sum(&tree, 22), paths);
+    assert_result(has_path_sum(&tree, 22), true, "Path Sum");
+    assert_result(paths, vec![vec![5, 4, 11, 2], vec![5, 8, 4, 5]], "Path Sum II");
+    let tree = create_tree(&[Some(10), Some(5), Some(-3), Some(3), Some(2), None, Some(11), Some(3), Some(-2), None, Some(1)]);
+    println!("Path Sum III: [10,5,-3,3,2,null,11,3,-2,null,1], 8 -> {}", path_sum_count(&tree, 8));
+    assert_result(path_sum_count(&tree, 8), 3, "Path Sum III");
+    let tree = create_tree(&[Some(-10), Some(9), Some(20), None, None, Some(15), Some(7)]);
+    println!("Max Path Sum: [-10,9,20,null,null,15,7] -> {:?}", max_path_sum(&tree));
+    assert_result(max_path_sum(&tree), Some(42), "Binary Tree Maximum Path Sum");
+    
+    let tree = create_tree(&[Some(3), Some(5), Some(1), Some(6), Some(2), Some(0), Some(8), None, None, Some(7), Some(4)]);
+    println!("LCA: (5, 1) -> {:?}, (5, 4) -> {:?}", lowest_common_ancestor(&tree, 5, 1), lowest_common_ancestor(&tree, 5, 4));
+    assert_result((lowest_common_ancestor(&tree, 5, 1), lowest_common_ancestor(&tree, 5, 4)), (Some(3), Some(5)), "Lowest Common Ancestor");
+    let bst_tree = create_tree(&[Some(6), Some(2), Some(8), Some(0), Some(4), Some(7), Some(9), None, None, Some(3), Some(5)]);
+    println!("LCA (BST): (2, 8) -> {:?}, (2, 4) -> {:?}", lowest_common_ancestor_bst(&bst_tree, 2, 8), lowest_common_ancestor_bst(&bst_tree, 2, 4));
+    assert_result((lowest_common_ancestor_bst(&bst_tree, 2, 8), lowest_common_ancestor_bst(&bst_tree, 2, 4)), (Some(6), Some(2)), "Lowest Common Ancestor (BST)");
+    
+    let tree = create_tree(&[Some(1), Some(2), Some(3), None, Some(5), None, Some(4)]);
+    println!("Right Side View: [1,2,3,null,5,null,4] -> {:?}", right_side_view(&tree));
+    assert_result(right_side_view(&tree), vec![1, 3, 4], "Right Side View");
+    let tree = create_tree(&[Some(1), Some(2), Some(3), Some(4), Some(6), Some(5), Some(7)]);
+    let vertical = measure_time("Vertical Order", || vertical_order(&tree));
+    println!("Vertical Order: [1,2,3,4,6,5,7] -> {:?}", vertical);
+    assert_result(vertical, vec![vec![4], vec![2], vec![1, 5, 6], vec![3], vec![7]], "Vertical Order Traversal");
+    
+    // Visualization: the same printers work for TreeNode and every map type
+    let tree = create_tree(&[Some(5), Some(3), Some(8), None, Some(4)]);
+    let art = ascii(&tree);
+    print!("ASCII:\n{}", art);
+    assert_result(art.as_str(), "5\n|-- 3\n|   |-- nil\n|   `-- 4\n`-- 8\n", "Print Binary Tree (ASCII)");
+    print!("DOT:\n{}", dot(&tree));
+    print!("Mermaid:\n{}", mermaid(&tree));
+    let llrb: LlrbTree<i32, ()> = (1..=6).map(|key| (key, ())).collect();
+    print!("LLRB 1..=6:\n{}", ascii(&llrb));
+    let btree = BTree::<i32, (), 3>::from_sorted((1..=7).map(|key| (key, ()))).unwrap();
+    let art = ascii(&btree);
+    print!("B-Tree 1..=7:\n{}", art);
+    assert_result(art.as_str(), "[3 6]\n|-- [1 2]\n|-- [4 5]\n`-- [7]\n", "Print B-Tree (ASCII)");
 }
 
 #[cfg(test)]
@@ -53,4 +554,127 @@ mod tests {
         assert_eq!(node.left, None);
         assert_eq!(node.right, None);
     }
-} 
\ No newline at end of file
+    
+    #[test]
+    fn test_create_tree_and_back() {
+        let values = [Some(1), None, Some(2), Some(3)];
+        let root = create_tree(&values).unwrap();
+        assert_eq!(root.val, 1);
+        assert_eq!(root.left, None);
+        let right = root.right.as_ref().unwrap();
+        assert_eq!((right.val, right.left.as_ref().map(|n| n.val), right.right.as_ref().map(|n| n.val)), (2, Some(3), None));
+        assert_eq!(tree_to_level_order(&Some(root)), values);
+        
+        assert_eq!(create_tree(&[]), None);
+        assert_eq!(create_tree(&[None, Some(1)]), None);
+        assert_eq!(tree_to_level_order(&None), vec![]);
+        // Trailing holes are dropped on the way back out
+        assert_eq!(tree_to_level_order(&create_tree(&[Some(1), Some(2), None, None, None])), vec![Some(1), Some(2)]);
+    }
+    
+    #[test]
+    fn test_depths() {
+        let tree = create_tree(&[Some(3), Some(9), Some(20), None, None, Some(15), Some(7)]);
+        assert_eq!((max_depth(&tree), min_depth(&tree)), (3, 2));
+        // The root's missing left child is not a leaf
+        let chain = create_tree(&[Some(2), None, Some(3), None, Some(4)]);
+        assert_eq!((max_depth(&chain), min_depth(&chain)), (3, 3));
+        assert_eq!((max_depth(&None), min_depth(&None)), (0, 0));
+    }
+    
+    #[test]
+    fn test_diameter() {
+        assert_eq!(diameter_of_binary_tree(&create_tree(&[Some(1), Some(2), Some(3), Some(4), Some(5)])), 3);
+        assert_eq!(diameter_of_binary_tree(&create_tree(&[Some(1)])), 0);
+        // The longest path need not pass through the root
+        let tree = create_tree(&[Some(1), Some(2), None, Some(3), Some(4), Some(5), None, None, Some(6), Some(7), None, None, Some(8)]);
+        assert_eq!(diameter_of_binary_tree(&tree), 6);
+    }
+    
+    #[test]
+    fn test_balanced_and_symmetric() {
+        assert!(is_balanced(&None));
+        assert!(is_balanced(&create_tree(&[Some(3), Some(9), Some(20), None, None, Some(15), Some(7)])));
+        // Both root subtrees have depth 3, but the left one is unbalanced inside
+        let tree = create_tree(&[Some(1), Some(2), Some(2), Some(3), None, None, Some(3), Some(4), None, None, Some(4)]);
+        assert!(!is_balanced(&tree));
+        assert!(is_symmetric(&tree));
+        assert!(is_symmetric(&None));
+        assert!(!is_symmetric(&create_tree(&[Some(1), Some(2), Some(2), None, Some(3), None, Some(3)])));
+    }
+    
+    #[test]
+    fn test_invert_tree() {
+        let tree = create_tree(&[Some(2), Some(1), Some(3), None, Some(5)]);
+        let inverted = invert_tree(tree);
+        assert_eq!(tree_to_level_order(&inverted), vec![Some(2), Some(3), Some(1), None, None, Some(5)]);
+        assert!(Inorder::new(&invert_tree(inverted)).eq([1, 5, 2, 3]));
+        assert_eq!(invert_tree(None), None);
+    }
+    
+    #[test]
+    fn test_path_sums() {
+        let tree = create_tree(&[Some(5), Some(4), Some(8), Some(11), None, Some(13), Some(4), Some(7), Some(2), None, None, Some(5), Some(1)]);
+        assert!(has_path_sum(&tree, 22));
+        assert!(has_path_sum(&tree, 26));
+        // 5 + 4 + 11 = 20 stops at an internal node
+        assert!(!has_path_sum(&tree, 20));
+        assert!(!has_path_sum(&None, 0));
+        assert_eq!(path_sum_paths(&tree, 22), vec![vec![5, 4, 11, 2], vec![5, 8, 4, 5]]);
+        assert_eq!(path_sum_paths(&tree, 100), Vec::<Vec<i32>>::new());
+        
+        let tree = create_tree(&[Some(10), Some(5), Some(-3), Some(3), Some(2), None, Some(11), Some(3), Some(-2), None, Some(1)]);
+        assert_eq!(path_sum_count(&tree, 8), 3);
+        assert_eq!(path_sum_count(&create_tree(&[Some(0), Some(0), Some(0)]), 0), 5);
+        // Sums beyond i32 must not overflow
+        let big = create_tree(&[Some(i32::MAX), Some(i32::MAX), None, Some(i32::MIN)]);
+        assert_eq!(path_sum_count(&big, i32::MIN), 1);
+        assert!(!has_path_sum(&create_tree(&[Some(i32::MIN)]), 1));
+        assert!(has_path_sum(&big, i32::MAX - 1));
+        assert_eq!(path_sum_paths(&create_tree(&[Some(i32::MIN)]), 1), Vec::<Vec<i32>>::new());
+        assert_eq!(path_sum_paths(&big, i32::MAX - 1), vec![vec![i32::MAX, i32::MAX, i32::MIN]]);
+    }
+    
+    #[test]
+    fn test_max_path_sum() {
+        assert_eq!(max_path_sum(&create_tree(&[Some(1), Some(2), Some(3)])), Some(6));
+        assert_eq!(max_path_sum(&create_tree(&[Some(-10), Some(9), Some(20), None, None, Some(15), Some(7)])), Some(42));
+        assert_eq!(max_path_sum(&create_tree(&[Some(-3), Some(-2), Some(-5)])), Some(-2));
+        assert_eq!(max_path_sum(&None), None);
+        // Sums beyond i32 must not overflow
+        let big = create_tree(&[Some(i32::MAX), Some(i32::MAX), Some(i32::MAX)]);
+        assert_eq!(max_path_sum(&big), Some(3 * i32::MAX as i64));
+        assert_eq!(max_path_sum(&create_tree(&[Some(i32::MIN)])), Some(i32::MIN as i64));
+    }
+    
+    #[test]
+    fn test_lowest_common_ancestor() {
+        let tree = create_tree(&[Some(3), Some(5), Some(1), Some(6), Some(2), Some(0), Some(8), None, None, Some(7), Some(4)]);
+        assert_eq!(lowest_common_ancestor(&tree, 5, 1), Some(3));
+        assert_eq!(lowest_common_ancestor(&tree, 7, 4), Some(2));
+        assert_eq!(lowest_common_ancestor(&tree, 6, 4), Some(5));
+        assert_eq!(lowest_common_ancestor(&tree, 8, 8), Some(8));
+        assert_eq!(lowest_common_ancestor(&tree, 5, 42), None);
+        
+        let bst = create_tree(&[Some(6), Some(2), Some(8), Some(0), Some(4), Some(7), Some(9), None, None, Some(3), Some(5)]);
+        assert_eq!(lowest_common_ancestor_bst(&bst, 2, 8), Some(6));
+        assert_eq!(lowest_common_ancestor_bst(&bst, 4, 2), Some(2));
+        assert_eq!(lowest_common_ancestor_bst(&bst, 3, 5), Some(4));
+        assert_eq!(lowest_common_ancestor_bst(&bst, 3, 1), None);
+        assert_eq!(lowest_common_ancestor_bst(&None, 3, 5), None);
+    }
+    
+    #[test]
+    fn test_level_views() {
+        let tree = create_tree(&[Some(1), Some(2), Some(3), Some(4), None, None, None, Some(5)]);
+        assert_eq!(right_side_view(&tree), vec![1, 3, 4, 5]);
+        assert_eq!(zigzag_level_order(&tree), vec![vec![1], vec![3, 2], vec![4], vec![5]]);
+        assert_eq!(right_side_view(&None), Vec::<i32>::new());
+        
+        let tree = create_tree(&[Some(3), Some(9), Some(20), None, None, Some(15), Some(7)]);
+        assert_eq!(vertical_order(&tree), vec![vec![9], vec![3, 15], vec![20], vec![7]]);
+        // 5 and 6 share row 2 and column 0, so they go in value order
+        let tree = create_tree(&[Some(1), Some(2), Some(3), Some(4), Some(6), Some(5), Some(7)]);
+        assert_eq!(vertical_order(&tree), vec![vec![4], vec![2], vec![1, 5, 6], vec![3], vec![7]]);
+    }
+}
\ No newline at end of file
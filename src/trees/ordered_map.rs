@@ -0,0 +1,192 @@
+//! Ordered Map Family
+//! 
+//! A common interface for the search trees in this module, so property tests
+//! and `benches/ordered_maps.rs` can run over every implementation with the same code.
+//! `std::collections::BTreeMap` implements it too, as the reference.
+//! - `Bst`: unbalanced, O(n) worst case
+//! - `AvlTree`: strictly height-balanced, O(log n)
+//! - `LlrbTree`: left-leaning red-black, O(log n)
+//! - `Treap`: randomized, expected O(log n)
+
+use super::avl::AvlTree;
+use super::bst::Bst;
+use super::red_black::LlrbTree;
+use super::treap::Treap;
+use std::collections::BTreeMap;
+
+/// An ordered map from `K` to `V`.
+pub trait OrderedMap<K: Ord, V> {
+    type Iter<'a>: Iterator<Item = (&'a K, &'a V)>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+    
+    /// Insert or replace, returning the previous value for `key`.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+    
+    fn get(&self, key: &K) -> Option<&V>;
+    
+    fn remove(&mut self, key: &K) -> Option<V>;
+    
+    fn len(&self) -> usize;
+    
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+    
+    fn min(&self) -> Option<(&K, &V)>;
+    
+    fn max(&self) -> Option<(&K, &V)>;
+    
+    /// Entries in ascending key order.
+    fn iter(&self) -> Self::Iter<'_>;
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for BTreeMap<K, V> {
+    type Iter<'a>
+        = std::collections::btree_map::Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+    
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+    
+    fn get(&self, key: &K) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+    
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BTreeMap::remove(self, key)
+    }
+    
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+    
+    fn min(&self) -> Option<(&K, &V)> {
+        self.first_key_value()
+    }
+    
+    fn max(&self) -> Option<(&K, &V)> {
+        self.last_key_value()
+    }
+    
+    fn iter(&self) -> Self::Iter<'_> {
+        BTreeMap::iter(self)
+    }
+}
+
+/// Deterministic pseudo-random keys (xorshift) so every map sees the same input.
+pub fn keys(n: usize, seed: u64) -> Vec<u64> {
+    let mut state = seed | 1;
+    (0..n)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % (4 * n as u64).max(1)
+        })
+        .collect()
+}
+
+/// Insert `input[i] -> i` for every `i`, returning a checksum of the replaced keys.
+pub fn insert_keys<M: OrderedMap<u64, u64>>(map: &mut M, input: &[u64]) -> u64 {
+    let mut checksum = 0u64;
+    for (i, &key) in input.iter().enumerate() {
+        if map.insert(key, i as u64).is_some() {
+            checksum = checksum.wrapping_add(key);
+        }
+    }
+    checksum
+}
+
+/// Look up every probe, returning a checksum of what was found.
+pub fn get_keys<M: OrderedMap<u64, u64>>(map: &M, probes: &[u64]) -> u64 {
+    probes.iter().fold(0u64, |checksum, key| {
+        let found = map.get(key).copied().unwrap_or(u64::MAX);
+        checksum.wrapping_mul(31).wrapping_add(found)
+    })
+}
+
+/// Remove every probe, returning a checksum of what was removed.
+pub fn remove_keys<M: OrderedMap<u64, u64>>(map: &mut M, probes: &[u64]) -> u64 {
+    probes.iter().fold(0u64, |checksum, key| {
+        let removed = map.remove(key).unwrap_or(u64::MAX);
+        checksum.wrapping_mul(31).wrapping_add(removed)
+    })
+}
+
+/// Run insert, lookup and remove workloads on one map type.
+/// Returns a checksum of everything observed so the implementations can be cross-checked.
+pub(super) fn run_workloads<M: OrderedMap<u64, u64>>(n: usize, make: impl Fn() -> M) -> u64 {
+    let input = keys(n, 0x5eed);
+    let probes = keys(n, 0xfeed);
+    let mut map = make();
+    let mut checksum = insert_keys(&mut map, &input);
+    checksum = checksum.wrapping_mul(31).wrapping_add(get_keys(&map, &probes));
+    checksum = checksum.wrapping_mul(31).wrapping_add(remove_keys(&mut map, &probes[..n / 2]));
+    for (key, value) in map.iter() {
+        checksum = checksum.wrapping_mul(31).wrapping_add(key ^ value);
+    }
+    checksum.wrapping_add(map.len() as u64)
+}
+
+/// Problem: Ordered Map Agreement
+/// Run the search trees through identical workloads on `n` keys; all must
+/// agree with `BTreeMap`. Timings live in `benches/ordered_maps.rs`.
+pub fn ordered_maps_agree(n: usize) -> bool {
+    let results = [
+        run_workloads(n, BTreeMap::new),
+        run_workloads(n, Bst::new),
+        run_workloads(n, AvlTree::new),
+        run_workloads(n, LlrbTree::new),
+        run_workloads(n, Treap::new),
+    ];
+    results.iter().all(|&checksum| checksum == results[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    /// Apply the same operations to a map and a `BTreeMap` reference.
+    fn check_against_reference<M: OrderedMap<i16, u32>>(mut map: M, ops: &[(u8, i16, u32)]) -> Result<(), TestCaseError> {
+        let mut reference = BTreeMap::new();
+        for &(op, key, value) in ops {
+            match op % 3 {
+                0 | 1 => prop_assert_eq!(map.insert(key, value), reference.insert(key, value)),
+                _ => prop_assert_eq!(map.remove(&key), reference.remove(&key)),
+            }
+            prop_assert_eq!(map.len(), reference.len());
+            prop_assert_eq!(map.contains_key(&key), reference.contains_key(&key));
+        }
+        prop_assert_eq!(map.min(), reference.first_key_value());
+        prop_assert_eq!(map.max(), reference.last_key_value());
+        prop_assert!(map.iter().eq(reference.iter()));
+        prop_assert_eq!(map.is_empty(), reference.is_empty());
+        Ok(())
+    }
+    
+    #[test]
+    fn test_benchmark_agrees() {
+        assert!(ordered_maps_agree(2_000));
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_maps_match_btreemap(ops in proptest::collection::vec((any::<u8>(), -64i16..64, any::<u32>()), 0..300), seed in any::<u64>()) {
+            check_against_reference(Bst::new(), &ops)?;
+            check_against_reference(AvlTree::new(), &ops)?;
+            check_against_reference(LlrbTree::new(), &ops)?;
+            check_against_reference(Treap::with_seed(seed), &ops)?;
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Persistent Segment Tree
+//! 
+//! A segment tree of counts where every update creates a new version and
+//! leaves the old ones intact. An update copies only the O(log n) nodes on
+//! the path it changes; everything else is shared with the previous version.
+//! - `PersistentSegmentTree`: point add and range sum against any version
+//! - `KthSmallest`: k-th smallest value in any subarray, from the difference
+//!   of two prefix versions
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    left: usize,
+    right: usize,
+    sum: i64,
+}
+
+/// Node 0 is the shared all-zero tree: its children are itself.
+const EMPTY: usize = 0;
+
+/// Problem: Persistent Segment Tree
+pub struct PersistentSegmentTree {
+    nodes: Vec<Node>,
+    /// Root node of each version; version 0 is all zeros.
+    roots: Vec<usize>,
+    size: usize,
+}
+
+impl PersistentSegmentTree {
+    /// `size` positions, all zero, as version 0.
+    pub fn new(size: usize) -> Self {
+        PersistentSegmentTree {
+            nodes: vec![Node { left: EMPTY, right: EMPTY, sum: 0 }],
+            roots: vec![EMPTY],
+            size,
+        }
+    }
+    
+    /// Number of positions.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    
+    /// Number of versions, including the initial one.
+    pub fn versions(&self) -> usize {
+        self.roots.len()
+    }
+    
+    /// Create a new version equal to `version` with `delta` added at `index`,
+    /// returning its number.
+    pub fn add(&mut self, version: usize, index: usize, delta: i64) -> usize {
+        assert!(index < self.size, "index {} out of bounds for size {}", index, self.size);
+        let root = self.insert(self.roots[version], 0, self.size, index, delta);
+        self.roots.push(root);
+        self.roots.len() - 1
+    }
+    
+    fn insert(&mut self, node: usize, low: usize, high: usize, index: usize, delta: i64) -> usize {
+        let mut copy = self.nodes[node];
+        copy.sum += delta;
+        if high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if index < mid {
+                copy.left = self.insert(copy.left, low, mid, index, delta);
+            } else {
+                copy.right = self.insert(copy.right, mid, high, index, delta);
+            }
+        }
+        self.nodes.push(copy);
+        self.nodes.len() - 1
+    }
+    
+    /// Sum of the positions in `range` as of `version`.
+    pub fn sum(&self, version: usize, range: Range<usize>) -> i64 {
+        assert!(range.end <= self.size, "range end {} out of bounds for size {}", range.end, self.size);
+        self.sum_in(self.roots[version], 0, self.size, &range)
+    }
+    
+    fn sum_in(&self, node: usize, low: usize, high: usize, range: &Range<usize>) -> i64 {
+        if node == EMPTY || range.end <= low || high <= range.start {
+            return 0;
+        }
+        if range.start <= low && high <= range.end {
+            return self.nodes[node].sum;
+        }
+        let mid = low + (high - low) / 2;
+        self.sum_in(self.nodes[node].left, low, mid, range) + self.sum_in(self.nodes[node].right, mid, high, range)
+    }
+    
+    /// Smallest position `p` such that the sum of positions `0..=p` in
+    /// `newer - older` reaches `k + 1`, i.e. the `k`-th (0-based) unit counted
+    /// in `newer` but not in `older`. Counts must be non-negative.
+    fn kth_in_difference(&self, older: usize, newer: usize, mut k: i64) -> Option<usize> {
+        let (mut old, mut new) = (self.roots[older], self.roots[newer]);
+        if k < 0 || self.nodes[new].sum - self.nodes[old].sum <= k {
+            return None;
+        }
+        let (mut low, mut high) = (0, self.size);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            let left = self.nodes[self.nodes[new].left].sum - self.nodes[self.nodes[old].left].sum;
+            if k < left {
+                (old, new, high) = (self.nodes[old].left, self.nodes[new].left, mid);
+            } else {
+                k -= left;
+                (old, new, low) = (self.nodes[old].right, self.nodes[new].right, mid);
+            }
+        }
+        Some(low)
+    }
+}
+
+/// Problem: K-th Smallest in Subarray
+/// Version `i` counts how often each (rank-compressed) value occurs among the
+/// first `i` elements, so versions `end` and `start` differ by exactly the
+/// values of `start..end`.
+pub struct KthSmallest {
+    tree: PersistentSegmentTree,
+    sorted: Vec<i32>,
+}
+
+impl KthSmallest {
+    pub fn new(values: &[i32]) -> Self {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        let mut tree = PersistentSegmentTree::new(sorted.len());
+        for (i, value) in values.iter().enumerate() {
+            let rank = sorted.binary_search(value).expect("value was collected above");
+            tree.add(i, rank, 1);
+        }
+        KthSmallest { tree, sorted }
+    }
+    
+    /// The `k`-th smallest (1-based) value of `values[range]`, or `None` if
+    /// the range holds fewer than `k` values or `k` is 0.
+    pub fn query(&self, range: Range<usize>, k: usize) -> Option<i32> {
+        self.check_range(&range);
+        let k = (k as i64).checked_sub(1)?;
+        self.tree.kth_in_difference(range.start, range.end, k).map(|rank| self.sorted[rank])
+    }
+    
+    /// How many values of `values[range]` are below `bound`.
+    pub fn count_less(&self, range: Range<usize>, bound: i32) -> usize {
+        self.check_range(&range);
+        let ranks = self.sorted.partition_point(|&value| value < bound);
+        (self.tree.sum(range.end, 0..ranks) - self.tree.sum(range.start, 0..ranks)) as usize
+    }
+    
+    /// Panics unless `range` is a valid range into the original values.
+    fn check_range(&self, range: &Range<usize>) {
+        let len = self.tree.versions() - 1;
+        assert!(range.start <= range.end && range.end <= len, "range {:?} out of bounds for length {}", range, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_versions_are_kept() {
+        let mut tree = PersistentSegmentTree::new(8);
+        let v1 = tree.add(0, 3, 5);
+        let v2 = tree.add(v1, 6, 2);
+        let v3 = tree.add(v1, 3, -1);
+        assert_eq!((tree.sum(0, 0..8), tree.sum(v1, 0..8), tree.sum(v2, 0..8), tree.sum(v3, 0..8)), (0, 5, 7, 4));
+        assert_eq!((tree.sum(v2, 4..8), tree.sum(v3, 4..8)), (2, 0));
+        assert_eq!(tree.versions(), 4);
+    }
+    
+    #[test]
+    fn test_kth_smallest() {
+        let kth = KthSmallest::new(&[1, 5, 2, 6, 3, 7, 4]);
+        assert_eq!(kth.query(1..5, 3), Some(5));
+        assert_eq!(kth.query(3..4, 1), Some(6));
+        assert_eq!(kth.query(0..7, 3), Some(3));
+        assert_eq!(kth.query(0..7, 8), None);
+        assert_eq!(kth.query(2..2, 1), None);
+        assert_eq!(kth.query(0..7, 0), None);
+        assert_eq!(kth.count_less(0..7, 5), 4);
+        assert_eq!(KthSmallest::new(&[]).query(0..0, 1), None);
+    }
+    
+    #[test]
+    #[should_panic(expected = "range 2..6 out of bounds for length 5")]
+    fn test_count_less_rejects_out_of_bounds_range() {
+        KthSmallest::new(&[1, 5, 2, 6, 3]).count_less(2..6, 5);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_kth_matches_sorting(values in proptest::collection::vec(-20..20i32, 0..50), a in 0..51usize, b in 0..51usize, k in 0..52usize, bound in -25..25i32) {
+            let (start, end) = (a.min(b).min(values.len()), a.max(b).min(values.len()));
+            let kth = KthSmallest::new(&values);
+            let mut window = values[start..end].to_vec();
+            window.sort_unstable();
+            let expected = k.checked_sub(1).and_then(|i| window.get(i).copied());
+            prop_assert_eq!(kth.query(start..end, k), expected);
+            prop_assert_eq!(kth.count_less(start..end, bound), window.iter().filter(|&&value| value < bound).count());
+        }
+    }
+}
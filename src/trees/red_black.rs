@@ -0,0 +1,431 @@
+//! Left-Leaning Red-Black Tree
+//! 
+//! Sedgewick's left-leaning red-black tree: a binary encoding of a 2-3 tree in
+//! which a red link glues a node to its parent to form a 3-node. Red links
+//! always lean left, so insert and delete only need two rotations and a color
+//! flip to restore balance on the way back up.
+//! - insert / get / remove, with `move_red_left` / `move_red_right` on the way down
+//! - in-order iteration
+//! - `check_invariants` (debug builds only) verifies colors, black height and key order
+
+use super::ordered_map::OrderedMap;
+use super::visualize::{Sketch, Visualize};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// Color of the link from the parent to this node.
+    red: bool,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+fn is_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().is_some_and(|node| node.red)
+}
+
+/// Whether the left child of `link` is red.
+fn is_red_left<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().is_some_and(|node| is_red(&node.left))
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut pivot = node.right.take().expect("rotate_left needs a right child");
+    node.right = pivot.left.take();
+    pivot.red = node.red;
+    node.red = true;
+    pivot.left = Some(node);
+    pivot
+}
+
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut pivot = node.left.take().expect("rotate_right needs a left child");
+    node.left = pivot.right.take();
+    pivot.red = node.red;
+    node.red = true;
+    pivot.right = Some(node);
+    pivot
+}
+
+/// Flip the colors of a node and both of its children, splitting or forming a 4-node.
+fn flip_colors<K, V>(node: &mut Node<K, V>) {
+    node.red = !node.red;
+    for child in [&mut node.left, &mut node.right].into_iter().flatten() {
+        child.red = !child.red;
+    }
+}
+
+/// Restore the left-leaning invariants on the way back up.
+fn fix_up<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    if is_red(&node.right) && !is_red(&node.left) {
+        node = rotate_left(node);
+    }
+    if is_red(&node.left) && is_red_left(&node.left) {
+        node = rotate_right(node);
+    }
+    if is_red(&node.left) && is_red(&node.right) {
+        flip_colors(&mut node);
+    }
+    node
+}
+
+/// Make the left child or one of its children red before descending left.
+fn move_red_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    flip_colors(&mut node);
+    if is_red_left(&node.right) {
+        node.right = node.right.take().map(rotate_right);
+        node = rotate_left(node);
+        flip_colors(&mut node);
+    }
+    node
+}
+
+/// Make the right child or one of its children red before descending right.
+fn move_red_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    flip_colors(&mut node);
+    if is_red_left(&node.left) {
+        node = rotate_right(node);
+        flip_colors(&mut node);
+    }
+    node
+}
+
+fn insert<K: Ord, V>(link: Link<K, V>, key: K, value: V) -> (Box<Node<K, V>>, Option<V>) {
+    let Some(mut node) = link else {
+        let node = Box::new(Node {
+            key,
+            value,
+            red: true,
+            left: None,
+            right: None,
+        });
+        return (node, None);
+    };
+    let old = match key.cmp(&node.key) {
+        Ordering::Less => {
+            let (left, old) = insert(node.left.take(), key, value);
+            node.left = Some(left);
+            old
+        }
+        Ordering::Greater => {
+            let (right, old) = insert(node.right.take(), key, value);
+            node.right = Some(right);
+            old
+        }
+        Ordering::Equal => Some(std::mem::replace(&mut node.value, value)),
+    };
+    (fix_up(node), old)
+}
+
+/// Remove the minimum of a subtree, returning what remains and the removed node.
+fn remove_min<K, V>(mut node: Box<Node<K, V>>) -> (Link<K, V>, Box<Node<K, V>>) {
+    if node.left.is_none() {
+        // Left-leaning: a node without a left child has no right child either
+        return (None, node);
+    }
+    if !is_red(&node.left) && !is_red_left(&node.left) {
+        node = move_red_left(node);
+    }
+    let (left, min) = remove_min(node.left.take().expect("checked above"));
+    node.left = left;
+    (Some(fix_up(node)), min)
+}
+
+/// Remove `key`, which must be present in the subtree.
+fn remove<K: Ord, V>(mut node: Box<Node<K, V>>, key: &K) -> (Link<K, V>, V) {
+    let removed;
+    if *key < node.key {
+        if !is_red(&node.left) && !is_red_left(&node.left) {
+            node = move_red_left(node);
+        }
+        let (left, value) = remove(node.left.take().expect("key is present"), key);
+        node.left = left;
+        removed = value;
+    } else {
+        if is_red(&node.left) {
+            node = rotate_right(node);
+        }
+        if *key == node.key && node.right.is_none() {
+            return (None, node.value);
+        }
+        if !is_red(&node.right) && !is_red_left(&node.right) {
+            node = move_red_right(node);
+        }
+        if *key == node.key {
+            // Replace this entry with its successor's and remove the successor instead
+            let (right, mut min) = remove_min(node.right.take().expect("key has a successor"));
+            node.right = right;
+            std::mem::swap(&mut node.key, &mut min.key);
+            removed = std::mem::replace(&mut node.value, min.value);
+        } else {
+            let (right, value) = remove(node.right.take().expect("key is present"), key);
+            node.right = right;
+            removed = value;
+        }
+    }
+    (Some(fix_up(node)), removed)
+}
+
+/// Problem: Left-Leaning Red-Black Tree
+pub struct LlrbTree<K, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+impl<K: Ord, V> LlrbTree<K, V> {
+    pub fn new() -> Self {
+        LlrbTree { root: None, len: 0 }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Height of the tree counting red and black links; 0 when empty.
+    pub fn height(&self) -> usize {
+        fn walk<K, V>(link: &Link<K, V>) -> usize {
+            link.as_ref().map_or(0, |node| 1 + walk(&node.left).max(walk(&node.right)))
+        }
+        walk(&self.root)
+    }
+    
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(&node.value),
+            };
+        }
+        None
+    }
+    
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+    
+    /// Insert or replace, returning the previous value for `key`.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (mut root, old) = insert(self.root.take(), key, value);
+        root.red = false;
+        self.root = Some(root);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+    
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let mut root = self.root.take().expect("key is present");
+        // Start the descent as if the root were the middle of a 3-node
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.red = true;
+        }
+        let (mut root, removed) = remove(root, key);
+        if let Some(root) = root.as_mut() {
+            root.red = false;
+        }
+        self.root = root;
+        self.len -= 1;
+        Some(removed)
+    }
+    
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+    
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+    
+    /// Entries in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+    
+    /// Panic if the tree breaks a left-leaning red-black invariant: the root is
+    /// black, no right link is red, no node has two red links in a row, every
+    /// root-to-leaf path crosses the same number of black links, and keys are
+    /// strictly increasing in order. A no-op in release builds.
+    pub fn check_invariants(&self) {
+        /// Returns the black height of the subtree and its number of nodes.
+        fn walk<K: Ord, V>(link: &Link<K, V>, low: Option<&K>, high: Option<&K>) -> (usize, usize) {
+            let Some(node) = link else { return (0, 0) };
+            assert!(low.is_none_or(|low| *low < node.key), "keys out of order");
+            assert!(high.is_none_or(|high| node.key < *high), "keys out of order");
+            assert!(!is_red(&node.right), "red link leans right");
+            assert!(!(node.red && is_red(&node.left)), "two red links in a row");
+            let (left_black, left_count) = walk(&node.left, low, Some(&node.key));
+            let (right_black, right_count) = walk(&node.right, Some(&node.key), high);
+            assert_eq!(left_black, right_black, "black heights differ");
+            (left_black + usize::from(!node.red), left_count + right_count + 1)
+        }
+        if cfg!(debug_assertions) {
+            assert!(!is_red(&self.root), "root is red");
+            let (_, count) = walk(&self.root, None, None);
+            assert_eq!(count, self.len, "stale length");
+        }
+    }
+}
+
+impl<K: Ord, V> Default for LlrbTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for LlrbTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = LlrbTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for LlrbTree<K, V> {
+    type Iter<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+    
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        LlrbTree::insert(self, key, value)
+    }
+    
+    fn get(&self, key: &K) -> Option<&V> {
+        LlrbTree::get(self, key)
+    }
+    
+    fn remove(&mut self, key: &K) -> Option<V> {
+        LlrbTree::remove(self, key)
+    }
+    
+    fn len(&self) -> usize {
+        LlrbTree::len(self)
+    }
+    
+    fn min(&self) -> Option<(&K, &V)> {
+        LlrbTree::min(self)
+    }
+    
+    fn max(&self) -> Option<(&K, &V)> {
+        LlrbTree::max(self)
+    }
+    
+    fn iter(&self) -> Iter<'_, K, V> {
+        LlrbTree::iter(self)
+    }
+}
+
+impl<K: Debug, V> Visualize for LlrbTree<K, V> {
+    fn sketch(&self) -> Option<Sketch> {
+        fn sketch<K: Debug, V>(link: &Link<K, V>) -> Option<Sketch> {
+            let node = link.as_ref()?;
+            Some(Sketch::binary(format!("{:?}{}", node.key, if node.red { " (red)" } else { "" }), sketch(&node.left), sketch(&node.right)))
+        }
+        sketch(&self.root)
+    }
+}
+
+/// In-order iterator over an `LlrbTree`.
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left_spine(&mut self, mut node: Option<&'a Node<K, V>>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_insert_sorted_stays_balanced() {
+        let mut tree = LlrbTree::new();
+        for key in 0..1000 {
+            assert_eq!(tree.insert(key, key), None);
+            tree.check_invariants();
+        }
+        assert_eq!(tree.insert(500, -1), Some(500));
+        assert_eq!(tree.len(), 1000);
+        // A red-black tree is at most twice as deep as a perfectly balanced one
+        assert!(tree.height() <= 2 * 10);
+        assert!(tree.iter().map(|(key, _)| *key).eq(0..1000));
+    }
+    
+    #[test]
+    fn test_remove() {
+        let mut tree: LlrbTree<u32, u32> = (0..300).map(|k| (k, k * 10)).collect();
+        for key in (0..300).rev().step_by(3) {
+            assert_eq!(tree.remove(&key), Some(key * 10));
+            tree.check_invariants();
+        }
+        assert_eq!(tree.remove(&299), None);
+        assert_eq!(tree.len(), 200);
+        assert_eq!(tree.min(), Some((&0, &0)));
+        assert_eq!(tree.max(), Some((&298, &2980)));
+        while let Some((&key, _)) = tree.min() {
+            tree.remove(&key);
+            tree.check_invariants();
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.height(), 0);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_invariants_hold_after_every_operation(ops in proptest::collection::vec((any::<bool>(), 0u8..64), 0..200)) {
+            let mut tree = LlrbTree::new();
+            for (insert, key) in ops {
+                if insert {
+                    tree.insert(key, ());
+                } else {
+                    tree.remove(&key);
+                }
+                tree.check_invariants();
+            }
+        }
+    }
+}
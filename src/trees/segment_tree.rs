@@ -0,0 +1,471 @@
+//! Segment Trees
+//! 
+//! Range queries over an array that changes between queries, in O(log n) each.
+//! - `SegmentTree<T, Op>`: point update and range query for any monoid `Op`
+//!   (sum, min, max, or a closure); the operation need not be commutative
+//! - `LazySegmentTree`: range add and range assign with range sum/min/max,
+//!   deferring updates to children until a query or update reaches them
+
+use std::ops::{Bound, Range, RangeBounds};
+
+/// An associative operation with an identity element.
+pub trait Monoid<T> {
+    fn identity(&self) -> T;
+    
+    /// Must be associative; `combine(a, b)` covers `a`'s range followed by `b`'s.
+    fn combine(&self, a: &T, b: &T) -> T;
+}
+
+/// Addition, with identity 0.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sum;
+
+/// Minimum, with identity `T::MAX`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Min;
+
+/// Maximum, with identity `T::MIN`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Max;
+
+macro_rules! impl_integer_monoids {
+    ($($t:ty),*) => {$(
+        impl Monoid<$t> for Sum {
+            fn identity(&self) -> $t {
+                0
+            }
+            
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                a + b
+            }
+        }
+        
+        impl Monoid<$t> for Min {
+            fn identity(&self) -> $t {
+                <$t>::MAX
+            }
+            
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                *a.min(b)
+            }
+        }
+        
+        impl Monoid<$t> for Max {
+            fn identity(&self) -> $t {
+                <$t>::MIN
+            }
+            
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                *a.max(b)
+            }
+        }
+    )*};
+}
+
+impl_integer_monoids!(i32, i64, u32, u64, usize);
+
+/// A monoid given by an identity value and a combining closure.
+#[derive(Clone)]
+pub struct FnMonoid<T, F> {
+    identity: T,
+    combine: F,
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> Monoid<T> for FnMonoid<T, F> {
+    fn identity(&self) -> T {
+        self.identity.clone()
+    }
+    
+    fn combine(&self, a: &T, b: &T) -> T {
+        (self.combine)(a, b)
+    }
+}
+
+/// Resolve `range` against a length, panicking like slice indexing when it is out of bounds.
+pub(super) fn to_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range starts at {} but ends at {}", start, end);
+    assert!(end <= len, "range end {} out of bounds for length {}", end, len);
+    start..end
+}
+
+/// Problem: Range Query with Point Update (Segment Tree)
+/// A perfect binary tree over the values padded to a power of two, stored
+/// implicitly: node `i` has children `2i` and `2i + 1`, and leaves start at `size`.
+pub struct SegmentTree<T, Op> {
+    nodes: Vec<T>,
+    size: usize,
+    len: usize,
+    op: Op,
+}
+
+impl<T: Clone, Op: Monoid<T>> SegmentTree<T, Op> {
+    /// Build over `values` in O(n).
+    pub fn new(values: &[T], op: Op) -> Self {
+        let len = values.len();
+        let size = len.next_power_of_two();
+        let mut nodes = vec![op.identity(); 2 * size];
+        nodes[size..size + len].clone_from_slice(values);
+        for i in (1..size).rev() {
+            nodes[i] = op.combine(&nodes[2 * i], &nodes[2 * i + 1]);
+        }
+        SegmentTree { nodes, size, len, op }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    pub fn get(&self, index: usize) -> &T {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        &self.nodes[self.size + index]
+    }
+    
+    /// Replace the value at `index`, updating its ancestors.
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        let mut i = self.size + index;
+        self.nodes[i] = value;
+        while i > 1 {
+            i /= 2;
+            self.nodes[i] = self.op.combine(&self.nodes[2 * i], &self.nodes[2 * i + 1]);
+        }
+    }
+    
+    /// Combine the values in `range`, in order; the identity for an empty range.
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let Range { start, end } = to_range(range, self.len);
+        let (mut low, mut high) = (start + self.size, end + self.size);
+        // Fold inwards from both ends, keeping left and right results apart so order is preserved
+        let mut left = self.op.identity();
+        let mut right = self.op.identity();
+        while low < high {
+            if low % 2 == 1 {
+                left = self.op.combine(&left, &self.nodes[low]);
+                low += 1;
+            }
+            if high % 2 == 1 {
+                high -= 1;
+                right = self.op.combine(&self.nodes[high], &right);
+            }
+            low /= 2;
+            high /= 2;
+        }
+        self.op.combine(&left, &right)
+    }
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> SegmentTree<T, FnMonoid<T, F>> {
+    /// Build over `values` with an arbitrary monoid: `combine` must be associative
+    /// and `identity` neutral for it.
+    pub fn with_fn(values: &[T], identity: T, combine: F) -> Self {
+        Self::new(values, FnMonoid { identity, combine })
+    }
+}
+
+/// An update waiting to be pushed down to a node's children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pending {
+    None,
+    Add(i64),
+    Assign(i64),
+}
+
+impl Pending {
+    /// The single update equivalent to `self` followed by `next`.
+    fn then(self, next: Pending) -> Pending {
+        match (self, next) {
+            (Pending::Assign(value), Pending::Add(delta)) => Pending::Assign(value + delta),
+            (Pending::Add(a), Pending::Add(b)) => Pending::Add(a + b),
+            (current, Pending::None) => current,
+            (_, next) => next,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Summary {
+    sum: i64,
+    min: i64,
+    max: i64,
+}
+
+/// Problem: Range Update and Range Query (Lazy Segment Tree)
+/// Each node keeps the sum, min and max of its range plus one pending update;
+/// a range update stops at the O(log n) nodes that cover it exactly.
+pub struct LazySegmentTree {
+    summaries: Vec<Summary>,
+    pending: Vec<Pending>,
+    len: usize,
+}
+
+impl LazySegmentTree {
+    pub fn new(values: &[i64]) -> Self {
+        let len = values.len();
+        let empty = Summary { sum: 0, min: i64::MAX, max: i64::MIN };
+        let mut tree = LazySegmentTree {
+            summaries: vec![empty; 4 * len.max(1)],
+            pending: vec![Pending::None; 4 * len.max(1)],
+            len,
+        };
+        if len > 0 {
+            tree.build(1, 0, len, values);
+        }
+        tree
+    }
+    
+    fn build(&mut self, node: usize, low: usize, high: usize, values: &[i64]) {
+        if high - low == 1 {
+            let value = values[low];
+            self.summaries[node] = Summary { sum: value, min: value, max: value };
+            return;
+        }
+        let mid = low + (high - low) / 2;
+        self.build(2 * node, low, mid, values);
+        self.build(2 * node + 1, mid, high, values);
+        self.pull(node);
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    fn pull(&mut self, node: usize) {
+        let (left, right) = (self.summaries[2 * node], self.summaries[2 * node + 1]);
+        self.summaries[node] = Summary {
+            sum: left.sum + right.sum,
+            min: left.min.min(right.min),
+            max: left.max.max(right.max),
+        };
+    }
+    
+    /// Apply `update` to the whole of `node`, which covers `width` values.
+    fn apply(&mut self, node: usize, width: usize, update: Pending) {
+        let summary = &mut self.summaries[node];
+        match update {
+            Pending::None => return,
+            Pending::Add(delta) => {
+                summary.sum += delta * width as i64;
+                summary.min += delta;
+                summary.max += delta;
+            }
+            Pending::Assign(value) => *summary = Summary { sum: value * width as i64, min: value, max: value },
+        }
+        self.pending[node] = self.pending[node].then(update);
+    }
+    
+    fn push(&mut self, node: usize, low: usize, high: usize) {
+        let update = std::mem::replace(&mut self.pending[node], Pending::None);
+        let mid = low + (high - low) / 2;
+        self.apply(2 * node, mid - low, update);
+        self.apply(2 * node + 1, high - mid, update);
+    }
+    
+    fn update(&mut self, node: usize, low: usize, high: usize, range: &Range<usize>, update: Pending) {
+        if range.end <= low || high <= range.start {
+            return;
+        }
+        if range.start <= low && high <= range.end {
+            self.apply(node, high - low, update);
+            return;
+        }
+        self.push(node, low, high);
+        let mid = low + (high - low) / 2;
+        self.update(2 * node, low, mid, range, update);
+        self.update(2 * node + 1, mid, high, range, update);
+        self.pull(node);
+    }
+    
+    fn query(&mut self, node: usize, low: usize, high: usize, range: &Range<usize>) -> Summary {
+        if range.end <= low || high <= range.start {
+            return Summary { sum: 0, min: i64::MAX, max: i64::MIN };
+        }
+        if range.start <= low && high <= range.end {
+            return self.summaries[node];
+        }
+        self.push(node, low, high);
+        let mid = low + (high - low) / 2;
+        let left = self.query(2 * node, low, mid, range);
+        let right = self.query(2 * node + 1, mid, high, range);
+        Summary {
+            sum: left.sum + right.sum,
+            min: left.min.min(right.min),
+            max: left.max.max(right.max),
+        }
+    }
+    
+    fn update_range(&mut self, range: impl RangeBounds<usize>, update: Pending) {
+        let range = to_range(range, self.len);
+        if !range.is_empty() {
+            self.update(1, 0, self.len, &range, update);
+        }
+    }
+    
+    fn summarize(&mut self, range: impl RangeBounds<usize>) -> Option<Summary> {
+        let range = to_range(range, self.len);
+        if range.is_empty() {
+            return None;
+        }
+        Some(self.query(1, 0, self.len, &range))
+    }
+    
+    /// Add `delta` to every value in `range`.
+    pub fn add(&mut self, range: impl RangeBounds<usize>, delta: i64) {
+        self.update_range(range, Pending::Add(delta));
+    }
+    
+    /// Set every value in `range` to `value`.
+    pub fn assign(&mut self, range: impl RangeBounds<usize>, value: i64) {
+        self.update_range(range, Pending::Assign(value));
+    }
+    
+    /// Sum of the values in `range`; 0 for an empty range. Takes `&mut self`
+    /// because answering pushes pending updates further down.
+    pub fn sum(&mut self, range: impl RangeBounds<usize>) -> i64 {
+        self.summarize(range).map_or(0, |summary| summary.sum)
+    }
+    
+    /// Smallest value in `range`, or `None` if it is empty.
+    pub fn min(&mut self, range: impl RangeBounds<usize>) -> Option<i64> {
+        self.summarize(range).map(|summary| summary.min)
+    }
+    
+    /// Largest value in `range`, or `None` if it is empty.
+    pub fn max(&mut self, range: impl RangeBounds<usize>) -> Option<i64> {
+        self.summarize(range).map(|summary| summary.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_monoids() {
+        let values = [5, -2, 7, 0, 3, -8, 4];
+        let sum = SegmentTree::new(&values, Sum);
+        let min = SegmentTree::new(&values, Min);
+        let max = SegmentTree::new(&values, Max);
+        assert_eq!((sum.query(..), min.query(..), max.query(..)), (9, -8, 7));
+        assert_eq!((sum.query(1..4), min.query(1..4), max.query(1..4)), (5, -2, 7));
+        assert_eq!((sum.query(3..3), min.query(3..3)), (0, i32::MAX));
+        assert_eq!(sum.query(6..=6), 4);
+    }
+    
+    #[test]
+    fn test_non_commutative_fn_monoid() {
+        let words: Vec<String> = ["a", "b", "c", "d", "e"].iter().map(|s| s.to_string()).collect();
+        let mut tree = SegmentTree::with_fn(&words, String::new(), |a: &String, b: &String| format!("{}{}", a, b));
+        assert_eq!(tree.query(..), "abcde");
+        assert_eq!(tree.query(1..4), "bcd");
+        tree.set(2, "X".to_string());
+        assert_eq!(tree.query(2..), "Xde");
+        assert_eq!(tree.get(2), "X");
+    }
+    
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_query_out_of_bounds() {
+        SegmentTree::new(&[1, 2, 3], Sum).query(1..4);
+    }
+    
+    #[test]
+    fn test_lazy_assign_then_add() {
+        let mut tree = LazySegmentTree::new(&[1, 2, 3, 4, 5, 6]);
+        tree.assign(1..5, 10);
+        tree.add(3.., -4);
+        // [1, 10, 10, 6, 6, 2]
+        assert_eq!(tree.sum(..), 35);
+        assert_eq!((tree.min(1..4), tree.max(3..)), (Some(6), Some(6)));
+        tree.assign(.., 0);
+        assert_eq!((tree.sum(..), tree.min(..), tree.max(..)), (0, Some(0), Some(0)));
+        assert_eq!(tree.min(2..2), None);
+        
+        let mut empty = LazySegmentTree::new(&[]);
+        empty.add(.., 5);
+        assert_eq!((empty.sum(..), empty.max(..), empty.len()), (0, None, 0));
+    }
+    
+    #[derive(Debug, Clone)]
+    enum Op {
+        Set(usize, i64),
+        Add(usize, usize, i64),
+        Assign(usize, usize, i64),
+        Query(usize, usize),
+    }
+    
+    fn op_strategy() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (0..64usize, -1000..1000i64).prop_map(|(i, v)| Op::Set(i, v)),
+            (0..64usize, 0..64usize, -1000..1000i64).prop_map(|(a, b, v)| Op::Add(a, b, v)),
+            (0..64usize, 0..64usize, -1000..1000i64).prop_map(|(a, b, v)| Op::Assign(a, b, v)),
+            (0..64usize, 0..64usize).prop_map(|(a, b)| Op::Query(a, b)),
+        ]
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_matches_brute_force(mut values in proptest::collection::vec(-1000..1000i64, 1..64), ops in proptest::collection::vec(op_strategy(), 0..200)) {
+            let n = values.len();
+            let mut sums = SegmentTree::new(&values, Sum);
+            let mut mins = SegmentTree::new(&values, Min);
+            let mut lazy = LazySegmentTree::new(&values);
+            for op in ops {
+                match op {
+                    Op::Set(i, value) => {
+                        let i = i % n;
+                        values[i] = value;
+                        sums.set(i, value);
+                        mins.set(i, value);
+                        lazy.assign(i..=i, value);
+                    }
+                    Op::Add(a, b, delta) => {
+                        let (low, high) = ((a % n).min(b % n), (a % n).max(b % n));
+                        values[low..high].iter_mut().for_each(|value| *value += delta);
+                        lazy.add(low..high, delta);
+                        for (i, &value) in values.iter().enumerate().take(high).skip(low) {
+                            sums.set(i, value);
+                            mins.set(i, value);
+                        }
+                    }
+                    Op::Assign(a, b, value) => {
+                        let (low, high) = ((a % n).min(b % n), (a % n).max(b % n));
+                        values[low..high].fill(value);
+                        lazy.assign(low..high, value);
+                        for i in low..high {
+                            sums.set(i, value);
+                            mins.set(i, value);
+                        }
+                    }
+                    Op::Query(a, b) => {
+                        let (low, high) = ((a % n).min(b % n), (a % n).max(b % n) + 1);
+                        let slice = &values[low..high];
+                        prop_assert_eq!(sums.query(low..high), slice.iter().sum::<i64>());
+                        prop_assert_eq!(mins.query(low..high), *slice.iter().min().unwrap());
+                        prop_assert_eq!(lazy.sum(low..high), slice.iter().sum::<i64>());
+                        prop_assert_eq!(lazy.min(low..high), slice.iter().min().copied());
+                        prop_assert_eq!(lazy.max(low..high), slice.iter().max().copied());
+                    }
+                }
+            }
+        }
+    }
+}
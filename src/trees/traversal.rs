@@ -0,0 +1,378 @@
+//! Binary Tree Traversals
+//! 
+//! Preorder, inorder, postorder and level-order traversals of `TreeNode`
+//! trees, each returned as an iterator over node values.
+//! - Recursive: walk the tree up front and iterate over the collected values
+//! - Iterative: lazy iterators driven by an explicit stack or queue
+//! - Morris: O(1) extra space, consuming the tree as it goes
+
+use super::TreeNode;
+use std::collections::VecDeque;
+
+/// Problem: Binary Tree Preorder Traversal (recursive)
+pub fn preorder_recursive(root: &Option<Box<TreeNode>>) -> std::vec::IntoIter<i32> {
+    fn walk(node: &Option<Box<TreeNode>>, out: &mut Vec<i32>) {
+        if let Some(node) = node {
+            out.push(node.val);
+            walk(&node.left, out);
+            walk(&node.right, out);
+        }
+    }
+    let mut out = Vec::new();
+    walk(root, &mut out);
+    out.into_iter()
+}
+
+/// Problem: Binary Tree Inorder Traversal (recursive)
+pub fn inorder_recursive(root: &Option<Box<TreeNode>>) -> std::vec::IntoIter<i32> {
+    fn walk(node: &Option<Box<TreeNode>>, out: &mut Vec<i32>) {
+        if let Some(node) = node {
+            walk(&node.left, out);
+            out.push(node.val);
+            walk(&node.right, out);
+        }
+    }
+    let mut out = Vec::new();
+    walk(root, &mut out);
+    out.into_iter()
+}
+
+/// Problem: Binary Tree Postorder Traversal (recursive)
+pub fn postorder_recursive(root: &Option<Box<TreeNode>>) -> std::vec::IntoIter<i32> {
+    fn walk(node: &Option<Box<TreeNode>>, out: &mut Vec<i32>) {
+        if let Some(node) = node {
+            walk(&node.left, out);
+            walk(&node.right, out);
+            out.push(node.val);
+        }
+    }
+    let mut out = Vec::new();
+    walk(root, &mut out);
+    out.into_iter()
+}
+
+/// Problem: Binary Tree Level Order Traversal
+/// The values on each level, top to bottom, found by a depth-first walk that tracks depth.
+pub fn levels(root: &Option<Box<TreeNode>>) -> Vec<Vec<i32>> {
+    fn walk(node: &Option<Box<TreeNode>>, depth: usize, levels: &mut Vec<Vec<i32>>) {
+        if let Some(node) = node {
+            if levels.len() == depth {
+                levels.push(Vec::new());
+            }
+            levels[depth].push(node.val);
+            walk(&node.left, depth + 1, levels);
+            walk(&node.right, depth + 1, levels);
+        }
+    }
+    let mut levels = Vec::new();
+    walk(root, 0, &mut levels);
+    levels
+}
+
+/// Level-order traversal (recursive): the rows of `levels`, flattened.
+pub fn level_order_recursive(root: &Option<Box<TreeNode>>) -> std::iter::Flatten<std::vec::IntoIter<Vec<i32>>> {
+    levels(root).into_iter().flatten()
+}
+
+/// Lazy preorder traversal with an explicit stack.
+pub struct Preorder<'a> {
+    stack: Vec<&'a TreeNode>,
+}
+
+impl<'a> Preorder<'a> {
+    pub fn new(root: &'a Option<Box<TreeNode>>) -> Self {
+        Preorder {
+            stack: root.as_deref().into_iter().collect(),
+        }
+    }
+}
+
+impl Iterator for Preorder<'_> {
+    type Item = i32;
+    
+    fn next(&mut self) -> Option<i32> {
+        let node = self.stack.pop()?;
+        // Right goes on first so the left subtree is visited first
+        self.stack.extend(node.right.as_deref());
+        self.stack.extend(node.left.as_deref());
+        Some(node.val)
+    }
+}
+
+/// Lazy inorder traversal: the stack holds the path of nodes whose left subtree is being visited.
+pub struct Inorder<'a> {
+    stack: Vec<&'a TreeNode>,
+}
+
+impl<'a> Inorder<'a> {
+    pub fn new(root: &'a Option<Box<TreeNode>>) -> Self {
+        let mut inorder = Inorder { stack: Vec::new() };
+        inorder.push_left_spine(root.as_deref());
+        inorder
+    }
+    
+    fn push_left_spine(&mut self, mut node: Option<&'a TreeNode>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl Iterator for Inorder<'_> {
+    type Item = i32;
+    
+    fn next(&mut self) -> Option<i32> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some(node.val)
+    }
+}
+
+/// Lazy postorder traversal with one stack, remembering the last node emitted
+/// to tell whether a node's right subtree is already done.
+pub struct Postorder<'a> {
+    stack: Vec<&'a TreeNode>,
+    last: Option<&'a TreeNode>,
+}
+
+impl<'a> Postorder<'a> {
+    pub fn new(root: &'a Option<Box<TreeNode>>) -> Self {
+        let mut postorder = Postorder {
+            stack: Vec::new(),
+            last: None,
+        };
+        postorder.push_left_spine(root.as_deref());
+        postorder
+    }
+    
+    fn push_left_spine(&mut self, mut node: Option<&'a TreeNode>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl Iterator for Postorder<'_> {
+    type Item = i32;
+    
+    fn next(&mut self) -> Option<i32> {
+        loop {
+            let &node = self.stack.last()?;
+            match node.right.as_deref() {
+                Some(right) if !self.last.is_some_and(|last| std::ptr::eq(last, right)) => {
+                    self.push_left_spine(Some(right));
+                }
+                _ => {
+                    self.stack.pop();
+                    self.last = Some(node);
+                    return Some(node.val);
+                }
+            }
+        }
+    }
+}
+
+/// Lazy level-order traversal with a queue.
+pub struct LevelOrder<'a> {
+    queue: VecDeque<&'a TreeNode>,
+}
+
+impl<'a> LevelOrder<'a> {
+    pub fn new(root: &'a Option<Box<TreeNode>>) -> Self {
+        LevelOrder {
+            queue: root.as_deref().into_iter().collect(),
+        }
+    }
+}
+
+impl Iterator for LevelOrder<'_> {
+    type Item = i32;
+    
+    fn next(&mut self) -> Option<i32> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(node.left.as_deref());
+        self.queue.extend(node.right.as_deref());
+        Some(node.val)
+    }
+}
+
+/// Morris inorder traversal of an owned tree.
+///
+/// Classic Morris threading points a predecessor's right link back at its
+/// ancestor, which a `Box`-owned tree cannot express. Instead each node with a
+/// left child is rotated right until the current node has none, so the tree
+/// unrolls into its inorder sequence with no stack. Each rotation lengthens the
+/// right spine below the cursor by one node and only emitting shortens it, so
+/// there are fewer than n rotations in total.
+pub struct MorrisInorder {
+    current: Option<Box<TreeNode>>,
+}
+
+impl MorrisInorder {
+    pub fn new(root: Option<Box<TreeNode>>) -> Self {
+        MorrisInorder { current: root }
+    }
+}
+
+impl Iterator for MorrisInorder {
+    type Item = i32;
+    
+    fn next(&mut self) -> Option<i32> {
+        loop {
+            let mut node = self.current.take()?;
+            match node.left.take() {
+                Some(mut left) => {
+                    node.left = left.right.take();
+                    left.right = Some(node);
+                    self.current = Some(left);
+                }
+                None => {
+                    self.current = node.right.take();
+                    return Some(node.val);
+                }
+            }
+        }
+    }
+}
+
+/// Morris preorder traversal of an owned tree.
+///
+/// After emitting a node, its right subtree is hung off the rightmost node of
+/// its left subtree (where Morris threading would have put the thread), and the
+/// traversal continues left. Every node is walked past on such a rightmost
+/// search at most once, so the whole traversal is O(n) with no stack.
+pub struct MorrisPreorder {
+    current: Option<Box<TreeNode>>,
+}
+
+impl MorrisPreorder {
+    pub fn new(root: Option<Box<TreeNode>>) -> Self {
+        MorrisPreorder { current: root }
+    }
+}
+
+impl Iterator for MorrisPreorder {
+    type Item = i32;
+    
+    fn next(&mut self) -> Option<i32> {
+        let mut node = self.current.take()?;
+        self.current = match node.left.take() {
+            Some(mut left) => {
+                let mut rightmost = &mut left;
+                while rightmost.right.is_some() {
+                    rightmost = rightmost.right.as_mut().unwrap();
+                }
+                rightmost.right = node.right.take();
+                Some(left)
+            }
+            None => node.right.take(),
+        };
+        Some(node.val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trees::create_tree;
+    
+    /// Every traversal strategy for one order must agree with `expected`.
+    fn check_all(values: &[Option<i32>], pre: &[i32], ino: &[i32], post: &[i32], level: &[i32]) {
+        let root = create_tree(values);
+        assert_eq!(preorder_recursive(&root).collect::<Vec<_>>(), pre);
+        assert_eq!(Preorder::new(&root).collect::<Vec<_>>(), pre);
+        assert_eq!(MorrisPreorder::new(create_tree(values)).collect::<Vec<_>>(), pre);
+        
+        assert_eq!(inorder_recursive(&root).collect::<Vec<_>>(), ino);
+        assert_eq!(Inorder::new(&root).collect::<Vec<_>>(), ino);
+        assert_eq!(MorrisInorder::new(create_tree(values)).collect::<Vec<_>>(), ino);
+        
+        assert_eq!(postorder_recursive(&root).collect::<Vec<_>>(), post);
+        assert_eq!(Postorder::new(&root).collect::<Vec<_>>(), post);
+        
+        assert_eq!(level_order_recursive(&root).collect::<Vec<_>>(), level);
+        assert_eq!(LevelOrder::new(&root).collect::<Vec<_>>(), level);
+    }
+    
+    #[test]
+    fn test_empty_and_single() {
+        check_all(&[], &[], &[], &[], &[]);
+        check_all(&[Some(7)], &[7], &[7], &[7], &[7]);
+    }
+    
+    #[test]
+    fn test_complete_tree() {
+        //        1
+        //      /   \
+        //     2     3
+        //    / \   / \
+        //   4   5 6   7
+        let values: Vec<_> = (1..=7).map(Some).collect();
+        check_all(
+            &values,
+            &[1, 2, 4, 5, 3, 6, 7],
+            &[4, 2, 5, 1, 6, 3, 7],
+            &[4, 5, 2, 6, 7, 3, 1],
+            &[1, 2, 3, 4, 5, 6, 7],
+        );
+    }
+    
+    #[test]
+    fn test_left_skewed_tree() {
+        // 1 <- 2 <- 3 <- 4 down the left side
+        let values = [Some(1), Some(2), None, Some(3), None, Some(4)];
+        check_all(&values, &[1, 2, 3, 4], &[4, 3, 2, 1], &[4, 3, 2, 1], &[1, 2, 3, 4]);
+    }
+    
+    #[test]
+    fn test_right_skewed_tree() {
+        let values = [Some(1), None, Some(2), None, Some(3), None, Some(4)];
+        check_all(&values, &[1, 2, 3, 4], &[1, 2, 3, 4], &[4, 3, 2, 1], &[1, 2, 3, 4]);
+    }
+    
+    #[test]
+    fn test_tree_with_holes() {
+        //      5
+        //     / \
+        //    3   8
+        //     \  /
+        //     4 6
+        //        \
+        //         7
+        let values = [Some(5), Some(3), Some(8), None, Some(4), Some(6), None, None, None, None, Some(7)];
+        check_all(
+            &values,
+            &[5, 3, 4, 8, 6, 7],
+            &[3, 4, 5, 6, 7, 8],
+            &[4, 3, 7, 6, 8, 5],
+            &[5, 3, 8, 4, 6, 7],
+        );
+        assert_eq!(levels(&create_tree(&values)), vec![vec![5], vec![3, 8], vec![4, 6], vec![7]]);
+    }
+    
+    #[test]
+    fn test_deep_skewed_tree_iterative() {
+        // Deep enough that the lazy iterators must not recurse
+        let mut root = None;
+        for val in (0..5_000).rev() {
+            let mut node = Box::new(TreeNode::new(val));
+            node.right = root;
+            root = Some(node);
+        }
+        assert!(Inorder::new(&root).eq(0..5_000));
+        assert!(Postorder::new(&root).eq((0..5_000).rev()));
+        assert!(LevelOrder::new(&root).eq(0..5_000));
+        assert!(MorrisPreorder::new(root).eq(0..5_000));
+    }
+    
+    #[test]
+    fn test_iterators_are_lazy() {
+        let root = create_tree(&(1..=7).map(Some).collect::<Vec<_>>());
+        let mut inorder = Inorder::new(&root);
+        assert_eq!(inorder.next(), Some(4));
+        assert_eq!(inorder.stack.len(), 2);
+        assert_eq!(inorder.take(2).collect::<Vec<_>>(), vec![2, 5]);
+    }
+}
@@ -0,0 +1,360 @@
+//! Treap
+//! 
+//! A binary search tree on keys that is also a max-heap on random priorities.
+//! The shape is that of a BST built by inserting the keys in random order, so
+//! the expected depth is O(log n) whatever order the keys actually arrive in.
+//! Priorities come from a seedable xorshift generator, so runs are reproducible.
+//! - insert by rotating the new node up to its heap position
+//! - remove by merging the removed node's two subtrees
+//! - `check_invariants` (debug builds only) verifies key order and heap order
+
+use super::ordered_map::OrderedMap;
+use super::visualize::{Sketch, Visualize};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    priority: u64,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+fn priority<K, V>(link: &Link<K, V>) -> Option<u64> {
+    link.as_ref().map(|node| node.priority)
+}
+
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut pivot = node.left.take().expect("rotate_right needs a left child");
+    node.left = pivot.right.take();
+    pivot.right = Some(node);
+    pivot
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut pivot = node.right.take().expect("rotate_left needs a right child");
+    node.right = pivot.left.take();
+    pivot.left = Some(node);
+    pivot
+}
+
+/// Insert a new node (or replace the value of an existing key), rotating the
+/// new node up while its priority beats its parent's.
+fn insert<K: Ord, V>(link: Link<K, V>, fresh: Box<Node<K, V>>) -> (Box<Node<K, V>>, Option<V>) {
+    let Some(mut node) = link else {
+        return (fresh, None);
+    };
+    match fresh.key.cmp(&node.key) {
+        Ordering::Less => {
+            let (left, old) = insert(node.left.take(), fresh);
+            node.left = Some(left);
+            if priority(&node.left) > Some(node.priority) {
+                node = rotate_right(node);
+            }
+            (node, old)
+        }
+        Ordering::Greater => {
+            let (right, old) = insert(node.right.take(), fresh);
+            node.right = Some(right);
+            if priority(&node.right) > Some(node.priority) {
+                node = rotate_left(node);
+            }
+            (node, old)
+        }
+        Ordering::Equal => {
+            let old = std::mem::replace(&mut node.value, fresh.value);
+            (node, Some(old))
+        }
+    }
+}
+
+/// Merge two treaps where every key in `left` is below every key in `right`.
+fn merge<K, V>(left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    match (left, right) {
+        (None, other) | (other, None) => other,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.right = merge(left.right.take(), Some(right));
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take());
+                Some(right)
+            }
+        }
+    }
+}
+
+fn remove<K: Ord, V>(link: &mut Link<K, V>, key: &K) -> Option<V> {
+    let node = link.as_mut()?;
+    match key.cmp(&node.key) {
+        Ordering::Less => remove(&mut node.left, key),
+        Ordering::Greater => remove(&mut node.right, key),
+        Ordering::Equal => {
+            let mut node = link.take().expect("matched above");
+            *link = merge(node.left.take(), node.right.take());
+            Some(node.value)
+        }
+    }
+}
+
+const DEFAULT_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Problem: Treap
+pub struct Treap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+    rng: u64,
+}
+
+impl<K: Ord, V> Treap<K, V> {
+    /// A treap with a fixed default seed.
+    pub fn new() -> Self {
+        Self::with_seed(DEFAULT_SEED)
+    }
+    
+    /// A treap whose priorities are drawn from a generator seeded with `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Treap {
+            root: None,
+            len: 0,
+            // xorshift gets stuck at zero
+            rng: if seed == 0 { DEFAULT_SEED } else { seed },
+        }
+    }
+    
+    fn next_priority(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Height of the tree; 0 when empty.
+    pub fn height(&self) -> usize {
+        fn walk<K, V>(link: &Link<K, V>) -> usize {
+            link.as_ref().map_or(0, |node| 1 + walk(&node.left).max(walk(&node.right)))
+        }
+        walk(&self.root)
+    }
+    
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(&node.value),
+            };
+        }
+        None
+    }
+    
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+    
+    /// Insert or replace, returning the previous value for `key`.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let fresh = Box::new(Node {
+            key,
+            value,
+            priority: self.next_priority(),
+            left: None,
+            right: None,
+        });
+        let (root, old) = insert(self.root.take(), fresh);
+        self.root = Some(root);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+    
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let removed = remove(&mut self.root, key);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+    
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+    
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+    
+    /// Entries in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+    
+    /// Panic unless keys are strictly increasing in order and every node's
+    /// priority is at least its children's. A no-op in release builds.
+    pub fn check_invariants(&self) {
+        fn walk<K: Ord, V>(link: &Link<K, V>, low: Option<&K>, high: Option<&K>) -> usize {
+            let Some(node) = link else { return 0 };
+            assert!(low.is_none_or(|low| *low < node.key), "keys out of order");
+            assert!(high.is_none_or(|high| node.key < *high), "keys out of order");
+            assert!(priority(&node.left).is_none_or(|p| p <= node.priority), "heap order violated");
+            assert!(priority(&node.right).is_none_or(|p| p <= node.priority), "heap order violated");
+            walk(&node.left, low, Some(&node.key)) + walk(&node.right, Some(&node.key), high) + 1
+        }
+        if cfg!(debug_assertions) {
+            assert_eq!(walk(&self.root, None, None), self.len, "stale length");
+        }
+    }
+}
+
+impl<K: Ord, V> Default for Treap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for Treap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut treap = Treap::new();
+        for (key, value) in iter {
+            treap.insert(key, value);
+        }
+        treap
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for Treap<K, V> {
+    type Iter<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+    
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        Treap::insert(self, key, value)
+    }
+    
+    fn get(&self, key: &K) -> Option<&V> {
+        Treap::get(self, key)
+    }
+    
+    fn remove(&mut self, key: &K) -> Option<V> {
+        Treap::remove(self, key)
+    }
+    
+    fn len(&self) -> usize {
+        Treap::len(self)
+    }
+    
+    fn min(&self) -> Option<(&K, &V)> {
+        Treap::min(self)
+    }
+    
+    fn max(&self) -> Option<(&K, &V)> {
+        Treap::max(self)
+    }
+    
+    fn iter(&self) -> Iter<'_, K, V> {
+        Treap::iter(self)
+    }
+}
+
+impl<K: Debug, V> Visualize for Treap<K, V> {
+    fn sketch(&self) -> Option<Sketch> {
+        fn sketch<K: Debug, V>(link: &Link<K, V>) -> Option<Sketch> {
+            let node = link.as_ref()?;
+            Some(Sketch::binary(format!("{:?}", node.key), sketch(&node.left), sketch(&node.right)))
+        }
+        sketch(&self.root)
+    }
+}
+
+/// In-order iterator over a `Treap`.
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left_spine(&mut self, mut node: Option<&'a Node<K, V>>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sorted_input_stays_shallow() {
+        let mut treap = Treap::with_seed(7);
+        for key in 0..2000 {
+            treap.insert(key, ());
+            treap.check_invariants();
+        }
+        assert!(treap.iter().map(|(key, _)| *key).eq(0..2000));
+        // Expected depth is about 3 ln n (~23 here); a plain BST would be 2000 deep
+        assert!(treap.height() < 60, "height {}", treap.height());
+    }
+    
+    #[test]
+    fn test_same_seed_same_shape() {
+        let shape = |seed| {
+            let mut treap = Treap::with_seed(seed);
+            for key in 0..100u32 {
+                treap.insert(key, ());
+            }
+            (treap.height(), treap.root.as_ref().map(|root| root.key))
+        };
+        assert_eq!(shape(42), shape(42));
+        assert_ne!(shape(42), shape(43));
+    }
+    
+    #[test]
+    fn test_insert_replace_remove() {
+        let mut treap: Treap<&str, i32> = [("b", 2), ("a", 1), ("c", 3)].into_iter().collect();
+        assert_eq!(treap.insert("b", 20), Some(2));
+        assert_eq!(treap.len(), 3);
+        assert_eq!(treap.remove(&"b"), Some(20));
+        assert_eq!(treap.remove(&"b"), None);
+        treap.check_invariants();
+        assert_eq!(treap.min(), Some((&"a", &1)));
+        assert_eq!(treap.max(), Some((&"c", &3)));
+        assert_eq!(treap.get(&"c"), Some(&3));
+        assert_eq!(treap.len(), 2);
+    }
+}
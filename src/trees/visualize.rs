@@ -0,0 +1,274 @@
+//! Tree Visualization
+//! 
+//! Render trees as text for debugging: indented ASCII art with branches,
+//! Graphviz DOT, or a Mermaid flowchart. Every tree type in this module
+//! implements `Visualize` by reducing itself to a `Sketch` (a label per node
+//! and its child slots), so the three printers work on all of them.
+//! - `ascii`: one node per line, `|--` and `` `-- `` branches, `nil` for a missing child
+//! - `dot`: a `digraph`, with invisible placeholders keeping left and right children apart
+//! - `mermaid`: a top-down `graph`, labelling the edge of an only child `left` or `right`
+
+use super::TreeNode;
+use std::fmt::Write;
+
+/// A tree reduced to what the printers need. Binary trees always have two
+/// child slots, with `None` for a missing child; multiway trees list only
+/// the children they have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sketch {
+    pub label: String,
+    pub children: Vec<Option<Sketch>>,
+}
+
+impl Sketch {
+    pub fn leaf(label: String) -> Self {
+        Sketch { label, children: Vec::new() }
+    }
+    
+    /// A binary node; a node with neither child gets no slots at all.
+    pub fn binary(label: String, left: Option<Sketch>, right: Option<Sketch>) -> Self {
+        if left.is_none() && right.is_none() {
+            return Sketch::leaf(label);
+        }
+        Sketch { label, children: vec![left, right] }
+    }
+    
+    /// Whether some child slot is empty, so positions matter when drawing.
+    fn has_gap(&self) -> bool {
+        self.children.iter().any(Option::is_none)
+    }
+}
+
+impl Drop for Sketch {
+    /// Free children with an explicit stack; the default recursive drop could
+    /// overflow on the sketch of a degenerate tree.
+    fn drop(&mut self) {
+        let mut stack: Vec<Sketch> = self.children.drain(..).flatten().collect();
+        while let Some(mut sketch) = stack.pop() {
+            stack.extend(sketch.children.drain(..).flatten());
+        }
+    }
+}
+
+/// A tree that can be drawn by `ascii`, `dot` and `mermaid`.
+pub trait Visualize {
+    /// The tree's shape and labels; `None` when it is empty.
+    fn sketch(&self) -> Option<Sketch>;
+}
+
+impl Visualize for Option<Box<TreeNode>> {
+    fn sketch(&self) -> Option<Sketch> {
+        let node = self.as_ref()?;
+        Some(Sketch::binary(node.val.to_string(), node.left.sketch(), node.right.sketch()))
+    }
+}
+
+/// Problem: Print Binary Tree (ASCII)
+/// The root on the first line, then each child indented below its parent,
+/// first child first. An empty tree renders as `nil`. Like the other
+/// printers, it walks the sketch with an explicit stack, so depth is no limit.
+///
+/// ```text
+/// 5
+/// |-- 3
+/// |   |-- nil
+/// |   `-- 4
+/// `-- 8
+/// ```
+pub fn ascii(tree: &impl Visualize) -> String {
+    let Some(sketch) = tree.sketch() else { return "nil\n".to_string() };
+    let mut out = format!("{}\n", sketch.label);
+    let mut indent = String::new();
+    // Each frame is a node being drawn and the index of its next child
+    let mut stack = vec![(&sketch, 0)];
+    while let Some(frame) = stack.last_mut() {
+        let (node, i) = *frame;
+        frame.1 += 1;
+        let Some(child) = node.children.get(i) else {
+            stack.pop();
+            // Every frame but the root's added four characters of indent
+            indent.truncate(indent.len().saturating_sub(4));
+            continue;
+        };
+        let last = i + 1 == node.children.len();
+        let label = child.as_ref().map_or("nil", |child| child.label.as_str());
+        writeln!(out, "{}{}{}", indent, if last { "`-- " } else { "|-- " }, label).unwrap();
+        if let Some(child) = child {
+            indent.push_str(if last { "    " } else { "|   " });
+            stack.push((child, 0));
+        }
+    }
+    out
+}
+
+/// Quote `label` for DOT, which uses C-style escapes inside double quotes.
+fn dot_quote(label: &str) -> String {
+    format!("\"{}\"", label.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Problem: Print Binary Tree (Graphviz DOT)
+/// Nodes are named `n0`, `n1`, ... in preorder. A missing child becomes an
+/// invisible node so Graphviz still places the present one on the correct side.
+pub fn dot(tree: &impl Visualize) -> String {
+    let mut out = String::from("digraph Tree {\n    node [shape=box];\n");
+    if let Some(sketch) = tree.sketch() {
+        writeln!(out, "    n0 [label={}];", dot_quote(&sketch.label)).unwrap();
+        let mut next = 1;
+        // Each frame is a node being drawn, its id and the index of its next child
+        let mut stack = vec![(&sketch, 0, 0)];
+        while let Some(frame) = stack.last_mut() {
+            let (node, id, i) = *frame;
+            frame.2 += 1;
+            let Some(child) = node.children.get(i) else {
+                stack.pop();
+                continue;
+            };
+            let child_id = next;
+            next += 1;
+            match child {
+                Some(child) => {
+                    writeln!(out, "    n{} -> n{};", id, child_id).unwrap();
+                    writeln!(out, "    n{} [label={}];", child_id, dot_quote(&child.label)).unwrap();
+                    stack.push((child, child_id, 0));
+                }
+                None => {
+                    writeln!(out, "    n{} [label=\"\", style=invis];", child_id).unwrap();
+                    writeln!(out, "    n{} -> n{} [style=invis];", id, child_id).unwrap();
+                }
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Problem: Print Binary Tree (Mermaid)
+/// Nodes are named `n0`, `n1`, ... in preorder, declared with their label the
+/// first time they appear. Missing children are left out, so the edge to an
+/// only child says which side it is on.
+pub fn mermaid(tree: &impl Visualize) -> String {
+    fn node(id: usize, label: &str) -> String {
+        format!("n{}[\"{}\"]", id, label.replace('"', "#quot;"))
+    }
+    let mut out = String::from("graph TD\n");
+    if let Some(sketch) = tree.sketch() {
+        writeln!(out, "    {}", node(0, &sketch.label)).unwrap();
+        let mut next = 1;
+        // Each frame is a node being drawn, its id and the index of its next child
+        let mut stack = vec![(&sketch, 0, 0)];
+        while let Some(frame) = stack.last_mut() {
+            let (parent, id, i) = *frame;
+            frame.2 += 1;
+            let Some(child) = parent.children.get(i) else {
+                stack.pop();
+                continue;
+            };
+            let Some(child) = child else { continue };
+            let child_id = next;
+            next += 1;
+            let edge = match (parent.has_gap(), i) {
+                (false, _) => "-->",
+                (true, 0) => "-->|left|",
+                (true, _) => "-->|right|",
+            };
+            writeln!(out, "    n{} {} {}", id, edge, node(child_id, &child.label)).unwrap();
+            stack.push((child, child_id, 0));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trees::avl::AvlTree;
+    use crate::trees::bst::Bst;
+    use crate::trees::btree::BTree;
+    use crate::trees::create_tree;
+    use crate::trees::red_black::LlrbTree;
+    use crate::trees::treap::Treap;
+    
+    fn sample() -> Option<Box<TreeNode>> {
+        create_tree(&[Some(5), Some(3), Some(8), None, Some(4), Some(6), Some(9)])
+    }
+    
+    #[test]
+    fn test_ascii() {
+        let expected = "\
+5
+|-- 3
+|   |-- nil
+|   `-- 4
+`-- 8
+    |-- 6
+    `-- 9
+";
+        assert_eq!(ascii(&sample()), expected);
+        assert_eq!(ascii(&None), "nil\n");
+        assert_eq!(ascii(&create_tree(&[Some(1)])), "1\n");
+    }
+    
+    #[test]
+    fn test_dot() {
+        let expected = "\
+digraph Tree {
+    node [shape=box];
+    n0 [label=\"5\"];
+    n0 -> n1;
+    n1 [label=\"3\"];
+    n2 [label=\"\", style=invis];
+    n1 -> n2 [style=invis];
+    n1 -> n3;
+    n3 [label=\"4\"];
+    n0 -> n4;
+    n4 [label=\"8\"];
+    n4 -> n5;
+    n5 [label=\"6\"];
+    n4 -> n6;
+    n6 [label=\"9\"];
+}
+";
+        assert_eq!(dot(&sample()), expected);
+        assert_eq!(dot(&None), "digraph Tree {\n    node [shape=box];\n}\n");
+        assert_eq!(dot_quote("say \"hi\" \\"), "\"say \\\"hi\\\" \\\\\"");
+    }
+    
+    #[test]
+    fn test_mermaid() {
+        let expected = "\
+graph TD
+    n0[\"5\"]
+    n0 --> n1[\"3\"]
+    n1 -->|right| n2[\"4\"]
+    n0 --> n3[\"8\"]
+    n3 --> n4[\"6\"]
+    n3 --> n5[\"9\"]
+";
+        assert_eq!(mermaid(&sample()), expected);
+        assert_eq!(mermaid(&None), "graph TD\n");
+    }
+    
+    #[test]
+    fn test_every_tree_type() {
+        let keys = [4, 2, 6, 1, 3, 5, 7];
+        let expected = "4\n|-- 2\n|   |-- 1\n|   `-- 3\n`-- 6\n    |-- 5\n    `-- 7\n";
+        let bst: Bst<i32, ()> = keys.iter().map(|&key| (key, ())).collect();
+        assert_eq!(ascii(&bst), expected);
+        let avl: AvlTree<i32, ()> = keys.iter().map(|&key| (key, ())).collect();
+        assert!(ascii(&avl).starts_with("4 (h=3)\n|-- 2 (h=2)\n|   |-- 1 (h=1)\n"));
+        
+        // Red nodes are marked; a red left leaf leaves its parent with one child
+        let llrb: LlrbTree<i32, ()> = (1..=2).map(|key| (key, ())).collect();
+        assert_eq!(ascii(&llrb), "2\n|-- 1 (red)\n`-- nil\n");
+        
+        // Whatever the priorities, every key is drawn exactly once
+        let treap: Treap<i32, ()> = keys.iter().map(|&key| (key, ())).collect();
+        let art = ascii(&treap);
+        assert_eq!(art.lines().filter(|line| !line.ends_with("nil")).count(), keys.len());
+        
+        let btree = BTree::<i32, (), 3>::from_sorted((1..=7).map(|key| (key, ()))).unwrap();
+        assert_eq!(ascii(&btree), "[3 6]\n|-- [1 2]\n|-- [4 5]\n`-- [7]\n");
+        assert!(mermaid(&btree).contains("    n0 --> n3[\"[7]\"]\n"));
+        assert_eq!(ascii(&BTree::<i32, (), 3>::new()), "nil\n");
+    }
+}